@@ -0,0 +1,175 @@
+use std::fmt::Write;
+
+use crate::backend::{
+    ArtifactKind, Backend, BackendKind, RenderRequest, RenderedArtifact, flatten_document,
+    hyphenated_name, leaf_names, table_rows,
+};
+use crate::core::{ColumnMeta, Document};
+use crate::error::{DocpackError, DocpackResult};
+
+pub struct AsciidocBackend;
+
+impl Backend for AsciidocBackend {
+    fn kind(&self) -> BackendKind {
+        BackendKind::Asciidoc
+    }
+
+    fn render(&self, doc: &Document, req: &RenderRequest) -> DocpackResult<RenderedArtifact> {
+        let body = match (req.artifact, req.style.as_str()) {
+            (ArtifactKind::DataModule, "asciidoc-attributes") => render_attributes(doc, req),
+            (ArtifactKind::TableFragment, "asciidoc-table") => render_table(doc, req),
+            _ => Err(DocpackError::Render {
+                backend: req.backend,
+                artifact: req.artifact,
+                detail: format!("unsupported AsciiDoc style '{}'", req.style),
+            }),
+        }?;
+        Ok(RenderedArtifact { body })
+    }
+}
+
+/// Renders one document attribute entry per leaf, named after the root name
+/// and the leaf path, such as `:sales-1-amount: 12.5`.
+fn render_attributes(doc: &Document, req: &RenderRequest) -> DocpackResult<String> {
+    let leaves = flatten_document(doc);
+    let names = leaf_names(&leaves, req, |path| hyphenated_name(&req.root_name, path))?;
+    let mut output = String::new();
+    for (name, (_, value)) in names.iter().zip(&leaves) {
+        let value = multiline(value, " + \\\n");
+        if value.is_empty() {
+            writeln!(output, ":{name}:").map_err(into_render_error(req))?;
+        } else {
+            writeln!(output, ":{name}: {value}").map_err(into_render_error(req))?;
+        }
+    }
+    Ok(output)
+}
+
+/// Renders a `|===` table, one source line per row. Numeric columns are
+/// right-aligned through the `cols` attribute.
+fn render_table(doc: &Document, req: &RenderRequest) -> DocpackResult<String> {
+    let rows = table_rows(doc, req)?;
+    let width = doc.table_width().unwrap_or(0);
+    let columns = doc
+        .meta
+        .tabular_columns
+        .clone()
+        .unwrap_or_else(|| (1..=width).map(|index| index.to_string()).collect());
+    let cols = columns
+        .iter()
+        .map(|column| match doc.meta.column(column) {
+            Some(meta) if meta.kind.is_numeric() => ">1",
+            _ => "1",
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    let mut output = String::new();
+    if doc.meta.tabular_columns.is_some() {
+        writeln!(output, "[%header,cols=\"{cols}\"]").map_err(into_render_error(req))?;
+    } else {
+        writeln!(output, "[cols=\"{cols}\"]").map_err(into_render_error(req))?;
+    }
+    writeln!(output, "|===").map_err(into_render_error(req))?;
+    if let Some(columns) = &doc.meta.tabular_columns {
+        let header = columns
+            .iter()
+            .map(|column| {
+                doc.meta
+                    .column(column)
+                    .map_or_else(|| column.clone(), ColumnMeta::header)
+            })
+            .collect::<Vec<_>>();
+        writeln!(output, "{}", join_row(&header)).map_err(into_render_error(req))?;
+        writeln!(output).map_err(into_render_error(req))?;
+    }
+    for row in rows {
+        writeln!(output, "{}", join_row(&row)).map_err(into_render_error(req))?;
+    }
+    writeln!(output, "|===").map_err(into_render_error(req))?;
+    Ok(output)
+}
+
+fn join_row(cells: &[String]) -> String {
+    cells
+        .iter()
+        .map(|cell| {
+            let lines = cell
+                .lines()
+                .map(|line| {
+                    if line.trim().is_empty() {
+                        "{empty}".to_string()
+                    } else {
+                        cell_line(line)
+                    }
+                })
+                .collect::<Vec<_>>();
+            format!("|{}", lines.join(" +\n"))
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Escapes the cell separator and wraps text that inline formatting,
+/// replacements, or attribute references would change in `pass:c[...]`,
+/// which keeps only special-character escaping.
+fn cell_line(line: &str) -> String {
+    let line = if needs_passthrough(line) {
+        format!("pass:c[{}]", line.replace(']', "\\]"))
+    } else {
+        line.to_string()
+    };
+    line.replace('|', "\\|")
+}
+
+fn needs_passthrough(line: &str) -> bool {
+    line.contains(|ch| "*_`#~^+[]{}'\"(\\".contains(ch))
+        || ["--", "...", "->", "<-", "=>", "<="]
+            .iter()
+            .any(|pattern| line.contains(pattern))
+}
+
+/// Joins the lines of an attribute value with a hard line break, writing
+/// `{empty}` for blank lines, after escaping attribute references.
+fn multiline(value: &str, separator: &str) -> String {
+    escape_references(value)
+        .lines()
+        .map(|line| {
+            if line.trim().is_empty() {
+                "{empty}"
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Backslash-escapes `{name}` so text is never read as an attribute
+/// reference.
+fn escape_references(value: &str) -> String {
+    let mut escaped = String::new();
+    for (index, ch) in value.char_indices() {
+        if ch == '{' && is_reference(&value[index + 1..]) {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+fn is_reference(rest: &str) -> bool {
+    rest.split_once('}').is_some_and(|(name, _)| {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|ch| ch.is_alphanumeric() || matches!(ch, '_' | '-' | ':' | ' '))
+    })
+}
+
+fn into_render_error(req: &RenderRequest) -> impl FnOnce(std::fmt::Error) -> DocpackError + '_ {
+    move |_| DocpackError::Render {
+        backend: req.backend,
+        artifact: req.artifact,
+        detail: "failed to format AsciiDoc output".to_string(),
+    }
+}
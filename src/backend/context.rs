@@ -0,0 +1,159 @@
+use std::fmt::Write;
+
+use crate::backend::{
+    ArtifactKind, Backend, BackendKind, RenderRequest, RenderedArtifact, flatten_document,
+    hyphenated_name, leaf_names, table_rows,
+};
+use crate::core::{ColumnMeta, Document};
+use crate::error::{DocpackError, DocpackResult};
+
+pub struct ContextBackend;
+
+impl Backend for ContextBackend {
+    fn kind(&self) -> BackendKind {
+        BackendKind::Context
+    }
+
+    fn render(&self, doc: &Document, req: &RenderRequest) -> DocpackResult<RenderedArtifact> {
+        let body = match (req.artifact, req.style.as_str()) {
+            (ArtifactKind::DataModule, "context-setvariables") => render_setvariables(doc, req),
+            (ArtifactKind::TableFragment, "context-xtable") => render_xtable(doc, req),
+            (ArtifactKind::TableFragment, "context-natural-table") => {
+                render_natural_table(doc, req)
+            }
+            _ => Err(DocpackError::Render {
+                backend: req.backend,
+                artifact: req.artifact,
+                detail: format!("unsupported ConTeXt style '{}'", req.style),
+            }),
+        }?;
+        Ok(RenderedArtifact { body })
+    }
+}
+
+/// Renders one `\setvariables` block in the `root_name` namespace, one
+/// `key={value}` line per flattened leaf, read back with
+/// `\getvariable{root_name}{key}`. The namespace sits inside brackets as
+/// plain text, so it is limited to ASCII letters, digits, `_`, and `-`.
+fn render_setvariables(doc: &Document, req: &RenderRequest) -> DocpackResult<String> {
+    if req.root_name.is_empty()
+        || !req
+            .root_name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-'))
+    {
+        return Err(DocpackError::Render {
+            backend: req.backend,
+            artifact: req.artifact,
+            detail: format!(
+                "root name '{}' is not a valid \\setvariables namespace; use ASCII letters, digits, '_', or '-'",
+                req.root_name
+            ),
+        });
+    }
+    let leaves = flatten_document(doc);
+    let keys = leaf_names(&leaves, req, |path| hyphenated_name(&path[0], &path[1..]))?;
+    let mut output = String::new();
+    writeln!(output, "\\setvariables[{}][", req.root_name).map_err(into_render_error(req))?;
+    for (key, (_, value)) in keys.iter().zip(&leaves) {
+        writeln!(output, "  {key}={{{}}},", context_escape(value))
+            .map_err(into_render_error(req))?;
+    }
+    writeln!(output, "]").map_err(into_render_error(req))?;
+    Ok(output)
+}
+
+fn render_xtable(doc: &Document, req: &RenderRequest) -> DocpackResult<String> {
+    let rows = table_rows(doc, req)?;
+    let mut output = String::new();
+    writeln!(output, "\\startxtable").map_err(into_render_error(req))?;
+    if let Some(columns) = header_row(doc) {
+        writeln!(output, "\\startxtablehead").map_err(into_render_error(req))?;
+        writeln!(output, "{}", xtable_row(&columns)).map_err(into_render_error(req))?;
+        writeln!(output, "\\stopxtablehead").map_err(into_render_error(req))?;
+    }
+    writeln!(output, "\\startxtablebody").map_err(into_render_error(req))?;
+    for row in rows {
+        writeln!(output, "{}", xtable_row(&row)).map_err(into_render_error(req))?;
+    }
+    writeln!(output, "\\stopxtablebody").map_err(into_render_error(req))?;
+    writeln!(output, "\\stopxtable").map_err(into_render_error(req))?;
+    Ok(output)
+}
+
+fn render_natural_table(doc: &Document, req: &RenderRequest) -> DocpackResult<String> {
+    let rows = table_rows(doc, req)?;
+    let mut output = String::new();
+    writeln!(output, "\\bTABLE").map_err(into_render_error(req))?;
+    if let Some(columns) = header_row(doc) {
+        writeln!(output, "\\bTABLEhead").map_err(into_render_error(req))?;
+        writeln!(output, "{}", natural_row(&columns, "TH")).map_err(into_render_error(req))?;
+        writeln!(output, "\\eTABLEhead").map_err(into_render_error(req))?;
+    }
+    writeln!(output, "\\bTABLEbody").map_err(into_render_error(req))?;
+    for row in rows {
+        writeln!(output, "{}", natural_row(&row, "TD")).map_err(into_render_error(req))?;
+    }
+    writeln!(output, "\\eTABLEbody").map_err(into_render_error(req))?;
+    writeln!(output, "\\eTABLE").map_err(into_render_error(req))?;
+    Ok(output)
+}
+
+fn header_row(doc: &Document) -> Option<Vec<String>> {
+    doc.meta.tabular_columns.as_ref().map(|columns| {
+        columns
+            .iter()
+            .map(|column| {
+                doc.meta
+                    .column(column)
+                    .map_or_else(|| column.clone(), ColumnMeta::header)
+            })
+            .collect()
+    })
+}
+
+fn xtable_row(cells: &[String]) -> String {
+    let cells = cells
+        .iter()
+        .map(|cell| format!("\\startxcell {} \\stopxcell", context_escape(cell)))
+        .collect::<Vec<_>>();
+    format!("\\startxrow {} \\stopxrow", cells.join(" "))
+}
+
+fn natural_row(cells: &[String], tag: &str) -> String {
+    let cells = cells
+        .iter()
+        .map(|cell| format!("\\b{tag} {} \\e{tag}", context_escape(cell)))
+        .collect::<Vec<_>>();
+    format!("\\bTR {} \\eTR", cells.join(" "))
+}
+
+fn context_escape(value: &str) -> String {
+    let mut escaped = String::new();
+    for ch in value.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\letterbackslash{}"),
+            '{' => escaped.push_str("\\{"),
+            '}' => escaped.push_str("\\}"),
+            '$' => escaped.push_str("\\$"),
+            '&' => escaped.push_str("\\&"),
+            '%' => escaped.push_str("\\%"),
+            '#' => escaped.push_str("\\#"),
+            '_' => escaped.push_str("\\_"),
+            '^' => escaped.push_str("\\letterhat{}"),
+            '~' => escaped.push_str("\\lettertilde{}"),
+            '|' => escaped.push_str("\\letterbar{}"),
+            '\n' => escaped.push(' '),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn into_render_error(req: &RenderRequest) -> impl FnOnce(std::fmt::Error) -> DocpackError + '_ {
+    move |_| DocpackError::Render {
+        backend: req.backend,
+        artifact: req.artifact,
+        detail: "failed to format ConTeXt output".to_string(),
+    }
+}
@@ -0,0 +1,349 @@
+use std::fmt::{Display, Formatter};
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer};
+
+use crate::core::{Date, Time, Value};
+
+/// Number conventions of a language or region, written as a BCP 47 tag such
+/// as `en`, `de`, `fr-CA`, or `de-CH`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Locale {
+    tag: String,
+    decimal: &'static str,
+    group: &'static str,
+    percent: &'static str,
+}
+
+impl Locale {
+    /// Resolves a locale tag, or `None` when its language is not supported.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let mut parts = tag.split(['-', '_']);
+        let language = parts.next()?.to_ascii_lowercase();
+        let region = parts.next().map(str::to_ascii_uppercase);
+        let (decimal, group, percent) = match (language.as_str(), region.as_deref()) {
+            ("de" | "it" | "fr" | "rm", Some("CH" | "LI")) => (".", "\u{2019}", "%"),
+            ("es", Some("MX" | "US")) => (".", ",", "%"),
+            ("en" | "ja" | "zh" | "ko" | "he" | "th" | "hi" | "ga", _) => (".", ",", "%"),
+            ("fr", _) => (",", "\u{202f}", "\u{202f}%"),
+            (
+                "de" | "es" | "it" | "nl" | "pt" | "da" | "id" | "tr" | "el" | "ro" | "sl" | "hr",
+                _,
+            ) => (",", ".", "\u{a0}%"),
+            (
+                "ru" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "nn" | "no" | "uk" | "hu" | "bg"
+                | "lt" | "lv" | "et",
+                _,
+            ) => (",", "\u{a0}", "\u{a0}%"),
+            _ => return None,
+        };
+        Some(Self {
+            tag: tag.to_string(),
+            decimal,
+            group,
+            percent,
+        })
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::parse("en").expect("en is a supported locale")
+    }
+}
+
+impl<'de> Deserialize<'de> for Locale {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tag = String::deserialize(deserializer)?;
+        Self::parse(&tag).ok_or_else(|| D::Error::custom(format!("unsupported locale '{tag}'")))
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.tag)
+    }
+}
+
+/// Display rules for the cells of one table column.
+///
+/// Number rules apply to integer, float, and decimal cells; `date` applies to
+/// date and datetime cells; `null` replaces empty cells. Other cells keep
+/// their plain text.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ColumnFormat {
+    /// Fixed number of fraction digits, rounding half away from zero.
+    pub decimals: Option<u32>,
+    /// Group integer digits with the locale's thousands separator.
+    #[serde(default)]
+    pub thousands: bool,
+    /// Multiply by 100 and append the locale's percent sign.
+    #[serde(default)]
+    pub percent: bool,
+    /// Text placed before a formatted number, such as a currency symbol.
+    pub prefix: Option<String>,
+    /// Text placed after a formatted number.
+    pub suffix: Option<String>,
+    /// Pattern for date and datetime cells; see [`ColumnFormat::validate`].
+    pub date: Option<String>,
+    /// Text shown for null cells instead of an empty cell.
+    pub null: Option<String>,
+}
+
+impl ColumnFormat {
+    /// Checks that the date pattern only uses supported tokens: `%Y`, `%y`,
+    /// `%m`, `%-m`, `%d`, `%-d`, `%H`, `%M`, `%S`, and `%%`.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(pattern) = &self.date {
+            render_date_pattern(pattern, None, None)?;
+        }
+        Ok(())
+    }
+
+    /// Formats one scalar cell, or returns `None` for nested values.
+    pub fn apply(&self, value: &Value, locale: &Locale) -> Option<String> {
+        let numeric_rules = self.decimals.is_some()
+            || self.thousands
+            || self.percent
+            || self.prefix.is_some()
+            || self.suffix.is_some();
+        match value.as_typed() {
+            Value::Null => Some(self.null.clone().unwrap_or_default()),
+            Value::Integer(number) if numeric_rules => {
+                Some(self.format_number(&number.to_string(), locale))
+            }
+            Value::Float(number) if numeric_rules && number.is_finite() => {
+                Some(self.format_number(&number.to_string(), locale))
+            }
+            Value::Decimal(text) if numeric_rules => Some(self.format_number(text, locale)),
+            Value::Date(date) if self.date.is_some() => {
+                render_date_pattern(self.date.as_deref()?, Some(date), None).ok()
+            }
+            Value::DateTime(datetime) if self.date.is_some() => render_date_pattern(
+                self.date.as_deref()?,
+                Some(&datetime.date),
+                Some(&datetime.time),
+            )
+            .ok(),
+            _ => value.scalar_text(),
+        }
+    }
+
+    fn format_number(&self, text: &str, locale: &Locale) -> String {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        let mut integer = integer.to_string();
+        let mut fraction = fraction.to_string();
+        if self.percent {
+            let shifted = fraction.chars().chain(std::iter::repeat('0')).take(2);
+            integer.extend(shifted);
+            fraction = fraction.chars().skip(2).collect();
+        }
+        if let Some(places) = self.decimals {
+            (integer, fraction) = round_digits(&integer, &fraction, places as usize);
+        }
+        let integer = integer.trim_start_matches('0');
+        let integer = if integer.is_empty() { "0" } else { integer };
+        let is_zero = integer == "0" && fraction.bytes().all(|byte| byte == b'0');
+
+        let mut output = String::new();
+        if negative && !is_zero {
+            output.push('-');
+        }
+        output.push_str(self.prefix.as_deref().unwrap_or(""));
+        if self.thousands {
+            output.push_str(&group_digits(integer, locale.group));
+        } else {
+            output.push_str(integer);
+        }
+        if !fraction.is_empty() {
+            output.push_str(locale.decimal);
+            output.push_str(&fraction);
+        }
+        if self.percent {
+            output.push_str(locale.percent);
+        }
+        output.push_str(self.suffix.as_deref().unwrap_or(""));
+        output
+    }
+}
+
+/// Rounds a decimal digit string to `places` fraction digits, half away from
+/// zero, padding with zeros when it has fewer.
+fn round_digits(integer: &str, fraction: &str, places: usize) -> (String, String) {
+    let mut digits = integer
+        .bytes()
+        .chain(fraction.bytes().chain(std::iter::repeat(b'0')).take(places))
+        .collect::<Vec<_>>();
+    if fraction
+        .as_bytes()
+        .get(places)
+        .is_some_and(|digit| *digit >= b'5')
+    {
+        let mut index = digits.len();
+        loop {
+            if index == 0 {
+                digits.insert(0, b'1');
+                break;
+            }
+            index -= 1;
+            if digits[index] == b'9' {
+                digits[index] = b'0';
+            } else {
+                digits[index] += 1;
+                break;
+            }
+        }
+    }
+    let split = digits.len() - places;
+    let text = String::from_utf8(digits).expect("ASCII digits");
+    (text[..split].to_string(), text[split..].to_string())
+}
+
+fn group_digits(integer: &str, separator: &str) -> String {
+    let mut grouped = String::new();
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index).is_multiple_of(3) {
+            grouped.push_str(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+fn render_date_pattern(
+    pattern: &str,
+    date: Option<&Date>,
+    time: Option<&Time>,
+) -> Result<String, String> {
+    let zero_time = Time {
+        hour: 0,
+        minute: 0,
+        second: 0,
+        nanosecond: 0,
+    };
+    let time = time.unwrap_or(&zero_time);
+    let date = date.copied().unwrap_or(Date {
+        year: 1970,
+        month: 1,
+        day: 1,
+    });
+    let mut output = String::new();
+    let mut chars = pattern.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            output.push(ch);
+            continue;
+        }
+        let token = match chars.next() {
+            Some('-') => format!("-{}", chars.next().unwrap_or(' ')),
+            Some(other) => other.to_string(),
+            None => String::new(),
+        };
+        match token.as_str() {
+            "Y" => output.push_str(&format!("{:04}", date.year)),
+            "y" => output.push_str(&format!("{:02}", date.year.rem_euclid(100))),
+            "m" => output.push_str(&format!("{:02}", date.month)),
+            "-m" => output.push_str(&date.month.to_string()),
+            "d" => output.push_str(&format!("{:02}", date.day)),
+            "-d" => output.push_str(&date.day.to_string()),
+            "H" => output.push_str(&format!("{:02}", time.hour)),
+            "M" => output.push_str(&format!("{:02}", time.minute)),
+            "S" => output.push_str(&format!("{:02}", time.second)),
+            "%" => output.push('%'),
+            other => {
+                return Err(format!(
+                    "unsupported date pattern token '%{other}'; use %Y, %y, %m, %-m, %d, %-d, %H, %M, %S, or %%"
+                ));
+            }
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ColumnFormat, Locale};
+    use crate::core::{Date, Value};
+
+    fn format(rules: ColumnFormat, locale: &str, value: Value) -> String {
+        rules
+            .apply(&value, &Locale::parse(locale).unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn rounds_groups_and_decorates_numbers() {
+        let money = ColumnFormat {
+            decimals: Some(2),
+            thousands: true,
+            prefix: Some("$".to_string()),
+            ..ColumnFormat::default()
+        };
+        assert_eq!(
+            format(money.clone(), "en", Value::Float(1234567.5)),
+            "$1,234,567.50"
+        );
+        assert_eq!(format(money.clone(), "en", Value::Float(-0.004)), "$0.00");
+        assert_eq!(
+            format(money, "en", Value::Decimal("999.995".to_string())),
+            "$1,000.00"
+        );
+
+        let euro = ColumnFormat {
+            decimals: Some(2),
+            thousands: true,
+            suffix: Some("\u{a0}€".to_string()),
+            ..ColumnFormat::default()
+        };
+        assert_eq!(
+            format(euro, "de", Value::Integer(-1234)),
+            "-1.234,00\u{a0}€"
+        );
+    }
+
+    #[test]
+    fn formats_percentages_per_locale() {
+        let percent = ColumnFormat {
+            decimals: Some(1),
+            percent: true,
+            ..ColumnFormat::default()
+        };
+        assert_eq!(
+            format(percent.clone(), "en", Value::Float(0.30000000000000004)),
+            "30.0%"
+        );
+        assert_eq!(format(percent, "fr", Value::Float(0.125)), "12,5\u{202f}%");
+    }
+
+    #[test]
+    fn formats_dates_and_null_fallbacks() {
+        let rules = ColumnFormat {
+            date: Some("%-d.%-m.%Y".to_string()),
+            null: Some("n/a".to_string()),
+            ..ColumnFormat::default()
+        };
+        assert_eq!(
+            format(
+                rules.clone(),
+                "de",
+                Value::Date(Date::new(2025, 3, 7).unwrap())
+            ),
+            "7.3.2025"
+        );
+        assert_eq!(format(rules.clone(), "de", Value::Null), "n/a");
+        assert_eq!(format(rules, "de", Value::Float(0.5)), "0.5");
+
+        let invalid = ColumnFormat {
+            date: Some("%Q".to_string()),
+            ..ColumnFormat::default()
+        };
+        assert!(invalid.validate().unwrap_err().contains("'%Q'"));
+        assert!(Locale::parse("xx").is_none());
+        assert_eq!(Locale::parse("de-CH").unwrap().group, "\u{2019}");
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt::Write;
+
+use crate::backend::{
+    ArtifactKind, Backend, BackendKind, RenderRequest, RenderedArtifact, find_non_finite,
+    table_rows,
+};
+use crate::core::{ColumnMeta, Document};
+use crate::error::{DocpackError, DocpackResult};
+
+pub struct HtmlBackend;
+
+impl Backend for HtmlBackend {
+    fn kind(&self) -> BackendKind {
+        BackendKind::Html
+    }
+
+    fn render(&self, doc: &Document, req: &RenderRequest) -> DocpackResult<RenderedArtifact> {
+        let body = match (req.artifact, req.style.as_str()) {
+            (ArtifactKind::DataModule, "html-json-script") => render_json_script(doc, req),
+            (ArtifactKind::TableFragment, "html-table") => render_table(doc, req),
+            _ => Err(DocpackError::Render {
+                backend: req.backend,
+                artifact: req.artifact,
+                detail: format!("unsupported HTML style '{}'", req.style),
+            }),
+        }?;
+        Ok(RenderedArtifact { body })
+    }
+}
+
+/// Renders the document as pretty-printed JSON inside a non-executing
+/// `<script>` element whose `id` is the root name.
+fn render_json_script(doc: &Document, req: &RenderRequest) -> DocpackResult<String> {
+    if let Some((path, value)) = find_non_finite(&doc.root, String::new()) {
+        return Err(DocpackError::Render {
+            backend: req.backend,
+            artifact: req.artifact,
+            detail: format!(
+                "JSON has no spelling for non-finite float {value} at {}; set non_finite to \"null\"",
+                if path.is_empty() { "/" } else { &path }
+            ),
+        });
+    }
+    let json = serde_json::to_string_pretty(&doc.root.to_json()).map_err(|error| {
+        DocpackError::Render {
+            backend: req.backend,
+            artifact: req.artifact,
+            detail: format!("failed to serialize JSON payload: {error}"),
+        }
+    })?;
+    let mut output = String::new();
+    writeln!(
+        output,
+        "<script type=\"application/json\" id=\"{}\">",
+        html_escape(&req.root_name)
+    )
+    .map_err(into_render_error(req))?;
+    // `<` only appears inside JSON strings, where `<` is an equivalent
+    // spelling that cannot close the script element early.
+    writeln!(output, "{}", json.replace('<', "\\u003c")).map_err(into_render_error(req))?;
+    writeln!(output, "</script>").map_err(into_render_error(req))?;
+    Ok(output)
+}
+
+/// Renders a `<table>` with a `<thead>` when the source has a header and one
+/// `<tbody>` row per record. `css_class` becomes the table's `class`.
+fn render_table(doc: &Document, req: &RenderRequest) -> DocpackResult<String> {
+    let rows = table_rows(doc, req)?;
+    let mut output = String::new();
+    match &req.options.css_class {
+        Some(class) => writeln!(output, "<table class=\"{}\">", html_escape(class)),
+        None => writeln!(output, "<table>"),
+    }
+    .map_err(into_render_error(req))?;
+    if let Some(columns) = &doc.meta.tabular_columns {
+        writeln!(output, "  <thead>").map_err(into_render_error(req))?;
+        write!(output, "    <tr>").map_err(into_render_error(req))?;
+        for column in columns {
+            let header = doc
+                .meta
+                .column(column)
+                .map_or_else(|| column.clone(), ColumnMeta::header);
+            write!(output, "<th>{}</th>", html_cell(&header)).map_err(into_render_error(req))?;
+        }
+        writeln!(output, "</tr>").map_err(into_render_error(req))?;
+        writeln!(output, "  </thead>").map_err(into_render_error(req))?;
+    }
+    writeln!(output, "  <tbody>").map_err(into_render_error(req))?;
+    for row in rows {
+        write!(output, "    <tr>").map_err(into_render_error(req))?;
+        for cell in row {
+            write!(output, "<td>{}</td>", html_cell(&cell)).map_err(into_render_error(req))?;
+        }
+        writeln!(output, "</tr>").map_err(into_render_error(req))?;
+    }
+    writeln!(output, "  </tbody>").map_err(into_render_error(req))?;
+    writeln!(output, "</table>").map_err(into_render_error(req))?;
+    Ok(output)
+}
+
+/// Escapes cell text and turns line breaks into `<br>`.
+fn html_cell(value: &str) -> String {
+    html_escape(&value.replace("\r\n", "\n")).replace('\n', "<br>")
+}
+
+fn html_escape(value: &str) -> String {
+    let mut escaped = String::new();
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn into_render_error(req: &RenderRequest) -> impl FnOnce(std::fmt::Error) -> DocpackError + '_ {
+    move |_| DocpackError::Render {
+        backend: req.backend,
+        artifact: req.artifact,
+        detail: "failed to format HTML output".to_string(),
+    }
+}
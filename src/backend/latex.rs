@@ -1,7 +1,10 @@
 use std::fmt::Write;
 
-use crate::backend::{ArtifactKind, Backend, BackendKind, RenderRequest, RenderedArtifact};
-use crate::core::{Document, Value};
+use crate::backend::{
+    ArtifactKind, Backend, BackendKind, RenderRequest, RenderedArtifact, flatten_document,
+    table_rows,
+};
+use crate::core::{ColumnMeta, Document};
 use crate::error::{DocpackError, DocpackResult};
 
 pub struct LatexBackend;
@@ -73,18 +76,18 @@ fn render_classic_data_module(doc: &Document, req: &RenderRequest) -> DocpackRes
 }
 
 fn render_booktabs_longtable(doc: &Document, req: &RenderRequest) -> DocpackResult<String> {
-    let rows = collect_table_rows(doc, req)?;
+    let rows = table_rows(doc, req)?;
     let width = doc.table_width().unwrap_or(0);
     let mut output = String::new();
     writeln!(output, "\\begin{{longtable}}{{{}}}", "l".repeat(width))
         .map_err(into_render_error(req))?;
-    if let Some(columns) = &doc.meta.tabular_columns {
+    if let Some(columns) = header_row(doc) {
         writeln!(output, "\\toprule").map_err(into_render_error(req))?;
-        writeln!(output, "{} \\\\", join_latex_row(columns)).map_err(into_render_error(req))?;
+        writeln!(output, "{} \\\\", join_latex_row(&columns)).map_err(into_render_error(req))?;
         writeln!(output, "\\midrule").map_err(into_render_error(req))?;
         writeln!(output, "\\endfirsthead").map_err(into_render_error(req))?;
         writeln!(output, "\\toprule").map_err(into_render_error(req))?;
-        writeln!(output, "{} \\\\", join_latex_row(columns)).map_err(into_render_error(req))?;
+        writeln!(output, "{} \\\\", join_latex_row(&columns)).map_err(into_render_error(req))?;
         writeln!(output, "\\midrule").map_err(into_render_error(req))?;
         writeln!(output, "\\endhead").map_err(into_render_error(req))?;
     }
@@ -97,13 +100,13 @@ fn render_booktabs_longtable(doc: &Document, req: &RenderRequest) -> DocpackResu
 }
 
 fn render_plain_tabular(doc: &Document, req: &RenderRequest) -> DocpackResult<String> {
-    let rows = collect_table_rows(doc, req)?;
+    let rows = table_rows(doc, req)?;
     let width = doc.table_width().unwrap_or(0);
     let mut output = String::new();
     writeln!(output, "\\begin{{tabular}}{{{}}}", "l".repeat(width))
         .map_err(into_render_error(req))?;
-    if let Some(columns) = &doc.meta.tabular_columns {
-        writeln!(output, "{} \\\\", join_latex_row(columns)).map_err(into_render_error(req))?;
+    if let Some(columns) = header_row(doc) {
+        writeln!(output, "{} \\\\", join_latex_row(&columns)).map_err(into_render_error(req))?;
         writeln!(output, "\\hline").map_err(into_render_error(req))?;
     }
     for row in rows {
@@ -113,129 +116,19 @@ fn render_plain_tabular(doc: &Document, req: &RenderRequest) -> DocpackResult<St
     Ok(output)
 }
 
-fn collect_table_rows(doc: &Document, req: &RenderRequest) -> DocpackResult<Vec<Vec<String>>> {
-    match &doc.root {
-        Value::List(rows) => {
-            rows.iter()
-                .map(|row| match row {
-                    Value::Object(values) => {
-                        let columns = doc.meta.tabular_columns.as_ref().ok_or_else(|| {
-                            DocpackError::Render {
-                                backend: req.backend,
-                                artifact: req.artifact,
-                                detail: "record-shaped table data requires tabular columns"
-                                    .to_string(),
-                            }
-                        })?;
-                        columns
-                            .iter()
-                            .map(|column| {
-                                render_scalar_cell(values.get(column).unwrap_or(&Value::Null), req)
-                            })
-                            .collect()
-                    }
-                    Value::List(values) => values
-                        .iter()
-                        .map(|value| render_scalar_cell(value, req))
-                        .collect(),
-                    _ => Err(DocpackError::Render {
-                        backend: req.backend,
-                        artifact: req.artifact,
-                        detail: "table-fragment expects rows as objects or lists".to_string(),
-                    }),
-                })
-                .collect()
-        }
-        _ => Err(DocpackError::Render {
-            backend: req.backend,
-            artifact: req.artifact,
-            detail: "table-fragment expects a list root".to_string(),
-        }),
-    }
-}
-
-fn render_scalar_cell(value: &Value, req: &RenderRequest) -> DocpackResult<String> {
-    value.scalar_text().ok_or_else(|| DocpackError::Render {
-        backend: req.backend,
-        artifact: req.artifact,
-        detail: "table-fragment only accepts scalar cell values".to_string(),
+fn header_row(doc: &Document) -> Option<Vec<String>> {
+    doc.meta.tabular_columns.as_ref().map(|columns| {
+        columns
+            .iter()
+            .map(|column| {
+                doc.meta
+                    .column(column)
+                    .map_or_else(|| column.clone(), ColumnMeta::header)
+            })
+            .collect()
     })
 }
 
-fn flatten_value(
-    value: &Value,
-    path: &mut Vec<String>,
-    out: &mut Vec<(Vec<String>, String)>,
-    parent_is_list: bool,
-) {
-    match value {
-        Value::Null => out.push((leaf_path(path), "none".to_string())),
-        Value::Bool(value) => out.push((leaf_path(path), value.to_string())),
-        Value::Integer(value) => out.push((leaf_path(path), value.to_string())),
-        Value::Float(value) => out.push((leaf_path(path), value.to_string())),
-        Value::String(value) => out.push((leaf_path(path), value.clone())),
-        Value::List(values) => {
-            for (index, value) in values.iter().enumerate() {
-                path.push((index + 1).to_string());
-                flatten_value(value, path, out, true);
-                path.pop();
-            }
-            if !parent_is_list {
-                let mut len_path = path.clone();
-                len_path.push("__len__".to_string());
-                out.push((len_path, values.len().to_string()));
-            }
-        }
-        Value::Object(values) => {
-            for (key, value) in values {
-                path.push(key.clone());
-                flatten_value(value, path, out, false);
-                path.pop();
-            }
-        }
-    }
-}
-
-fn leaf_path(path: &[String]) -> Vec<String> {
-    if path.is_empty() {
-        vec!["value".to_string()]
-    } else {
-        path.to_vec()
-    }
-}
-
-fn flatten_document(doc: &Document) -> Vec<(Vec<String>, String)> {
-    match (&doc.root, doc.meta.tabular_columns.as_ref()) {
-        (Value::List(rows), Some(columns)) if doc.is_tabular() => {
-            let mut out = Vec::new();
-            for (row_index, row) in rows.iter().enumerate() {
-                let row_number = (row_index + 1).to_string();
-                match row {
-                    Value::Object(values) => {
-                        for column in columns {
-                            if let Some(value) = values.get(column) {
-                                let mut path = vec![row_number.clone(), column.clone()];
-                                flatten_value(value, &mut path, &mut out, false);
-                            }
-                        }
-                    }
-                    _ => {
-                        let mut path = vec![row_number];
-                        flatten_value(row, &mut path, &mut out, true);
-                    }
-                }
-            }
-            out.push((vec!["__len__".to_string()], rows.len().to_string()));
-            out
-        }
-        _ => {
-            let mut out = Vec::new();
-            flatten_value(&doc.root, &mut Vec::new(), &mut out, false);
-            out
-        }
-    }
-}
-
 fn join_latex_row(cells: &[String]) -> String {
     cells
         .iter()
@@ -298,15 +191,15 @@ fn into_render_error(req: &RenderRequest) -> impl FnOnce(std::fmt::Error) -> Doc
 
 #[cfg(test)]
 mod tests {
-    use std::collections::BTreeMap;
+    use indexmap::IndexMap;
 
     use super::LatexBackend;
-    use crate::backend::{ArtifactKind, Backend, BackendKind, RenderRequest};
+    use crate::backend::{ArtifactKind, Backend, BackendKind, RenderOptions, RenderRequest};
     use crate::core::{Document, Origin, SourceFormat, SourceMeta, TopLevelShape, Value};
 
     #[test]
     fn renders_latex_expl3_module() {
-        let mut map = BTreeMap::new();
+        let mut map = IndexMap::new();
         map.insert("active".to_string(), Value::Bool(true));
         map.insert("age".to_string(), Value::Integer(30));
         map.insert("name".to_string(), Value::String("Alice".to_string()));
@@ -319,6 +212,7 @@ mod tests {
                 top_level_shape: TopLevelShape::Object,
                 tabular_columns: None,
                 header_present: None,
+                columns: Vec::new(),
             },
         };
         let req = RenderRequest {
@@ -326,6 +220,7 @@ mod tests {
             artifact: ArtifactKind::DataModule,
             style: "latex-expl3".to_string(),
             root_name: "data".to_string(),
+            options: RenderOptions::default(),
         };
         let rendered = LatexBackend.render(&doc, &req).unwrap();
         assert!(rendered.body.contains("\\prop_new:N \\g_docpack_data_prop"));
@@ -334,9 +229,9 @@ mod tests {
 
     #[test]
     fn renders_latex_classic_macro_module() {
-        let mut profile = BTreeMap::new();
+        let mut profile = IndexMap::new();
         profile.insert("name".to_string(), Value::String("Alice".to_string()));
-        let mut root = BTreeMap::new();
+        let mut root = IndexMap::new();
         root.insert("profile".to_string(), Value::Object(profile));
         let doc = Document {
             source_id: "data".to_string(),
@@ -347,6 +242,7 @@ mod tests {
                 top_level_shape: TopLevelShape::Object,
                 tabular_columns: None,
                 header_present: None,
+                columns: Vec::new(),
             },
         };
         let req = RenderRequest {
@@ -354,6 +250,7 @@ mod tests {
             artifact: ArtifactKind::DataModule,
             style: "latex-classic-macro".to_string(),
             root_name: "data".to_string(),
+            options: RenderOptions::default(),
         };
         let rendered = LatexBackend.render(&doc, &req).unwrap();
         assert_eq!(
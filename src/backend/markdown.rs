@@ -0,0 +1,213 @@
+use std::fmt::Write;
+
+use crate::backend::{
+    ArtifactKind, Backend, BackendKind, RenderRequest, RenderedArtifact, table_rows,
+};
+use crate::core::{ColumnMeta, Document, Value};
+use crate::error::{DocpackError, DocpackResult};
+
+pub struct MarkdownBackend;
+
+impl Backend for MarkdownBackend {
+    fn kind(&self) -> BackendKind {
+        BackendKind::Markdown
+    }
+
+    fn render(&self, doc: &Document, req: &RenderRequest) -> DocpackResult<RenderedArtifact> {
+        let body = match (req.artifact, req.style.as_str()) {
+            (ArtifactKind::DataModule, "markdown-front-matter") => render_front_matter(doc, req),
+            (ArtifactKind::TableFragment, "markdown-gfm") => render_gfm_table(doc, req),
+            _ => Err(DocpackError::Render {
+                backend: req.backend,
+                artifact: req.artifact,
+                detail: format!("unsupported Markdown style '{}'", req.style),
+            }),
+        }?;
+        Ok(RenderedArtifact { body })
+    }
+}
+
+/// Renders the document as a YAML front-matter block holding one key, the
+/// root name, so static-site generators expose it as page metadata.
+fn render_front_matter(doc: &Document, req: &RenderRequest) -> DocpackResult<String> {
+    let mut output = String::new();
+    writeln!(output, "---").map_err(into_render_error(req))?;
+    write_entry(&mut output, 0, &req.root_name, &doc.root);
+    writeln!(output, "---").map_err(into_render_error(req))?;
+    Ok(output)
+}
+
+fn write_entry(output: &mut String, indent: usize, key: &str, value: &Value) {
+    let pad = " ".repeat(indent);
+    match yaml_scalar(value) {
+        Some(scalar) => output.push_str(&format!("{pad}{}: {scalar}\n", yaml_key(key))),
+        None => {
+            output.push_str(&format!("{pad}{}:\n", yaml_key(key)));
+            write_block(output, indent + 2, value);
+        }
+    }
+}
+
+/// Writes a non-empty list or object in YAML block style.
+fn write_block(output: &mut String, indent: usize, value: &Value) {
+    match value {
+        Value::Lexical { value, .. } => write_block(output, indent, value),
+        Value::Object(values) => {
+            for (key, value) in values {
+                write_entry(output, indent, key, value);
+            }
+        }
+        Value::List(values) => {
+            let pad = " ".repeat(indent);
+            for value in values {
+                match yaml_scalar(value) {
+                    Some(scalar) => output.push_str(&format!("{pad}- {scalar}\n")),
+                    None => {
+                        let mut item = String::new();
+                        write_block(&mut item, indent + 2, value);
+                        output.push_str(&pad);
+                        output.push_str("- ");
+                        output.push_str(&item[indent + 2..]);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the inline YAML spelling of a value, or `None` for a non-empty list
+/// or object that needs block style. Strings are always double-quoted so text
+/// such as `yes`, `1.0`, or `a: b` keeps its meaning.
+fn yaml_scalar(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(value) => value.to_string(),
+        Value::Integer(value) => value.to_string(),
+        Value::Float(value) if value.is_nan() => ".nan".to_string(),
+        Value::Float(value) if value.is_infinite() => {
+            if value.is_sign_negative() {
+                "-.inf".to_string()
+            } else {
+                ".inf".to_string()
+            }
+        }
+        Value::Float(value) => value.to_string(),
+        Value::Decimal(value) => value.clone(),
+        Value::Date(value) => value.to_string(),
+        Value::DateTime(value) => value.to_string(),
+        Value::Time(value) => quote(&value.to_string()),
+        Value::Lexical { value, .. } => return yaml_scalar(value),
+        Value::String(value) => quote(value),
+        Value::List(values) if values.is_empty() => "[]".to_string(),
+        Value::Object(values) if values.is_empty() => "{}".to_string(),
+        Value::List(_) | Value::Object(_) => return None,
+    };
+    Some(text)
+}
+
+fn yaml_key(key: &str) -> String {
+    let plain = key
+        .chars()
+        .next()
+        .is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_')
+        && key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+    if plain && !matches!(key, "true" | "false" | "null" | "yes" | "no" | "on" | "off") {
+        key.to_string()
+    } else {
+        quote(key)
+    }
+}
+
+fn quote(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for ch in value.chars() {
+        match ch {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            ch if ch.is_control() => quoted.push_str(&format!("\\u{:04x}", ch as u32)),
+            _ => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Renders a GitHub Flavored Markdown pipe table. Numeric columns are
+/// right-aligned; a matrix without a header gets an empty header row, since
+/// GFM tables always have one.
+fn render_gfm_table(doc: &Document, req: &RenderRequest) -> DocpackResult<String> {
+    let rows = table_rows(doc, req)?;
+    let width = doc.table_width().unwrap_or(0);
+    let columns = doc
+        .meta
+        .tabular_columns
+        .clone()
+        .unwrap_or_else(|| (1..=width).map(|index| index.to_string()).collect());
+    let header = match &doc.meta.tabular_columns {
+        Some(columns) => columns
+            .iter()
+            .map(|column| {
+                doc.meta
+                    .column(column)
+                    .map_or_else(|| column.clone(), ColumnMeta::header)
+            })
+            .collect(),
+        None => vec![String::new(); width],
+    };
+    let alignment = columns
+        .iter()
+        .map(|column| match doc.meta.column(column) {
+            Some(meta) if meta.kind.is_numeric() => "---:".to_string(),
+            _ => "---".to_string(),
+        })
+        .collect::<Vec<_>>();
+    let mut output = String::new();
+    writeln!(output, "{}", join_gfm_row(&header)).map_err(into_render_error(req))?;
+    writeln!(output, "| {} |", alignment.join(" | ")).map_err(into_render_error(req))?;
+    for row in rows {
+        writeln!(output, "{}", join_gfm_row(&row)).map_err(into_render_error(req))?;
+    }
+    Ok(output)
+}
+
+fn join_gfm_row(cells: &[String]) -> String {
+    let cells = cells
+        .iter()
+        .map(|cell| gfm_escape(cell))
+        .collect::<Vec<_>>();
+    format!("| {} |", cells.join(" | "))
+}
+
+/// Escapes a cell so it stays inside its column: pipes and backslashes are
+/// backslash-escaped and line breaks become `<br>`.
+fn gfm_escape(value: &str) -> String {
+    let mut escaped = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '|' => escaped.push_str("\\|"),
+            '\r' => {
+                chars.next_if_eq(&'\n');
+                escaped.push_str("<br>");
+            }
+            '\n' => escaped.push_str("<br>"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn into_render_error(req: &RenderRequest) -> impl FnOnce(std::fmt::Error) -> DocpackError + '_ {
+    move |_| DocpackError::Render {
+        backend: req.backend,
+        artifact: req.artifact,
+        detail: "failed to format Markdown output".to_string(),
+    }
+}
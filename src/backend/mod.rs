@@ -1,10 +1,24 @@
+mod asciidoc;
+mod context;
+mod format;
+mod html;
 mod latex;
+mod markdown;
 mod request;
+mod rst;
 mod typst;
 
-pub use request::{ArtifactKind, BackendKind, RenderRequest, RenderedArtifact};
+pub use format::{ColumnFormat, Locale};
+pub use request::{
+    ArtifactKind, BackendKind, KeyOrder, NonFinitePolicy, RenderOptions, RenderRequest,
+    RenderedArtifact,
+};
 
-use crate::core::Document;
+use std::borrow::Cow;
+
+use indexmap::IndexMap;
+
+use crate::core::{Document, TopLevelShape, Value, infer_columns};
 use crate::error::{DocpackError, DocpackResult};
 
 /// Backend interface implemented by concrete renderers.
@@ -16,19 +30,362 @@ pub trait Backend {
 /// Renders a normalized document through the resolved backend request.
 pub fn render_document(doc: &Document, req: &RenderRequest) -> DocpackResult<RenderedArtifact> {
     validate_request(doc, req)?;
+    let doc = prepare_document(doc, req)?;
     match req.backend {
-        BackendKind::Typst => typst::TypstBackend.render(doc, req),
-        BackendKind::Latex => latex::LatexBackend.render(doc, req),
+        BackendKind::Typst => typst::TypstBackend.render(&doc, req),
+        BackendKind::Latex => latex::LatexBackend.render(&doc, req),
+        BackendKind::Markdown => markdown::MarkdownBackend.render(&doc, req),
+        BackendKind::Html => html::HtmlBackend.render(&doc, req),
+        BackendKind::Asciidoc => asciidoc::AsciidocBackend.render(&doc, req),
+        BackendKind::Rst => rst::RstBackend.render(&doc, req),
+        BackendKind::Context => context::ContextBackend.render(&doc, req),
+    }
+}
+
+/// Applies the backend-independent render options to a document.
+fn prepare_document<'a>(
+    doc: &'a Document,
+    req: &RenderRequest,
+) -> DocpackResult<Cow<'a, Document>> {
+    let mut doc = Cow::Borrowed(doc);
+    if req.options.key_order == KeyOrder::Sorted {
+        doc.to_mut().root.sort_keys();
+    }
+    if req.artifact == ArtifactKind::TableFragment
+        && doc.meta.top_level_shape == TopLevelShape::Object
+    {
+        doc = Cow::Owned(key_value_document(&doc));
+    }
+    match req.options.non_finite {
+        NonFinitePolicy::Reject => {
+            if let Some((path, value)) = find_non_finite(&doc.root, String::new()) {
+                return Err(DocpackError::Render {
+                    backend: req.backend,
+                    artifact: req.artifact,
+                    detail: format!(
+                        "non-finite float {value} at {}; set non_finite to \"native\" or \"null\" to render it",
+                        if path.is_empty() { "/" } else { &path }
+                    ),
+                });
+            }
+        }
+        NonFinitePolicy::Native => {}
+        NonFinitePolicy::Null => {
+            if find_non_finite(&doc.root, String::new()).is_some() {
+                null_non_finite(&mut doc.to_mut().root);
+            }
+        }
+    }
+    Ok(doc)
+}
+
+/// Renders one table-fragment cell as text, applying the column's format
+/// rules when the request declares any.
+pub(crate) fn table_cell_text(
+    value: &Value,
+    column: &str,
+    req: &RenderRequest,
+) -> DocpackResult<String> {
+    let text = match req.options.column_formats.get(column) {
+        Some(format) => format.apply(value, &req.options.locale),
+        None => value.scalar_text(),
+    };
+    text.ok_or_else(|| DocpackError::Render {
+        backend: req.backend,
+        artifact: req.artifact,
+        detail: "table-fragment only accepts scalar cell values".to_string(),
+    })
+}
+
+/// Collects table-fragment rows as cell text in column order, one
+/// [`table_cell_text`] call per cell.
+pub(crate) fn table_rows(doc: &Document, req: &RenderRequest) -> DocpackResult<Vec<Vec<String>>> {
+    match &doc.root {
+        Value::List(rows) => rows
+            .iter()
+            .map(|row| match row {
+                Value::Object(values) => {
+                    let columns =
+                        doc.meta
+                            .tabular_columns
+                            .as_ref()
+                            .ok_or_else(|| DocpackError::Render {
+                                backend: req.backend,
+                                artifact: req.artifact,
+                                detail: "record-shaped table data requires tabular columns"
+                                    .to_string(),
+                            })?;
+                    columns
+                        .iter()
+                        .map(|column| {
+                            table_cell_text(values.get(column).unwrap_or(&Value::Null), column, req)
+                        })
+                        .collect()
+                }
+                Value::List(values) => values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| table_cell_text(value, &(index + 1).to_string(), req))
+                    .collect(),
+                _ => Err(DocpackError::Render {
+                    backend: req.backend,
+                    artifact: req.artifact,
+                    detail: "table-fragment expects rows as objects or lists".to_string(),
+                }),
+            })
+            .collect(),
+        _ => Err(DocpackError::Render {
+            backend: req.backend,
+            artifact: req.artifact,
+            detail: "table-fragment expects a list root".to_string(),
+        }),
+    }
+}
+
+/// Flattens a document into `(path, text)` leaves for backends that store
+/// data as flat key-value entries. List paths use 1-based indexes, each list
+/// that is not itself a list item gets a `__len__` entry, and `Null` becomes
+/// `none`.
+pub(crate) fn flatten_document(doc: &Document) -> Vec<(Vec<String>, String)> {
+    match (&doc.root, doc.meta.tabular_columns.as_ref()) {
+        (Value::List(rows), Some(columns)) if doc.is_tabular() => {
+            let mut out = Vec::new();
+            for (row_index, row) in rows.iter().enumerate() {
+                let row_number = (row_index + 1).to_string();
+                match row {
+                    Value::Object(values) => {
+                        for column in columns {
+                            if let Some(value) = values.get(column) {
+                                let mut path = vec![row_number.clone(), column.clone()];
+                                flatten_value(value, &mut path, &mut out, false);
+                            }
+                        }
+                    }
+                    _ => {
+                        let mut path = vec![row_number];
+                        flatten_value(row, &mut path, &mut out, true);
+                    }
+                }
+            }
+            out.push((vec!["__len__".to_string()], rows.len().to_string()));
+            out
+        }
+        _ => {
+            let mut out = Vec::new();
+            flatten_value(&doc.root, &mut Vec::new(), &mut out, false);
+            out
+        }
+    }
+}
+
+fn flatten_value(
+    value: &Value,
+    path: &mut Vec<String>,
+    out: &mut Vec<(Vec<String>, String)>,
+    parent_is_list: bool,
+) {
+    match value {
+        Value::Null => out.push((leaf_path(path), "none".to_string())),
+        Value::Bool(value) => out.push((leaf_path(path), value.to_string())),
+        Value::Integer(value) => out.push((leaf_path(path), value.to_string())),
+        Value::Float(value) => out.push((leaf_path(path), value.to_string())),
+        Value::Decimal(value) | Value::String(value) => out.push((leaf_path(path), value.clone())),
+        Value::Date(value) => out.push((leaf_path(path), value.to_string())),
+        Value::DateTime(value) => out.push((leaf_path(path), value.to_string())),
+        Value::Time(value) => out.push((leaf_path(path), value.to_string())),
+        Value::Lexical { value, .. } => flatten_value(value, path, out, parent_is_list),
+        Value::List(values) => {
+            for (index, value) in values.iter().enumerate() {
+                path.push((index + 1).to_string());
+                flatten_value(value, path, out, true);
+                path.pop();
+            }
+            if !parent_is_list {
+                let mut len_path = path.clone();
+                len_path.push("__len__".to_string());
+                out.push((len_path, values.len().to_string()));
+            }
+        }
+        Value::Object(values) => {
+            for (key, value) in values {
+                path.push(key.clone());
+                flatten_value(value, path, out, false);
+                path.pop();
+            }
+        }
+    }
+}
+
+fn leaf_path(path: &[String]) -> Vec<String> {
+    if path.is_empty() {
+        vec!["value".to_string()]
+    } else {
+        path.to_vec()
+    }
+}
+
+/// Joins a root name and a flattened leaf path into a lowercase,
+/// hyphen-separated name such as `sales-1-amount`. Characters other than
+/// ASCII letters, digits, `_`, and `-` become hyphens.
+pub(crate) fn hyphenated_name(root_name: &str, path: &[String]) -> String {
+    let mut name = String::new();
+    for segment in std::iter::once(root_name).chain(path.iter().map(String::as_str)) {
+        if !name.is_empty() {
+            name.push('-');
+        }
+        for ch in segment.chars().flat_map(char::to_lowercase) {
+            let next = if ch.is_ascii_alphanumeric() || ch == '_' {
+                ch
+            } else {
+                '-'
+            };
+            if !(next == '-' && name.ends_with('-')) {
+                name.push(next);
+            }
+        }
+    }
+    name.trim_matches('-').to_string()
+}
+
+/// Names each flattened leaf through `name_of`, failing when two leaves get
+/// the same name, since the later entry would silently replace the earlier.
+pub(crate) fn leaf_names(
+    leaves: &[(Vec<String>, String)],
+    req: &RenderRequest,
+    name_of: impl Fn(&[String]) -> String,
+) -> DocpackResult<Vec<String>> {
+    let mut seen = IndexMap::new();
+    let mut names = Vec::with_capacity(leaves.len());
+    for (path, _) in leaves {
+        let name = name_of(path);
+        if let Some(previous) = seen.insert(name.clone(), path) {
+            return Err(DocpackError::Render {
+                backend: req.backend,
+                artifact: req.artifact,
+                detail: format!(
+                    "'/{}' and '/{}' both become the name '{name}'; rename one of them",
+                    previous.join("/"),
+                    path.join("/")
+                ),
+            });
+        }
+        names.push(name);
+    }
+    Ok(names)
+}
+
+pub(crate) fn find_non_finite(value: &Value, path: String) -> Option<(String, f64)> {
+    match value {
+        Value::Float(float) if !float.is_finite() => Some((path, *float)),
+        Value::Lexical { value, .. } => find_non_finite(value, path),
+        Value::List(values) => values
+            .iter()
+            .enumerate()
+            .find_map(|(index, value)| find_non_finite(value, format!("{path}/{index}"))),
+        Value::Object(values) => values
+            .iter()
+            .find_map(|(key, value)| find_non_finite(value, format!("{path}/{key}"))),
+        _ => None,
+    }
+}
+
+fn null_non_finite(value: &mut Value) {
+    match value {
+        Value::Float(float) if !float.is_finite() => *value = Value::Null,
+        Value::Lexical { value: inner, .. } if matches!(**inner, Value::Float(float) if !float.is_finite()) => {
+            *value = Value::Null
+        }
+        Value::List(values) => values.iter_mut().for_each(null_non_finite),
+        Value::Object(values) => values.values_mut().for_each(null_non_finite),
+        _ => {}
+    }
+}
+
+/// Rewrites an object-shaped document as a two-column `Key | Value` record
+/// table, one row per entry. Nested objects contribute `parent.child` keys.
+pub fn key_value_document(doc: &Document) -> Document {
+    fn collect(prefix: &str, value: &Value, rows: &mut Vec<Value>) {
+        match value {
+            Value::Object(values) if !values.is_empty() || prefix.is_empty() => {
+                for (key, value) in values {
+                    let key = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{prefix}.{key}")
+                    };
+                    collect(&key, value, rows);
+                }
+            }
+            value => rows.push(Value::Object(IndexMap::from([
+                ("Key".to_string(), Value::String(prefix.to_string())),
+                ("Value".to_string(), value.clone()),
+            ]))),
+        }
+    }
+
+    let mut rows = Vec::new();
+    collect("", &doc.root, &mut rows);
+    let root = Value::List(rows);
+    let names = vec!["Key".to_string(), "Value".to_string()];
+    let mut meta = doc.meta.clone();
+    meta.top_level_shape = TopLevelShape::TabularRecords;
+    meta.columns = infer_columns(&root, Some(&names));
+    meta.tabular_columns = Some(names);
+    Document {
+        source_id: doc.source_id.clone(),
+        root,
+        meta,
     }
 }
 
+/// Returns `true` when a document can render as a `table-fragment`: tabular
+/// sources directly, objects as key-value tables.
+pub fn supports_table_fragment(doc: &Document) -> bool {
+    doc.is_tabular() || doc.meta.top_level_shape == TopLevelShape::Object
+}
+
 /// Validates backend, artifact, style, and source-shape compatibility.
 pub fn validate_request(doc: &Document, req: &RenderRequest) -> DocpackResult<()> {
-    if req.artifact == ArtifactKind::TableFragment && !doc.is_tabular() {
+    if req.artifact == ArtifactKind::TableFragment && !supports_table_fragment(doc) {
         return Err(DocpackError::Render {
             backend: req.backend,
             artifact: req.artifact,
-            detail: "artifact table-fragment requires tabular source metadata".to_string(),
+            detail: "artifact table-fragment requires tabular source metadata or an object"
+                .to_string(),
+        });
+    }
+    if !req.options.column_formats.is_empty() {
+        let fail = |detail: String| DocpackError::Render {
+            backend: req.backend,
+            artifact: req.artifact,
+            detail,
+        };
+        if req.artifact != ArtifactKind::TableFragment {
+            return Err(fail(
+                "column formats only apply to table-fragment output".to_string(),
+            ));
+        }
+        let table = if doc.meta.top_level_shape == TopLevelShape::Object {
+            Cow::Owned(key_value_document(doc))
+        } else {
+            Cow::Borrowed(doc)
+        };
+        for (column, format) in &req.options.column_formats {
+            if table.meta.column(column).is_none() {
+                return Err(fail(format!("column format for unknown column '{column}'")));
+            }
+            format
+                .validate()
+                .map_err(|detail| fail(format!("column format for '{column}': {detail}")))?;
+        }
+    }
+    if req.options.css_class.is_some()
+        && (req.backend != BackendKind::Html || req.artifact != ArtifactKind::TableFragment)
+    {
+        return Err(DocpackError::Render {
+            backend: req.backend,
+            artifact: req.artifact,
+            detail: "css_class only applies to html table-fragment output".to_string(),
         });
     }
     if !style_supported(req.backend, req.artifact, &req.style) {
@@ -72,6 +429,57 @@ pub fn style_supported(backend: BackendKind, artifact: ArtifactKind, style: &str
                 ArtifactKind::TableFragment,
                 "latex-plain-tabular"
             )
+            | (
+                BackendKind::Markdown,
+                ArtifactKind::DataModule,
+                "markdown-front-matter"
+            )
+            | (
+                BackendKind::Markdown,
+                ArtifactKind::TableFragment,
+                "markdown-gfm"
+            )
+            | (
+                BackendKind::Html,
+                ArtifactKind::DataModule,
+                "html-json-script"
+            )
+            | (BackendKind::Html, ArtifactKind::TableFragment, "html-table")
+            | (
+                BackendKind::Asciidoc,
+                ArtifactKind::DataModule,
+                "asciidoc-attributes"
+            )
+            | (
+                BackendKind::Asciidoc,
+                ArtifactKind::TableFragment,
+                "asciidoc-table"
+            )
+            | (
+                BackendKind::Rst,
+                ArtifactKind::DataModule,
+                "rst-substitutions"
+            )
+            | (
+                BackendKind::Rst,
+                ArtifactKind::TableFragment,
+                "rst-list-table"
+            )
+            | (
+                BackendKind::Context,
+                ArtifactKind::DataModule,
+                "context-setvariables"
+            )
+            | (
+                BackendKind::Context,
+                ArtifactKind::TableFragment,
+                "context-xtable"
+            )
+            | (
+                BackendKind::Context,
+                ArtifactKind::TableFragment,
+                "context-natural-table"
+            )
     )
 }
 
@@ -83,6 +491,13 @@ pub fn style_implied_backend(style: &str) -> Option<BackendKind> {
         | "latex-classic-macro"
         | "latex-booktabs-longtable"
         | "latex-plain-tabular" => Some(BackendKind::Latex),
+        "markdown-front-matter" | "markdown-gfm" => Some(BackendKind::Markdown),
+        "html-json-script" | "html-table" => Some(BackendKind::Html),
+        "asciidoc-attributes" | "asciidoc-table" => Some(BackendKind::Asciidoc),
+        "rst-substitutions" | "rst-list-table" => Some(BackendKind::Rst),
+        "context-setvariables" | "context-xtable" | "context-natural-table" => {
+            Some(BackendKind::Context)
+        }
         _ => None,
     }
 }
@@ -90,10 +505,23 @@ pub fn style_implied_backend(style: &str) -> Option<BackendKind> {
 /// Infers an artifact category from a style identifier when possible.
 pub fn style_implied_artifact(style: &str) -> Option<ArtifactKind> {
     match style {
-        "typst-table" | "latex-booktabs-longtable" | "latex-plain-tabular" => {
-            Some(ArtifactKind::TableFragment)
-        }
-        "typst-official" | "latex-expl3" | "latex-classic-macro" => Some(ArtifactKind::DataModule),
+        "typst-table"
+        | "latex-booktabs-longtable"
+        | "latex-plain-tabular"
+        | "markdown-gfm"
+        | "html-table"
+        | "asciidoc-table"
+        | "rst-list-table"
+        | "context-xtable"
+        | "context-natural-table" => Some(ArtifactKind::TableFragment),
+        "typst-official"
+        | "latex-expl3"
+        | "latex-classic-macro"
+        | "markdown-front-matter"
+        | "html-json-script"
+        | "asciidoc-attributes"
+        | "rst-substitutions"
+        | "context-setvariables" => Some(ArtifactKind::DataModule),
         _ => None,
     }
 }
@@ -105,5 +533,15 @@ pub fn default_style(backend: BackendKind, artifact: ArtifactKind) -> &'static s
         (BackendKind::Typst, ArtifactKind::TableFragment) => "typst-table",
         (BackendKind::Latex, ArtifactKind::DataModule) => "latex-expl3",
         (BackendKind::Latex, ArtifactKind::TableFragment) => "latex-booktabs-longtable",
+        (BackendKind::Markdown, ArtifactKind::DataModule) => "markdown-front-matter",
+        (BackendKind::Markdown, ArtifactKind::TableFragment) => "markdown-gfm",
+        (BackendKind::Html, ArtifactKind::DataModule) => "html-json-script",
+        (BackendKind::Html, ArtifactKind::TableFragment) => "html-table",
+        (BackendKind::Asciidoc, ArtifactKind::DataModule) => "asciidoc-attributes",
+        (BackendKind::Asciidoc, ArtifactKind::TableFragment) => "asciidoc-table",
+        (BackendKind::Rst, ArtifactKind::DataModule) => "rst-substitutions",
+        (BackendKind::Rst, ArtifactKind::TableFragment) => "rst-list-table",
+        (BackendKind::Context, ArtifactKind::DataModule) => "context-setvariables",
+        (BackendKind::Context, ArtifactKind::TableFragment) => "context-xtable",
     }
 }
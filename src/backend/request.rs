@@ -1,14 +1,22 @@
 use std::fmt::{Display, Formatter};
 
 use clap::ValueEnum;
+use indexmap::IndexMap;
 use serde::Deserialize;
 
+use crate::backend::{ColumnFormat, Locale};
+
 /// Concrete backend families supported by `docpack`.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, ValueEnum)]
 #[serde(rename_all = "lowercase")]
 pub enum BackendKind {
     Typst,
     Latex,
+    Markdown,
+    Html,
+    Asciidoc,
+    Rst,
+    Context,
 }
 
 impl Display for BackendKind {
@@ -16,6 +24,11 @@ impl Display for BackendKind {
         let value = match self {
             Self::Typst => "typst",
             Self::Latex => "latex",
+            Self::Markdown => "markdown",
+            Self::Html => "html",
+            Self::Asciidoc => "asciidoc",
+            Self::Rst => "rst",
+            Self::Context => "context",
         };
         write!(f, "{value}")
     }
@@ -39,6 +52,64 @@ impl Display for ArtifactKind {
     }
 }
 
+/// Object key ordering applied before a document is rendered.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyOrder {
+    /// Keep object keys in the order the source declared them.
+    Source,
+    /// Reorder object keys lexically for stable diffs.
+    #[default]
+    Sorted,
+}
+
+impl Display for KeyOrder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Source => "source",
+            Self::Sorted => "sorted",
+        };
+        write!(f, "{value}")
+    }
+}
+
+/// How `NaN` and infinite floats are handled before rendering.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum NonFinitePolicy {
+    /// Fail with a render error naming the first offending path.
+    #[default]
+    Reject,
+    /// Emit the backend's own spelling, such as `float.nan` in Typst.
+    Native,
+    /// Treat the value as `Null`.
+    Null,
+}
+
+impl Display for NonFinitePolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Reject => "reject",
+            Self::Native => "native",
+            Self::Null => "null",
+        };
+        write!(f, "{value}")
+    }
+}
+
+/// Per-output rendering options that do not affect backend or style selection.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderOptions {
+    pub key_order: KeyOrder,
+    pub non_finite: NonFinitePolicy,
+    /// Separator conventions used by `column_formats`.
+    pub locale: Locale,
+    /// Display rules for table-fragment cells, keyed by column name.
+    pub column_formats: IndexMap<String, ColumnFormat>,
+    /// `class` attribute placed on HTML tables.
+    pub css_class: Option<String>,
+}
+
 /// Fully resolved render request passed to a backend.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RenderRequest {
@@ -46,6 +117,7 @@ pub struct RenderRequest {
     pub artifact: ArtifactKind,
     pub style: String,
     pub root_name: String,
+    pub options: RenderOptions,
 }
 
 /// Rendered output body returned by a backend.
@@ -0,0 +1,151 @@
+use std::fmt::Write;
+
+use crate::backend::{
+    ArtifactKind, Backend, BackendKind, RenderRequest, RenderedArtifact, flatten_document,
+    hyphenated_name, leaf_names, table_rows,
+};
+use crate::core::{ColumnMeta, Document};
+use crate::error::{DocpackError, DocpackResult};
+
+pub struct RstBackend;
+
+impl Backend for RstBackend {
+    fn kind(&self) -> BackendKind {
+        BackendKind::Rst
+    }
+
+    fn render(&self, doc: &Document, req: &RenderRequest) -> DocpackResult<RenderedArtifact> {
+        let body = match (req.artifact, req.style.as_str()) {
+            (ArtifactKind::DataModule, "rst-substitutions") => render_substitutions(doc, req),
+            (ArtifactKind::TableFragment, "rst-list-table") => render_list_table(doc, req),
+            _ => Err(DocpackError::Render {
+                backend: req.backend,
+                artifact: req.artifact,
+                detail: format!("unsupported reStructuredText style '{}'", req.style),
+            }),
+        }?;
+        Ok(RenderedArtifact { body })
+    }
+}
+
+/// Renders one `replace::` substitution definition per leaf, named after the
+/// root name and the leaf path, such as `|sales-1-amount|`.
+fn render_substitutions(doc: &Document, req: &RenderRequest) -> DocpackResult<String> {
+    let leaves = flatten_document(doc);
+    let names = leaf_names(&leaves, req, |path| hyphenated_name(&req.root_name, path))?;
+    let mut output = String::new();
+    for (name, (_, value)) in names.iter().zip(&leaves) {
+        let lines = value
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(escape_line)
+            .collect::<Vec<_>>();
+        // An empty definition is an error, and an escaped space renders as
+        // nothing.
+        let text = if lines.is_empty() {
+            "\\ ".to_string()
+        } else {
+            lines.join("\n   ")
+        };
+        writeln!(output, ".. |{name}| replace:: {text}").map_err(into_render_error(req))?;
+    }
+    Ok(output)
+}
+
+/// Renders a `list-table` directive with one header row when the source has
+/// a header.
+fn render_list_table(doc: &Document, req: &RenderRequest) -> DocpackResult<String> {
+    let rows = table_rows(doc, req)?;
+    let mut output = String::new();
+    writeln!(output, ".. list-table::").map_err(into_render_error(req))?;
+    if doc.meta.tabular_columns.is_some() {
+        writeln!(output, "   :header-rows: 1").map_err(into_render_error(req))?;
+    }
+    writeln!(output).map_err(into_render_error(req))?;
+    if let Some(columns) = &doc.meta.tabular_columns {
+        let header = columns
+            .iter()
+            .map(|column| {
+                doc.meta
+                    .column(column)
+                    .map_or_else(|| column.clone(), ColumnMeta::header)
+            })
+            .collect::<Vec<_>>();
+        write_row(&mut output, &header).map_err(into_render_error(req))?;
+    }
+    for row in rows {
+        write_row(&mut output, &row).map_err(into_render_error(req))?;
+    }
+    Ok(output)
+}
+
+fn write_row(output: &mut String, cells: &[String]) -> std::fmt::Result {
+    for (index, cell) in cells.iter().enumerate() {
+        let marker = if index == 0 { "   * -" } else { "     -" };
+        let lines = cell.lines().collect::<Vec<_>>();
+        match lines.as_slice() {
+            [] => writeln!(output, "{marker}")?,
+            [line] => writeln!(output, "{marker} {}", escape_line(line))?,
+            // Several lines become a line block so the breaks survive.
+            lines => {
+                for (line_index, line) in lines.iter().enumerate() {
+                    let prefix = if line_index == 0 { marker } else { "      " };
+                    if line.trim().is_empty() {
+                        writeln!(output, "{prefix} |")?;
+                    } else {
+                        writeln!(output, "{prefix} | {}", escape_inline(line))?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Escapes inline markup, plus a leading character that would otherwise
+/// start a list, enumeration, comment, or section adornment.
+fn escape_line(line: &str) -> String {
+    let escaped = escape_inline(line);
+    if starts_block_markup(&escaped) {
+        format!("\\{escaped}")
+    } else {
+        escaped
+    }
+}
+
+fn escape_inline(line: &str) -> String {
+    let mut escaped = String::new();
+    for ch in line.chars() {
+        if matches!(ch, '\\' | '*' | '`' | '|' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+fn starts_block_markup(line: &str) -> bool {
+    let Some(first) = line.chars().next() else {
+        return false;
+    };
+    if !first.is_alphanumeric() {
+        return first != '\\';
+    }
+    let label = line
+        .chars()
+        .take_while(|ch| ch.is_ascii_alphanumeric())
+        .collect::<String>();
+    let rest = &line[label.len()..];
+    let enumerator = label.chars().all(|ch| ch.is_ascii_digit())
+        || label.len() == 1
+        || label.chars().all(|ch| "ivxlcdmIVXLCDM".contains(ch));
+    enumerator && (rest.starts_with(". ") || rest.starts_with(") ") || rest == "." || rest == ")")
+}
+
+fn into_render_error(req: &RenderRequest) -> impl FnOnce(std::fmt::Error) -> DocpackError + '_ {
+    move |_| DocpackError::Render {
+        backend: req.backend,
+        artifact: req.artifact,
+        detail: "failed to format reStructuredText output".to_string(),
+    }
+}
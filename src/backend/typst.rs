@@ -1,9 +1,14 @@
 use std::fmt::Write;
 
-use crate::backend::{ArtifactKind, Backend, BackendKind, RenderRequest, RenderedArtifact};
-use crate::core::{Document, Value};
+use crate::backend::{
+    ArtifactKind, Backend, BackendKind, RenderRequest, RenderedArtifact, table_rows,
+};
+use crate::core::{ColumnMeta, Document, Value};
 use crate::error::{DocpackError, DocpackResult};
 
+/// Significant and fraction digits a Typst `decimal` can hold.
+const TYPST_DECIMAL_DIGITS: usize = 28;
+
 pub struct TypstBackend;
 
 impl Backend for TypstBackend {
@@ -20,7 +25,43 @@ impl Backend for TypstBackend {
     }
 }
 
+/// Renders the document as one `#let` binding. Typst datetimes carry
+/// neither a UTC offset nor fractions of a second, so offset date-times are
+/// shifted to UTC and sub-second values are rejected rather than truncated.
+/// Typst decimals hold at most 28 significant digits, so longer exact
+/// decimals are rejected too.
 fn render_data_module(doc: &Document, req: &RenderRequest) -> DocpackResult<String> {
+    let render_error = |detail: String| DocpackError::Render {
+        backend: req.backend,
+        artifact: req.artifact,
+        detail,
+    };
+    let at = |path: String| {
+        if path.is_empty() {
+            "/".to_string()
+        } else {
+            path
+        }
+    };
+    if let Some((path, value)) = find_leaf(&doc.root, String::new(), &|value| match value {
+        Value::DateTime(datetime) if datetime.time.nanosecond > 0 => Some(datetime.to_string()),
+        Value::Time(time) if time.nanosecond > 0 => Some(time.to_string()),
+        _ => None,
+    }) {
+        return Err(render_error(format!(
+            "Typst datetimes have no sub-second precision, but {} holds {value}",
+            at(path)
+        )));
+    }
+    if let Some((path, value)) = find_leaf(&doc.root, String::new(), &|value| match value {
+        Value::Decimal(text) if !fits_typst_decimal(text) => Some(text.clone()),
+        _ => None,
+    }) {
+        return Err(render_error(format!(
+            "Typst decimals hold at most {TYPST_DECIMAL_DIGITS} significant digits, but {} holds {value}",
+            at(path)
+        )));
+    }
     Ok(format!(
         "#let {} = {}\n",
         req.root_name,
@@ -30,14 +71,15 @@ fn render_data_module(doc: &Document, req: &RenderRequest) -> DocpackResult<Stri
 
 fn render_table_fragment(doc: &Document, req: &RenderRequest) -> DocpackResult<String> {
     let width = doc.table_width().unwrap_or(0);
-    let rows = collect_table_rows(doc, req)?;
+    let rows = table_rows(doc, req)?;
     let mut output = String::new();
     writeln!(output, "#table(").map_err(into_render_error(req))?;
     writeln!(output, "  columns: {width},").map_err(into_render_error(req))?;
     if let Some(columns) = &doc.meta.tabular_columns {
         write!(output, "  table.header").map_err(into_render_error(req))?;
         for column in columns {
-            write!(output, "[{}]", render_table_cell(column)).map_err(into_render_error(req))?;
+            write!(output, "[{}]", render_table_cell(&header_text(doc, column)))
+                .map_err(into_render_error(req))?;
         }
         writeln!(output, ",").map_err(into_render_error(req))?;
     }
@@ -55,53 +97,39 @@ fn render_table_fragment(doc: &Document, req: &RenderRequest) -> DocpackResult<S
     Ok(output)
 }
 
-fn collect_table_rows(doc: &Document, req: &RenderRequest) -> DocpackResult<Vec<Vec<String>>> {
-    match &doc.root {
-        Value::List(rows) => {
-            rows.iter()
-                .map(|row| match row {
-                    Value::Object(values) => {
-                        let columns = doc.meta.tabular_columns.as_ref().ok_or_else(|| {
-                            DocpackError::Render {
-                                backend: req.backend,
-                                artifact: req.artifact,
-                                detail: "record-shaped table data requires tabular columns"
-                                    .to_string(),
-                            }
-                        })?;
-                        columns
-                            .iter()
-                            .map(|column| {
-                                render_scalar_cell(values.get(column).unwrap_or(&Value::Null), req)
-                            })
-                            .collect()
-                    }
-                    Value::List(values) => values
-                        .iter()
-                        .map(|value| render_scalar_cell(value, req))
-                        .collect(),
-                    _ => Err(DocpackError::Render {
-                        backend: req.backend,
-                        artifact: req.artifact,
-                        detail: "table-fragment expects rows as objects or lists".to_string(),
-                    }),
-                })
-                .collect()
-        }
-        _ => Err(DocpackError::Render {
-            backend: req.backend,
-            artifact: req.artifact,
-            detail: "table-fragment expects a list root".to_string(),
-        }),
+/// Finds the first leaf, in document order, that `problem` describes.
+fn find_leaf(
+    value: &Value,
+    path: String,
+    problem: &impl Fn(&Value) -> Option<String>,
+) -> Option<(String, String)> {
+    match value {
+        Value::Lexical { value, .. } => find_leaf(value, path, problem),
+        Value::List(values) => values
+            .iter()
+            .enumerate()
+            .find_map(|(index, value)| find_leaf(value, format!("{path}/{index}"), problem)),
+        Value::Object(values) => values
+            .iter()
+            .find_map(|(key, value)| find_leaf(value, format!("{path}/{key}"), problem)),
+        value => problem(value).map(|text| (path, text)),
     }
 }
 
-fn render_scalar_cell(value: &Value, req: &RenderRequest) -> DocpackResult<String> {
-    value.scalar_text().ok_or_else(|| DocpackError::Render {
-        backend: req.backend,
-        artifact: req.artifact,
-        detail: "table-fragment only accepts scalar cell values".to_string(),
-    })
+/// Whether a normalized decimal such as `-12.50` fits Typst's `decimal`,
+/// which keeps at most `TYPST_DECIMAL_DIGITS` significant and fraction digits.
+fn fits_typst_decimal(text: &str) -> bool {
+    let unsigned = text.trim_start_matches('-');
+    let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    let significant = format!("{integer}{fraction}");
+    fraction.len() <= TYPST_DECIMAL_DIGITS
+        && significant.trim_start_matches('0').len() <= TYPST_DECIMAL_DIGITS
+}
+
+fn header_text(doc: &Document, column: &str) -> String {
+    doc.meta
+        .column(column)
+        .map_or_else(|| column.to_string(), ColumnMeta::header)
 }
 
 fn render_value(value: &Value) -> String {
@@ -109,7 +137,37 @@ fn render_value(value: &Value) -> String {
         Value::Null => "none".to_string(),
         Value::Bool(value) => value.to_string(),
         Value::Integer(value) => value.to_string(),
+        Value::Float(value) if value.is_nan() => "float.nan".to_string(),
+        Value::Float(value) if value.is_infinite() => {
+            if value.is_sign_negative() {
+                "-float.inf".to_string()
+            } else {
+                "float.inf".to_string()
+            }
+        }
         Value::Float(value) => value.to_string(),
+        Value::Decimal(value) => format!("decimal(\"{value}\")"),
+        Value::Date(value) => format!(
+            "datetime(year: {}, month: {}, day: {})",
+            value.year, value.month, value.day
+        ),
+        Value::DateTime(value) => {
+            let value = value.to_utc();
+            format!(
+                "datetime(year: {}, month: {}, day: {}, hour: {}, minute: {}, second: {})",
+                value.date.year,
+                value.date.month,
+                value.date.day,
+                value.time.hour,
+                value.time.minute,
+                value.time.second
+            )
+        }
+        Value::Time(value) => format!(
+            "datetime(hour: {}, minute: {}, second: {})",
+            value.hour, value.minute, value.second
+        ),
+        Value::Lexical { value, .. } => render_value(value),
         Value::String(value) => format!("\"{}\"", escape_string(value)),
         Value::List(values) => format!(
             "({})",
@@ -202,15 +260,15 @@ fn into_render_error(req: &RenderRequest) -> impl FnOnce(std::fmt::Error) -> Doc
 
 #[cfg(test)]
 mod tests {
-    use std::collections::BTreeMap;
+    use indexmap::IndexMap;
 
     use super::TypstBackend;
-    use crate::backend::{ArtifactKind, Backend, BackendKind, RenderRequest};
+    use crate::backend::{ArtifactKind, Backend, BackendKind, RenderOptions, RenderRequest};
     use crate::core::{Document, Origin, SourceFormat, SourceMeta, TopLevelShape, Value};
 
     #[test]
     fn renders_typst_data_module() {
-        let mut map = BTreeMap::new();
+        let mut map = IndexMap::new();
         map.insert("active".to_string(), Value::Bool(true));
         map.insert("age".to_string(), Value::Integer(30));
         map.insert("name".to_string(), Value::String("Alice".to_string()));
@@ -223,6 +281,7 @@ mod tests {
                 top_level_shape: TopLevelShape::Object,
                 tabular_columns: None,
                 header_present: None,
+                columns: Vec::new(),
             },
         };
         let req = RenderRequest {
@@ -230,6 +289,7 @@ mod tests {
             artifact: ArtifactKind::DataModule,
             style: "typst-official".to_string(),
             root_name: "data".to_string(),
+            options: RenderOptions::default(),
         };
         let rendered = TypstBackend.render(&doc, &req).unwrap();
         assert_eq!(
@@ -253,6 +313,7 @@ mod tests {
                 top_level_shape: TopLevelShape::TabularMatrix,
                 tabular_columns: None,
                 header_present: Some(false),
+                columns: Vec::new(),
             },
         };
         let req = RenderRequest {
@@ -260,6 +321,7 @@ mod tests {
             artifact: ArtifactKind::TableFragment,
             style: "typst-table".to_string(),
             root_name: "data".to_string(),
+            options: RenderOptions::default(),
         };
         let rendered = TypstBackend.render(&doc, &req).unwrap();
         assert_eq!(
@@ -1,7 +1,10 @@
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::builder::RangedU64ValueParser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
-use docpack::{ArtifactKind, BackendKind, SourceFormat};
+use docpack::{
+    ArtifactKind, BackendKind, ColumnType, KeyOrder, NonFinitePolicy, SourceFormat, Transform,
+};
 
 #[derive(Debug, Parser)]
 #[clap(author, version, about, propagate_version = true)]
@@ -19,6 +22,7 @@ pub enum Commands {
     Build(BuildArgs),
     Emit(EmitArgs),
     Inspect(InspectArgs),
+    Validate(ValidateArgs),
     Init(InitArgs),
 }
 
@@ -49,11 +53,35 @@ pub struct EmitArgs {
     #[arg(long)]
     pub root_name: Option<String>,
 
+    #[arg(long)]
+    pub key_order: Option<KeyOrder>,
+
+    #[arg(long)]
+    pub non_finite: Option<NonFinitePolicy>,
+
     #[arg(long, default_value_t = false)]
     pub no_header: bool,
 
     #[arg(long)]
     pub sheet: Option<String>,
+
+    #[arg(long, default_value_t = false)]
+    pub exact_decimals: bool,
+
+    #[arg(long, default_value_t = false)]
+    pub keep_numeric_text: bool,
+
+    #[arg(long = "column-type", value_name = "COLUMN=TYPE", value_parser = parse_column_type)]
+    pub column_types: Vec<(String, ColumnType)>,
+
+    #[arg(long, value_name = "POINTER")]
+    pub select: Option<String>,
+
+    #[arg(long, default_value_t = false)]
+    pub tabular: bool,
+
+    #[command(flatten)]
+    pub rows: RowArgs,
 }
 
 #[derive(Debug, Parser)]
@@ -81,11 +109,108 @@ pub struct InspectArgs {
     #[arg(long)]
     pub root_name: Option<String>,
 
+    #[arg(long)]
+    pub key_order: Option<KeyOrder>,
+
+    #[arg(long)]
+    pub non_finite: Option<NonFinitePolicy>,
+
+    #[arg(long, default_value_t = false)]
+    pub no_header: bool,
+
+    #[arg(long)]
+    pub sheet: Option<String>,
+
+    #[arg(long, default_value_t = false)]
+    pub exact_decimals: bool,
+
+    #[arg(long, default_value_t = false)]
+    pub keep_numeric_text: bool,
+
+    #[arg(long = "column-type", value_name = "COLUMN=TYPE", value_parser = parse_column_type)]
+    pub column_types: Vec<(String, ColumnType)>,
+
+    #[arg(long, value_name = "POINTER")]
+    pub select: Option<String>,
+
+    #[arg(long, default_value_t = false)]
+    pub tabular: bool,
+
+    #[command(flatten)]
+    pub rows: RowArgs,
+}
+
+#[derive(Debug, Parser)]
+pub struct ValidateArgs {
+    pub input: String,
+
+    #[arg(long)]
+    pub schema: Option<PathBuf>,
+
+    #[arg(short, long)]
+    pub format: Option<SourceFormat>,
+
     #[arg(long, default_value_t = false)]
     pub no_header: bool,
 
     #[arg(long)]
     pub sheet: Option<String>,
+
+    #[arg(long, default_value_t = false)]
+    pub exact_decimals: bool,
+
+    #[arg(long = "column-type", value_name = "COLUMN=TYPE", value_parser = parse_column_type)]
+    pub column_types: Vec<(String, ColumnType)>,
+
+    #[arg(long, value_name = "POINTER")]
+    pub select: Option<String>,
+
+    #[arg(long, default_value_t = false)]
+    pub tabular: bool,
+}
+
+// Row sampling shared by `emit` and `inspect`, applied in the order `every`,
+// `sample`, `head`, `tail`. A doc comment here would become the description of
+// both commands in `--help`.
+#[derive(Debug, Args)]
+pub struct RowArgs {
+    #[arg(long, value_name = "N")]
+    pub head: Option<usize>,
+
+    #[arg(long, value_name = "N")]
+    pub tail: Option<usize>,
+
+    #[arg(long, value_name = "N")]
+    pub sample: Option<usize>,
+
+    #[arg(long, requires = "sample")]
+    pub seed: Option<u64>,
+
+    #[arg(long, value_name = "N", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub every: Option<usize>,
+}
+
+impl RowArgs {
+    /// Converts the flags into transform steps.
+    pub fn steps(&self) -> Vec<Transform> {
+        let mut steps = Vec::new();
+        if let Some(step) = self.every {
+            steps.push(Transform::Every { step, offset: 0 });
+        }
+        if let Some(count) = self.sample {
+            steps.push(Transform::Sample {
+                count,
+                seed: self.seed.unwrap_or_default(),
+            });
+        }
+        if let Some(count) = self.head {
+            steps.push(Transform::Limit { count });
+        }
+        if let Some(count) = self.tail {
+            steps.push(Transform::Tail { count });
+        }
+        steps
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -102,6 +227,15 @@ pub enum InspectTarget {
     Manifest,
 }
 
+fn parse_column_type(value: &str) -> Result<(String, ColumnType), String> {
+    let (column, name) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected COLUMN=TYPE, got '{value}'"))?;
+    let column_type = ColumnType::from_name(name)
+        .ok_or_else(|| format!("unknown column type '{name}'; expected date, datetime, or time"))?;
+    Ok((column.to_string(), column_type))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
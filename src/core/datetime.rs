@@ -0,0 +1,289 @@
+use std::fmt::{Display, Formatter};
+
+/// Calendar date without a time zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+/// Wall-clock time of day without a time zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub nanosecond: u32,
+}
+
+/// Date plus time of day, optionally anchored by a UTC offset in minutes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+    pub offset_minutes: Option<i16>,
+}
+
+impl Date {
+    /// Builds a date after checking the month and day ranges.
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+        if (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month) {
+            Some(Self { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    /// Converts a day count relative to 1970-01-01 into a calendar date.
+    pub fn from_unix_days(days: i64) -> Self {
+        // Howard Hinnant's `civil_from_days`.
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+        Self { year, month, day }
+    }
+
+    /// Converts the date into a day count relative to 1970-01-01.
+    pub fn to_unix_days(self) -> i64 {
+        // Howard Hinnant's `days_from_civil`.
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let month = i64::from(self.month);
+        let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5
+            + i64::from(self.day)
+            - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    /// Parses an ISO 8601 calendar date such as `2025-01-31`.
+    pub fn parse_iso(text: &str) -> Option<Self> {
+        let mut parts = text.splitn(3, '-');
+        let year = parts.next().filter(|part| part.len() == 4)?;
+        let month = parts.next().filter(|part| part.len() == 2)?;
+        let day = parts.next().filter(|part| part.len() == 2)?;
+        Self::new(
+            parse_digits(year)? as i32,
+            parse_digits(month)? as u8,
+            parse_digits(day)? as u8,
+        )
+    }
+}
+
+impl Time {
+    /// Builds a time of day after checking every component range.
+    pub fn new(hour: u8, minute: u8, second: u8, nanosecond: u32) -> Option<Self> {
+        if hour < 24 && minute < 60 && second < 60 && nanosecond < 1_000_000_000 {
+            Some(Self {
+                hour,
+                minute,
+                second,
+                nanosecond,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Parses an ISO 8601 time such as `07:32`, `07:32:00` or `07:32:00.25`.
+    pub fn parse_iso(text: &str) -> Option<Self> {
+        let (clock, fraction) = match text.split_once('.') {
+            Some((clock, fraction)) => (clock, Some(fraction)),
+            None => (text, None),
+        };
+        let mut parts = clock.split(':');
+        let hour = parts.next().filter(|part| part.len() == 2)?;
+        let minute = parts.next().filter(|part| part.len() == 2)?;
+        let second = match parts.next() {
+            Some(second) if second.len() == 2 => parse_digits(second)? as u8,
+            Some(_) => return None,
+            None if fraction.is_none() => 0,
+            None => return None,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        let nanosecond = match fraction {
+            Some(fraction) if !fraction.is_empty() && fraction.len() <= 9 => {
+                parse_digits(fraction)? as u32 * 10u32.pow(9 - fraction.len() as u32)
+            }
+            Some(_) => return None,
+            None => 0,
+        };
+        Self::new(
+            parse_digits(hour)? as u8,
+            parse_digits(minute)? as u8,
+            second,
+            nanosecond,
+        )
+    }
+}
+
+impl DateTime {
+    /// Shifts a date-time with a UTC offset to the same instant in UTC.
+    /// Local date-times have no offset to apply and are returned unchanged.
+    pub fn to_utc(self) -> Self {
+        let Some(offset) = self.offset_minutes else {
+            return self;
+        };
+        let minutes = self.date.to_unix_days() * 1440
+            + i64::from(self.time.hour) * 60
+            + i64::from(self.time.minute)
+            - i64::from(offset);
+        let minute_of_day = minutes.rem_euclid(1440);
+        Self {
+            date: Date::from_unix_days(minutes.div_euclid(1440)),
+            time: Time {
+                hour: (minute_of_day / 60) as u8,
+                minute: (minute_of_day % 60) as u8,
+                ..self.time
+            },
+            offset_minutes: Some(0),
+        }
+    }
+
+    /// Parses an ISO 8601 date-time with a `T` or space separator and an
+    /// optional `Z` or `+HH:MM` offset.
+    pub fn parse_iso(text: &str) -> Option<Self> {
+        let (date, rest) = text.split_at_checked(10)?;
+        let rest = rest
+            .strip_prefix(['T', 't', ' '])
+            .filter(|rest| !rest.is_empty())?;
+        let (time, offset_minutes) = if let Some(time) = rest.strip_suffix(['Z', 'z']) {
+            (time, Some(0))
+        } else if let Some(index) = rest.rfind(['+', '-']) {
+            let (time, offset) = rest.split_at(index);
+            (time, Some(parse_offset(offset)?))
+        } else {
+            (rest, None)
+        };
+        Some(Self {
+            date: Date::parse_iso(date)?,
+            time: Time::parse_iso(time)?,
+            offset_minutes,
+        })
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Display for Time {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+        if self.nanosecond > 0 {
+            let fraction = format!("{:09}", self.nanosecond);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for DateTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}T{}", self.date, self.time)?;
+        match self.offset_minutes {
+            Some(0) => write!(f, "Z"),
+            Some(minutes) => {
+                let sign = if minutes < 0 { '-' } else { '+' };
+                let minutes = minutes.unsigned_abs();
+                write!(f, "{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+fn parse_offset(text: &str) -> Option<i16> {
+    let (sign, rest) = match text.as_bytes().first()? {
+        b'+' => (1, &text[1..]),
+        b'-' => (-1, &text[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = rest.split_once(':')?;
+    if hours.len() != 2 || minutes.len() != 2 {
+        return None;
+    }
+    let (hours, minutes) = (parse_digits(hours)?, parse_digits(minutes)?);
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    Some(sign * (hours * 60 + minutes) as i16)
+}
+
+fn parse_digits(text: &str) -> Option<u64> {
+    if text.is_empty() || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Date, DateTime, Time};
+
+    #[test]
+    fn parses_and_displays_iso_values() {
+        assert_eq!(
+            Date::parse_iso("2024-02-29").map(|date| date.to_string()),
+            Some("2024-02-29".to_string())
+        );
+        assert_eq!(Date::parse_iso("2025-02-29"), None);
+        assert_eq!(
+            Time::parse_iso("07:32:00.250").map(|time| time.to_string()),
+            Some("07:32:00.25".to_string())
+        );
+        assert_eq!(
+            DateTime::parse_iso("1979-05-27 07:32:00-07:00").map(|value| value.to_string()),
+            Some("1979-05-27T07:32:00-07:00".to_string())
+        );
+        assert_eq!(DateTime::parse_iso("1979-05-27"), None);
+    }
+
+    #[test]
+    fn converts_unix_days_to_dates() {
+        assert_eq!(Date::from_unix_days(0), Date::new(1970, 1, 1).unwrap());
+        assert_eq!(Date::from_unix_days(19_723), Date::new(2024, 1, 1).unwrap());
+        assert_eq!(Date::from_unix_days(-1), Date::new(1969, 12, 31).unwrap());
+        for days in [-719_468, -1, 0, 11_016, 19_723, 2_932_896] {
+            assert_eq!(Date::from_unix_days(days).to_unix_days(), days);
+        }
+    }
+
+    #[test]
+    fn shifts_offset_date_times_to_utc() {
+        let utc = |text: &str| DateTime::parse_iso(text).map(|value| value.to_utc().to_string());
+        assert_eq!(
+            utc("2025-01-01T01:30:00+02:00").as_deref(),
+            Some("2024-12-31T23:30:00Z")
+        );
+        assert_eq!(
+            utc("2024-02-28T20:15:05.5-05:45").as_deref(),
+            Some("2024-02-29T02:00:05.5Z")
+        );
+        assert_eq!(
+            utc("2025-01-01T12:00:00").as_deref(),
+            Some("2025-01-01T12:00:00")
+        );
+    }
+}
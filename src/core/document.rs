@@ -105,9 +105,16 @@ pub struct SourceMeta {
     pub top_level_shape: TopLevelShape,
     pub tabular_columns: Option<Vec<String>>,
     pub header_present: Option<bool>,
+    /// Per-column metadata, populated for tabular sources only.
+    pub columns: Vec<ColumnMeta>,
 }
 
 impl SourceMeta {
+    /// Returns the metadata for a named column.
+    pub fn column(&self, name: &str) -> Option<&ColumnMeta> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+
     /// Returns `true` when the source shape is tabular.
     pub fn is_tabular(&self) -> bool {
         matches!(
@@ -117,6 +124,162 @@ impl SourceMeta {
     }
 }
 
+/// Inferred and user-supplied metadata for one table column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnMeta {
+    pub name: String,
+    /// Most frequent kind among the non-null cells.
+    pub kind: ColumnKind,
+    pub nullable: bool,
+    /// Widest cell text in characters, not counting the header.
+    pub max_width: usize,
+    pub label: Option<String>,
+    pub unit: Option<String>,
+    pub description: Option<String>,
+}
+
+impl ColumnMeta {
+    /// Infers kind, nullability, and display width from a column's cells.
+    pub fn infer<'a>(name: impl Into<String>, cells: impl IntoIterator<Item = &'a Value>) -> Self {
+        let mut counts: Vec<(ColumnKind, usize)> = Vec::new();
+        let mut nullable = false;
+        let mut max_width = 0;
+        for cell in cells {
+            let kind = ColumnKind::of(cell);
+            if kind == ColumnKind::Null {
+                nullable = true;
+            } else if let Some((_, count)) = counts.iter_mut().find(|(seen, _)| *seen == kind) {
+                *count += 1;
+            } else {
+                counts.push((kind, 1));
+            }
+            if let Some(text) = cell.scalar_text() {
+                max_width = max_width.max(text.chars().count());
+            }
+        }
+        let kind = counts
+            .iter()
+            .rev()
+            .max_by_key(|(_, count)| *count)
+            .map_or(ColumnKind::Null, |(kind, _)| *kind);
+        Self {
+            name: name.into(),
+            kind,
+            nullable,
+            max_width,
+            label: None,
+            unit: None,
+            description: None,
+        }
+    }
+
+    /// Returns the header text: the label (or name), plus ` (unit)` when set.
+    pub fn header(&self) -> String {
+        let title = self.label.as_deref().unwrap_or(&self.name);
+        match &self.unit {
+            Some(unit) => format!("{title} ({unit})"),
+            None => title.to_string(),
+        }
+    }
+}
+
+/// Infers column metadata for a tabular root.
+///
+/// Record rows use `names`; matrix rows are named by 1-based column number.
+pub fn infer_columns(root: &Value, names: Option<&[String]>) -> Vec<ColumnMeta> {
+    let Value::List(rows) = root else {
+        return Vec::new();
+    };
+    match names {
+        Some(names) => names
+            .iter()
+            .map(|name| {
+                ColumnMeta::infer(
+                    name.clone(),
+                    rows.iter().map(|row| match row {
+                        Value::Object(values) => values.get(name).unwrap_or(&Value::Null),
+                        _ => &Value::Null,
+                    }),
+                )
+            })
+            .collect(),
+        None => {
+            let width = match rows.first() {
+                Some(Value::List(cells)) => cells.len(),
+                _ => 0,
+            };
+            (0..width)
+                .map(|index| {
+                    ColumnMeta::infer(
+                        (index + 1).to_string(),
+                        rows.iter().map(|row| match row {
+                            Value::List(cells) => cells.get(index).unwrap_or(&Value::Null),
+                            _ => &Value::Null,
+                        }),
+                    )
+                })
+                .collect()
+        }
+    }
+}
+
+/// Kind of value a column holds, as seen by backends and inspect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    Null,
+    Bool,
+    Integer,
+    Float,
+    Decimal,
+    Date,
+    DateTime,
+    Time,
+    String,
+    Nested,
+}
+
+impl ColumnKind {
+    /// Classifies one cell, looking through `Lexical` wrappers.
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::Lexical { value, .. } => Self::of(value),
+            Value::Null => Self::Null,
+            Value::Bool(_) => Self::Bool,
+            Value::Integer(_) => Self::Integer,
+            Value::Float(_) => Self::Float,
+            Value::Decimal(_) => Self::Decimal,
+            Value::Date(_) => Self::Date,
+            Value::DateTime(_) => Self::DateTime,
+            Value::Time(_) => Self::Time,
+            Value::String(_) => Self::String,
+            Value::List(_) | Value::Object(_) => Self::Nested,
+        }
+    }
+
+    /// Returns `true` for integer, float, and decimal columns.
+    pub fn is_numeric(self) -> bool {
+        matches!(self, Self::Integer | Self::Float | Self::Decimal)
+    }
+}
+
+impl Display for ColumnKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Null => "null",
+            Self::Bool => "bool",
+            Self::Integer => "integer",
+            Self::Float => "float",
+            Self::Decimal => "decimal",
+            Self::Date => "date",
+            Self::DateTime => "datetime",
+            Self::Time => "time",
+            Self::String => "string",
+            Self::Nested => "nested",
+        };
+        write!(f, "{value}")
+    }
+}
+
 /// Top-level source shape used for backend inference and table rendering.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TopLevelShape {
@@ -139,3 +302,48 @@ impl Display for TopLevelShape {
         write!(f, "{value}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::{ColumnKind, infer_columns};
+    use crate::core::Value;
+
+    #[test]
+    fn infers_dominant_kind_nullability_and_width() {
+        let row = |amount: Value, note: &str| {
+            Value::Object(IndexMap::from([
+                ("amount".to_string(), amount),
+                ("note".to_string(), Value::String(note.to_string())),
+            ]))
+        };
+        let root = Value::List(vec![
+            row(Value::Integer(12), "ok"),
+            row(Value::Null, "pending"),
+            row(Value::Integer(1500), "ok"),
+            row(Value::String("n/a".to_string()), "ok"),
+        ]);
+        let columns = infer_columns(&root, Some(&["amount".to_string(), "note".to_string()]));
+
+        assert_eq!(columns[0].kind, ColumnKind::Integer);
+        assert!(columns[0].nullable);
+        assert_eq!(columns[0].max_width, 4);
+        assert_eq!(columns[1].kind, ColumnKind::String);
+        assert!(!columns[1].nullable);
+        assert_eq!(columns[1].max_width, 7);
+    }
+
+    #[test]
+    fn names_matrix_columns_by_position() {
+        let root = Value::List(vec![
+            Value::List(vec![Value::String("a".to_string()), Value::Float(1.5)]),
+            Value::List(vec![Value::String("b".to_string()), Value::Float(2.0)]),
+        ]);
+        let columns = infer_columns(&root, None);
+
+        assert_eq!(columns[0].name, "1");
+        assert_eq!(columns[1].name, "2");
+        assert_eq!(columns[1].kind, ColumnKind::Float);
+    }
+}
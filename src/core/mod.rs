@@ -1,5 +1,10 @@
+mod datetime;
 mod document;
 mod value;
 
-pub use document::{Document, Origin, SourceFormat, SourceMeta, TopLevelShape};
+pub use datetime::{Date, DateTime, Time};
+pub use document::{
+    ColumnKind, ColumnMeta, Document, Origin, SourceFormat, SourceMeta, TopLevelShape,
+    infer_columns,
+};
 pub use value::Value;
@@ -1,4 +1,7 @@
-use std::collections::BTreeMap;
+use indexmap::IndexMap;
+use serde_json::{Map, Number, Value as JsonValue};
+
+use crate::core::{Date, DateTime, Time};
 
 /// Normalized value tree shared by every input format and backend.
 #[derive(Debug, Clone, PartialEq)]
@@ -7,9 +10,20 @@ pub enum Value {
     Bool(bool),
     Integer(i64),
     Float(f64),
+    /// Exact decimal number kept in plain `[-]digits[.digits]` notation.
+    Decimal(String),
+    Date(Date),
+    DateTime(DateTime),
+    Time(Time),
+    /// Number paired with the exact text the source spelled it with, such as
+    /// `1.50`, `007`, or `1e3`.
+    Lexical {
+        value: Box<Value>,
+        text: String,
+    },
     String(String),
     List(Vec<Value>),
-    Object(BTreeMap<String, Value>),
+    Object(IndexMap<String, Value>),
 }
 
 impl Value {
@@ -17,7 +31,16 @@ impl Value {
     pub fn is_scalar(&self) -> bool {
         matches!(
             self,
-            Self::Null | Self::Bool(_) | Self::Integer(_) | Self::Float(_) | Self::String(_)
+            Self::Null
+                | Self::Bool(_)
+                | Self::Integer(_)
+                | Self::Float(_)
+                | Self::Decimal(_)
+                | Self::Date(_)
+                | Self::DateTime(_)
+                | Self::Time(_)
+                | Self::Lexical { .. }
+                | Self::String(_)
         )
     }
 
@@ -28,8 +51,58 @@ impl Value {
             Self::Bool(value) => Some(value.to_string()),
             Self::Integer(value) => Some(value.to_string()),
             Self::Float(value) => Some(value.to_string()),
+            Self::Decimal(value) => Some(value.clone()),
+            Self::Date(value) => Some(value.to_string()),
+            Self::DateTime(value) => Some(value.to_string()),
+            Self::Time(value) => Some(value.to_string()),
+            Self::Lexical { text, .. } => Some(text.clone()),
             Self::String(value) => Some(value.clone()),
             Self::List(_) | Self::Object(_) => None,
         }
     }
+
+    /// Returns the typed value behind a `Lexical` wrapper, or `self`.
+    pub fn as_typed(&self) -> &Value {
+        match self {
+            Self::Lexical { value, .. } => value,
+            _ => self,
+        }
+    }
+
+    /// Converts the tree into JSON. Dates and times become ISO 8601 strings,
+    /// decimals keep their exact digits, and floats that JSON cannot
+    /// represent become `null`.
+    pub fn to_json(&self) -> JsonValue {
+        match self.as_typed() {
+            Self::Null => JsonValue::Null,
+            Self::Bool(value) => JsonValue::Bool(*value),
+            Self::Integer(value) => JsonValue::Number((*value).into()),
+            Self::Float(value) => {
+                Number::from_f64(*value).map_or(JsonValue::Null, JsonValue::Number)
+            }
+            Self::Decimal(text) => text
+                .parse::<Number>()
+                .map_or_else(|_| JsonValue::String(text.clone()), JsonValue::Number),
+            Self::List(items) => JsonValue::Array(items.iter().map(Self::to_json).collect()),
+            Self::Object(values) => JsonValue::Object(
+                values
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.to_json()))
+                    .collect::<Map<_, _>>(),
+            ),
+            scalar => JsonValue::String(scalar.scalar_text().unwrap_or_default()),
+        }
+    }
+
+    /// Recursively reorders every object in the tree by lexical key order.
+    pub fn sort_keys(&mut self) {
+        match self {
+            Self::List(values) => values.iter_mut().for_each(Self::sort_keys),
+            Self::Object(values) => {
+                values.sort_unstable_keys();
+                values.values_mut().for_each(Self::sort_keys);
+            }
+            _ => {}
+        }
+    }
 }
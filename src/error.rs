@@ -1,5 +1,6 @@
-use std::fmt::{Display, Formatter};
+use std::fmt::{Display, Formatter, Write as _};
 use std::io;
+use std::ops::Range;
 use std::path::PathBuf;
 
 use crate::backend::{ArtifactKind, BackendKind};
@@ -24,6 +25,7 @@ pub enum DocpackError {
         origin: Origin,
         detail: String,
         path: Option<String>,
+        location: Option<Box<SourceLocation>>,
     },
     UnsupportedKey {
         format: SourceFormat,
@@ -45,6 +47,16 @@ pub enum DocpackError {
     InvalidRootName {
         supplied: String,
     },
+    PointerNotFound {
+        origin: Origin,
+        pointer: String,
+        resolved: String,
+        detail: String,
+    },
+    NotTabular {
+        origin: Origin,
+        problems: Vec<String>,
+    },
     ManifestLoad {
         path: PathBuf,
         detail: String,
@@ -56,6 +68,46 @@ pub enum DocpackError {
     Inference {
         detail: String,
     },
+    Transform {
+        step: usize,
+        operation: String,
+        detail: String,
+    },
+    DerivedSource {
+        source_id: String,
+        detail: String,
+    },
+    MergeConflict {
+        source_id: String,
+        layer: String,
+        path: String,
+        existing: String,
+        incoming: String,
+    },
+    Redaction {
+        scope: String,
+        target: String,
+        detail: String,
+    },
+    SchemaLoad {
+        path: PathBuf,
+        detail: String,
+    },
+    SchemaViolation {
+        origin: Origin,
+        schema: PathBuf,
+        violations: Vec<String>,
+    },
+    SchemasFailed {
+        failed: usize,
+        total: usize,
+        report: String,
+    },
+    ChecksFailed {
+        failed: usize,
+        total: usize,
+        report: String,
+    },
     Render {
         backend: BackendKind,
         artifact: ArtifactKind,
@@ -75,8 +127,15 @@ impl Display for DocpackError {
                 origin,
                 detail,
                 path,
+                location,
             } => {
-                if let Some(path) = path {
+                if let Some(location) = location {
+                    write!(
+                        f,
+                        "failed to parse {format} from {origin} at line {}, column {}: {detail}",
+                        location.line, location.column
+                    )
+                } else if let Some(path) = path {
                     write!(
                         f,
                         "failed to parse {format} from {origin} at {path}: {detail}"
@@ -116,6 +175,25 @@ impl Display for DocpackError {
             Self::InvalidRootName { supplied } => {
                 write!(f, "invalid root name after sanitization: {supplied}")
             }
+            Self::NotTabular { origin, problems } => write!(
+                f,
+                "cannot treat {origin} as a table: {}",
+                problems.join("; ")
+            ),
+            Self::PointerNotFound {
+                origin,
+                pointer,
+                resolved,
+                detail,
+            } => write!(
+                f,
+                "cannot select {pointer} in {origin}: {detail}; deepest resolved path is {}",
+                if resolved.is_empty() {
+                    "the document root"
+                } else {
+                    resolved
+                }
+            ),
             Self::ManifestLoad { path, detail } => {
                 write!(f, "failed to load manifest {}: {detail}", path.display())
             }
@@ -126,6 +204,57 @@ impl Display for DocpackError {
                 problems.join("\n- ")
             ),
             Self::Inference { detail } => write!(f, "inference error: {detail}"),
+            Self::Transform {
+                step,
+                operation,
+                detail,
+            } => write!(f, "transform step {step} ({operation}) failed: {detail}"),
+            Self::DerivedSource { source_id, detail } => {
+                write!(f, "cannot derive source '{source_id}': {detail}")
+            }
+            Self::MergeConflict {
+                source_id,
+                layer,
+                path,
+                existing,
+                incoming,
+            } => write!(
+                f,
+                "cannot merge layer '{layer}' into source '{source_id}': {path} is {} {existing} in earlier layers but {} {incoming} in '{layer}'",
+                article(existing),
+                article(incoming)
+            ),
+            Self::Redaction {
+                scope,
+                target,
+                detail,
+            } => write!(f, "cannot redact {target} in {scope}: {detail}"),
+            Self::SchemaLoad { path, detail } => {
+                write!(f, "failed to load schema {}: {detail}", path.display())
+            }
+            Self::SchemaViolation {
+                origin,
+                schema,
+                violations,
+            } => write!(
+                f,
+                "{origin} does not match schema {}:\n- {}",
+                schema.display(),
+                violations.join("\n- ")
+            ),
+            Self::SchemasFailed {
+                failed,
+                total,
+                report,
+            } => write!(
+                f,
+                "{failed} of {total} source(s) do not match their schema:\n{report}"
+            ),
+            Self::ChecksFailed {
+                failed,
+                total,
+                report,
+            } => write!(f, "{failed} of {total} data check(s) failed:\n{report}"),
             Self::Render {
                 backend,
                 artifact,
@@ -135,6 +264,124 @@ impl Display for DocpackError {
     }
 }
 
+impl DocpackError {
+    /// Renders the offending source line with a caret under the parse error
+    /// location, when the error carries one.
+    pub fn source_excerpt(&self) -> Option<String> {
+        let Self::Parse {
+            location: Some(location),
+            ..
+        } = self
+        else {
+            return None;
+        };
+        let number = location.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let mut excerpt = String::new();
+        writeln!(excerpt, "{gutter} |").unwrap();
+        writeln!(excerpt, "{number} | {}", location.line_text).unwrap();
+        let indent = location
+            .line_text
+            .chars()
+            .take(location.column.saturating_sub(1))
+            .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        let start = location.column_byte();
+        let end = floor_char_boundary(
+            &location.line_text,
+            (start + location.span.len()).min(location.line_text.len()),
+        );
+        let width = location.line_text[start..end].chars().count().max(1);
+        writeln!(excerpt, "{gutter} | {indent}{}", "^".repeat(width)).unwrap();
+        Some(excerpt)
+    }
+}
+
+/// Line, column, and byte span of a parse error inside its source text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// Byte range of the offending input.
+    pub span: Range<usize>,
+    /// Full text of the offending line without its line terminator.
+    pub line_text: String,
+}
+
+impl SourceLocation {
+    /// Locates a byte span inside `text`.
+    pub fn from_span(text: &str, span: Range<usize>) -> Self {
+        let start = floor_char_boundary(text, span.start.min(text.len()));
+        let line_start = text[..start].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = text[start..]
+            .find('\n')
+            .map_or(text.len(), |index| start + index);
+        let line_end = clamp_line_end(text, line_start, line_end);
+        let end = span.end.clamp(start, line_end.max(start));
+        Self {
+            line: text[..line_start].matches('\n').count() + 1,
+            column: text[line_start..start].chars().count() + 1,
+            span: start..end,
+            line_text: text[line_start..line_end].to_string(),
+        }
+    }
+
+    /// Locates a 1-based line and column (in characters) inside `text`,
+    /// covering a single character.
+    pub fn from_line_column(text: &str, line: usize, column: usize) -> Self {
+        let line_start = text
+            .split_inclusive('\n')
+            .take(line.saturating_sub(1))
+            .map(str::len)
+            .sum::<usize>();
+        let line_end = text[line_start..]
+            .find('\n')
+            .map_or(text.len(), |index| line_start + index);
+        let line_end = clamp_line_end(text, line_start, line_end);
+        let start = text[line_start..line_end]
+            .char_indices()
+            .nth(column.saturating_sub(1))
+            .map_or(line_end, |(index, _)| line_start + index);
+        let end = text[start..line_end]
+            .chars()
+            .next()
+            .map_or(start, |ch| start + ch.len_utf8());
+        Self::from_span(text, start..end)
+    }
+
+    fn column_byte(&self) -> usize {
+        self.line_text
+            .char_indices()
+            .nth(self.column.saturating_sub(1))
+            .map_or(self.line_text.len(), |(index, _)| index)
+    }
+}
+
+fn article(kind: &str) -> &'static str {
+    if kind.starts_with(['a', 'e', 'i', 'o', 'u']) {
+        "an"
+    } else {
+        "a"
+    }
+}
+
+fn clamp_line_end(text: &str, line_start: usize, line_end: usize) -> usize {
+    if line_end > line_start && text.as_bytes()[line_end - 1] == b'\r' {
+        line_end - 1
+    } else {
+        line_end
+    }
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
 impl std::error::Error for DocpackError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
@@ -1,9 +1,10 @@
-use std::collections::BTreeMap;
 use std::io::Cursor;
 
-use crate::core::{Document, SourceMeta, TopLevelShape, Value};
+use indexmap::IndexMap;
+
+use crate::core::{Document, SourceMeta, TopLevelShape, Value, infer_columns};
 use crate::error::{DocpackError, DocpackResult};
-use crate::input::{SourceSpec, coerce_text_cell};
+use crate::input::{SourceSpec, child_path, coerce_text_cell, coerce_typed_cell};
 
 pub fn parse(spec: &SourceSpec) -> DocpackResult<Document> {
     let mut reader = csv::ReaderBuilder::new()
@@ -28,6 +29,7 @@ fn parse_records<R: std::io::Read>(
             origin: spec.origin.clone(),
             detail: error.to_string(),
             path: None,
+            location: None,
         })?
         .iter()
         .map(str::to_string)
@@ -41,6 +43,7 @@ fn parse_records<R: std::io::Read>(
             origin: spec.origin.clone(),
             detail: error.to_string(),
             path: None,
+            location: None,
         })?;
         if record.len() != expected {
             return Err(DocpackError::InconsistentRowWidth {
@@ -50,22 +53,25 @@ fn parse_records<R: std::io::Read>(
                 row_index: index + 2,
             });
         }
-        let mut row = BTreeMap::new();
+        let mut row = IndexMap::new();
         for (column, cell) in headers.iter().zip(record.iter()) {
-            row.insert(column.clone(), coerce_text_cell(cell));
+            row.insert(column.clone(), coerce_cell(spec, index, column, cell)?);
         }
         rows.push(Value::Object(row));
     }
 
+    let root = Value::List(rows);
+    let columns = infer_columns(&root, Some(&headers));
     Ok(Document {
         source_id: spec.source_id.clone(),
-        root: Value::List(rows),
+        root,
         meta: SourceMeta {
             format: spec.format,
             origin: spec.origin.clone(),
             top_level_shape: TopLevelShape::TabularRecords,
             tabular_columns: Some(headers),
             header_present: Some(true),
+            columns,
         },
     })
 }
@@ -83,6 +89,7 @@ fn parse_matrix<R: std::io::Read>(
             origin: spec.origin.clone(),
             detail: error.to_string(),
             path: None,
+            location: None,
         })?;
         let actual = record.len();
         match expected_width {
@@ -97,27 +104,48 @@ fn parse_matrix<R: std::io::Read>(
             None => expected_width = Some(actual),
             _ => {}
         }
-        rows.push(Value::List(record.iter().map(coerce_text_cell).collect()));
+        let row = record
+            .iter()
+            .enumerate()
+            .map(|(column, cell)| coerce_cell(spec, index, &(column + 1).to_string(), cell))
+            .collect::<DocpackResult<Vec<_>>>()?;
+        rows.push(Value::List(row));
     }
 
+    let root = Value::List(rows);
+    let columns = infer_columns(&root, None);
     Ok(Document {
         source_id: spec.source_id.clone(),
-        root: Value::List(rows),
+        root,
         meta: SourceMeta {
             format: spec.format,
             origin: spec.origin.clone(),
             top_level_shape: TopLevelShape::TabularMatrix,
             tabular_columns: None,
             header_present: Some(false),
+            columns,
         },
     })
 }
 
+fn coerce_cell(spec: &SourceSpec, row: usize, column: &str, cell: &str) -> DocpackResult<Value> {
+    let Some(column_type) = spec.options.column_types.get(column) else {
+        return Ok(coerce_text_cell(cell, &spec.options));
+    };
+    coerce_typed_cell(cell, *column_type).ok_or_else(|| DocpackError::Parse {
+        format: spec.format,
+        origin: spec.origin.clone(),
+        detail: format!("expected an ISO 8601 {column_type}, got '{cell}'"),
+        path: Some(child_path(&child_path("", row.to_string()), column)),
+        location: None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::parse;
-    use crate::core::{SourceFormat, TopLevelShape, Value};
-    use crate::input::SourceSpec;
+    use crate::core::{Date, SourceFormat, TopLevelShape, Value};
+    use crate::input::{ColumnType, ParseOptions, SourceSpec};
 
     #[test]
     fn parses_csv_with_header_as_records() {
@@ -156,4 +184,45 @@ mod tests {
             _ => panic!("expected list root"),
         }
     }
+
+    #[test]
+    fn parses_typed_date_columns() {
+        let options = ParseOptions {
+            column_types: [("when".to_string(), ColumnType::Date)]
+                .into_iter()
+                .collect(),
+            ..ParseOptions::default()
+        };
+        let spec = SourceSpec::from_stdin(
+            "events",
+            SourceFormat::Csv,
+            b"name,when\nlaunch,2025-03-01\nreview,\n".to_vec(),
+            false,
+            None,
+        )
+        .with_options(options.clone());
+        let doc = parse(&spec).unwrap();
+        let Value::List(rows) = &doc.root else {
+            panic!("expected list root");
+        };
+        let Value::Object(first) = &rows[0] else {
+            panic!("expected record row");
+        };
+        assert_eq!(
+            first.get("when"),
+            Some(&Value::Date(Date::new(2025, 3, 1).unwrap()))
+        );
+
+        let spec = SourceSpec::from_stdin(
+            "events",
+            SourceFormat::Csv,
+            b"name,when\nlaunch,March 1st\n".to_vec(),
+            false,
+            None,
+        )
+        .with_options(options);
+        let error = parse(&spec).unwrap_err().to_string();
+        assert!(error.contains("at /0/when"));
+        assert!(error.contains("expected an ISO 8601 date"));
+    }
 }
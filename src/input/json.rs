@@ -1,20 +1,29 @@
-use std::collections::BTreeMap;
+use indexmap::IndexMap;
 
 use serde_json::Value as JsonValue;
 
 use crate::core::{Document, SourceMeta, Value};
-use crate::error::{DocpackError, DocpackResult};
-use crate::input::{SourceSpec, infer_shape};
+use crate::error::{DocpackError, DocpackResult, SourceLocation};
+use crate::input::{
+    ParseOptions, SourceSpec, decimal_from_text, infer_shape, keep_numeric_text, strip_location,
+};
 
 pub fn parse(spec: &SourceSpec) -> DocpackResult<Document> {
     let value: JsonValue =
         serde_json::from_slice(&spec.bytes).map_err(|error| DocpackError::Parse {
             format: spec.format,
             origin: spec.origin.clone(),
-            detail: error.to_string(),
+            detail: strip_location(&error.to_string(), error.line(), error.column()),
             path: None,
+            location: (error.line() > 0).then(|| {
+                Box::new(SourceLocation::from_line_column(
+                    &String::from_utf8_lossy(&spec.bytes),
+                    error.line(),
+                    error.column(),
+                ))
+            }),
         })?;
-    let root = convert_json(value);
+    let root = convert_json(value, &spec.options);
     Ok(Document {
         source_id: spec.source_id.clone(),
         meta: SourceMeta {
@@ -23,34 +32,41 @@ pub fn parse(spec: &SourceSpec) -> DocpackResult<Document> {
             top_level_shape: infer_shape(&root),
             tabular_columns: None,
             header_present: None,
+            columns: Vec::new(),
         },
         root,
     })
 }
 
-fn convert_json(value: JsonValue) -> Value {
+fn convert_json(value: JsonValue, options: &ParseOptions) -> Value {
     match value {
         JsonValue::Null => Value::Null,
         JsonValue::Bool(value) => Value::Bool(value),
         JsonValue::Number(value) => {
-            if let Some(integer) = value.as_i64() {
+            let text = value.as_str();
+            let parsed = if let Some(integer) = value.as_i64() {
                 Value::Integer(integer)
-            } else if let Some(unsigned) = value.as_u64() {
-                match i64::try_from(unsigned) {
-                    Ok(integer) => Value::Integer(integer),
-                    Err(_) => Value::Float(unsigned as f64),
-                }
+            } else if let Some(decimal) = decimal_from_text(text, options) {
+                decimal
             } else {
-                Value::Float(value.as_f64().unwrap_or_default())
-            }
+                // `as_f64` gives up on literals past the `f64` range, which
+                // parse to an infinity here instead.
+                Value::Float(text.parse().unwrap_or_default())
+            };
+            keep_numeric_text(parsed, text, options)
         }
         JsonValue::String(value) => Value::String(value),
-        JsonValue::Array(values) => Value::List(values.into_iter().map(convert_json).collect()),
+        JsonValue::Array(values) => Value::List(
+            values
+                .into_iter()
+                .map(|value| convert_json(value, options))
+                .collect(),
+        ),
         JsonValue::Object(values) => Value::Object(
             values
                 .into_iter()
-                .map(|(key, value)| (key, convert_json(value)))
-                .collect::<BTreeMap<_, _>>(),
+                .map(|(key, value)| (key, convert_json(value, options)))
+                .collect::<IndexMap<_, _>>(),
         ),
     }
 }
@@ -59,7 +75,7 @@ fn convert_json(value: JsonValue) -> Value {
 mod tests {
     use super::parse;
     use crate::core::{Origin, SourceFormat, TopLevelShape, Value};
-    use crate::input::SourceSpec;
+    use crate::input::{ParseOptions, SourceSpec};
 
     #[test]
     fn parses_json_object_into_document() {
@@ -82,4 +98,48 @@ mod tests {
             _ => panic!("expected object root"),
         }
     }
+
+    #[test]
+    fn keeps_big_integers_and_opted_in_decimals_exact() {
+        let bytes = br#"{"big":18446744073709551616,"price":0.1,"rate":2.5e-3}"#.to_vec();
+        let spec = SourceSpec::from_stdin("data", SourceFormat::Json, bytes.clone(), false, None);
+        let doc = parse(&spec).unwrap();
+        let Value::Object(map) = &doc.root else {
+            panic!("expected object root");
+        };
+        assert_eq!(
+            map.get("big"),
+            Some(&Value::Decimal("18446744073709551616".to_string()))
+        );
+        assert_eq!(map.get("price"), Some(&Value::Float(0.1)));
+
+        let spec = SourceSpec::from_stdin("data", SourceFormat::Json, bytes, false, None)
+            .with_options(ParseOptions {
+                exact_decimals: true,
+                ..ParseOptions::default()
+            });
+        let doc = parse(&spec).unwrap();
+        let Value::Object(map) = &doc.root else {
+            panic!("expected object root");
+        };
+        assert_eq!(map.get("price"), Some(&Value::Decimal("0.1".to_string())));
+        assert_eq!(map.get("rate"), Some(&Value::Decimal("0.0025".to_string())));
+    }
+
+    #[test]
+    fn keeps_huge_exponents_as_floats() {
+        let bytes = br#"{"a":1e9223372036854775807,"b":-1e400,"c":1e-400}"#.to_vec();
+        let spec = SourceSpec::from_stdin("data", SourceFormat::Json, bytes, false, None)
+            .with_options(ParseOptions {
+                exact_decimals: true,
+                ..ParseOptions::default()
+            });
+        let doc = parse(&spec).unwrap();
+        let Value::Object(map) = &doc.root else {
+            panic!("expected object root");
+        };
+        assert_eq!(map.get("a"), Some(&Value::Float(f64::INFINITY)));
+        assert_eq!(map.get("b"), Some(&Value::Float(f64::NEG_INFINITY)));
+        assert_eq!(map.get("c"), Some(&Value::Float(0.0)));
+    }
 }
@@ -1,38 +1,66 @@
 mod csv;
 mod detect;
 mod json;
+mod schema;
+mod select;
 mod source;
+mod tabular;
 mod toml;
 mod xlsx;
 mod yaml;
 
 pub use detect::detect_format;
-pub use source::SourceSpec;
+pub use schema::Schema;
+pub use select::select_pointer;
+pub use source::{ColumnType, ParseOptions, SourceSpec};
+pub use tabular::promote_records;
 
-use crate::core::{Document, TopLevelShape, Value};
+use crate::core::{Date, DateTime, Document, Time, TopLevelShape, Value};
 use crate::error::DocpackResult;
 
 /// Parses a raw source into a normalized [`Document`].
 pub fn parse_source(spec: &SourceSpec) -> DocpackResult<Document> {
-    match spec.format {
+    let doc = match spec.format {
         crate::core::SourceFormat::Csv => csv::parse(spec),
         crate::core::SourceFormat::Json => json::parse(spec),
         crate::core::SourceFormat::Yaml => yaml::parse(spec),
         crate::core::SourceFormat::Toml => toml::parse(spec),
         crate::core::SourceFormat::Xlsx => xlsx::parse(spec),
+    }?;
+    let doc = match &spec.options.select {
+        Some(pointer) => select_pointer(doc, pointer)?,
+        None => doc,
+    };
+    if spec.options.tabular {
+        promote_records(doc)
+    } else {
+        Ok(doc)
     }
 }
 
 pub(crate) fn infer_shape(value: &Value) -> TopLevelShape {
     match value {
-        Value::Null | Value::Bool(_) | Value::Integer(_) | Value::Float(_) | Value::String(_) => {
-            TopLevelShape::Scalar
-        }
+        Value::Null
+        | Value::Bool(_)
+        | Value::Integer(_)
+        | Value::Float(_)
+        | Value::Decimal(_)
+        | Value::Date(_)
+        | Value::DateTime(_)
+        | Value::Time(_)
+        | Value::Lexical { .. }
+        | Value::String(_) => TopLevelShape::Scalar,
         Value::List(_) => TopLevelShape::List,
         Value::Object(_) => TopLevelShape::Object,
     }
 }
 
+/// Drops the ` at line N column M` marker that serde-based parsers embed for
+/// the reported position, since the location is carried separately.
+pub(crate) fn strip_location(message: &str, line: usize, column: usize) -> String {
+    message.replacen(&format!(" at line {line} column {column}"), "", 1)
+}
+
 pub(crate) fn child_path(base: &str, segment: impl AsRef<str>) -> String {
     if base.is_empty() {
         format!("/{}", segment.as_ref())
@@ -41,17 +69,162 @@ pub(crate) fn child_path(base: &str, segment: impl AsRef<str>) -> String {
     }
 }
 
-pub(crate) fn coerce_text_cell(value: &str) -> Value {
+pub(crate) fn coerce_text_cell(value: &str, options: &ParseOptions) -> Value {
     let trimmed = value.trim();
     if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("none") {
         Value::Null
     } else if let Ok(parsed) = trimmed.parse::<bool>() {
         Value::Bool(parsed)
     } else if let Ok(parsed) = trimmed.parse::<i64>() {
-        Value::Integer(parsed)
+        keep_numeric_text(Value::Integer(parsed), trimmed, options)
+    } else if let Some(decimal) = decimal_from_text(trimmed, options) {
+        keep_numeric_text(decimal, trimmed, options)
     } else if let Ok(parsed) = trimmed.parse::<f64>() {
-        Value::Float(parsed)
+        keep_numeric_text(Value::Float(parsed), trimmed, options)
     } else {
         Value::String(value.to_string())
     }
 }
+
+/// Wraps a parsed number in `Value::Lexical` when the source opted into
+/// keeping numeric text and the spelling differs from the canonical one.
+pub(crate) fn keep_numeric_text(value: Value, text: &str, options: &ParseOptions) -> Value {
+    if options.keep_numeric_text
+        && value
+            .scalar_text()
+            .is_some_and(|canonical| canonical != text)
+    {
+        Value::Lexical {
+            value: Box::new(value),
+            text: text.to_string(),
+        }
+    } else {
+        value
+    }
+}
+
+/// Parses a cell of a column with a declared type; `None` means the text does
+/// not match that type.
+pub(crate) fn coerce_typed_cell(value: &str, column_type: ColumnType) -> Option<Value> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Some(Value::Null);
+    }
+    match column_type {
+        ColumnType::Date => Date::parse_iso(trimmed).map(Value::Date),
+        ColumnType::Datetime => DateTime::parse_iso(trimmed).map(Value::DateTime),
+        ColumnType::Time => Time::parse_iso(trimmed).map(Value::Time),
+    }
+}
+
+/// Returns a `Decimal` for numeric text that would lose precision as `f64`.
+///
+/// Integers outside the `i64` range and literals that overflow `f64` become
+/// decimals as long as their plain form stays within `MAX_DECIMAL_DIGITS`
+/// digits; every other decimal literal only does so when the source opted
+/// into exact decimals. Text that cannot be normalized, such as `1e400`,
+/// returns `None` and is left to the caller's float parsing.
+pub(crate) fn decimal_from_text(text: &str, options: &ParseOptions) -> Option<Value> {
+    let normalized = normalize_decimal(text)?;
+    let integral = !text.contains(['.', 'e', 'E']);
+    let overflows = !text.parse::<f64>().is_ok_and(f64::is_finite);
+    if integral || overflows || options.exact_decimals {
+        Some(Value::Decimal(normalized))
+    } else {
+        None
+    }
+}
+
+/// Longest digit run an exponent may expand a decimal literal into.
+const MAX_DECIMAL_DIGITS: usize = 256;
+
+/// Rewrites a numeric literal into plain `[-]digits[.digits]` notation.
+///
+/// Exponents are expanded and redundant leading zeros are dropped, while
+/// fractional trailing zeros are kept so `1.50` stays `1.50`. Returns `None`
+/// when the expansion would exceed `MAX_DECIMAL_DIGITS` digits.
+pub(crate) fn normalize_decimal(text: &str) -> Option<String> {
+    let (negative, unsigned) = match text.as_bytes().first()? {
+        b'-' => (true, &text[1..]),
+        b'+' => (false, &text[1..]),
+        _ => (false, text),
+    };
+    let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i64>().ok()?),
+        None => (unsigned, 0),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let all_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+    if (integer.is_empty() && fraction.is_empty()) || !all_digits(integer) || !all_digits(fraction)
+    {
+        return None;
+    }
+
+    let (integer, fraction) = if exponent == 0 {
+        (integer.to_string(), fraction.to_string())
+    } else {
+        let digits = format!("{integer}{fraction}");
+        let point = i64::try_from(integer.len()).ok()?.checked_add(exponent)?;
+        let expanded = if point <= 0 {
+            point.unsigned_abs().saturating_add(digits.len() as u64)
+        } else {
+            point.unsigned_abs().max(digits.len() as u64)
+        };
+        if expanded > MAX_DECIMAL_DIGITS as u64 {
+            return None;
+        }
+        if point <= 0 {
+            (
+                String::new(),
+                format!("{}{digits}", "0".repeat(point.unsigned_abs() as usize)),
+            )
+        } else if point as usize >= digits.len() {
+            (
+                format!("{digits}{}", "0".repeat(point as usize - digits.len())),
+                String::new(),
+            )
+        } else {
+            let (integer, fraction) = digits.split_at(point as usize);
+            (integer.to_string(), fraction.to_string())
+        }
+    };
+    let integer = match integer.trim_start_matches('0') {
+        "" => "0",
+        trimmed => trimmed,
+    };
+    let mut normalized = String::new();
+    if negative {
+        normalized.push('-');
+    }
+    normalized.push_str(integer);
+    if !fraction.is_empty() {
+        normalized.push('.');
+        normalized.push_str(&fraction);
+    }
+    Some(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_decimal;
+
+    #[test]
+    fn normalizes_decimal_literals_to_plain_notation() {
+        assert_eq!(normalize_decimal("0.1").as_deref(), Some("0.1"));
+        assert_eq!(normalize_decimal("+007.50").as_deref(), Some("7.50"));
+        assert_eq!(normalize_decimal("-1.25e3").as_deref(), Some("-1250"));
+        assert_eq!(normalize_decimal("12e-4").as_deref(), Some("0.0012"));
+        assert_eq!(normalize_decimal(".5").as_deref(), Some("0.5"));
+        assert_eq!(normalize_decimal("inf"), None);
+        assert_eq!(normalize_decimal("1.2.3"), None);
+    }
+
+    #[test]
+    fn rejects_exponents_that_overflow_or_expand_too_far() {
+        assert_eq!(normalize_decimal("1e9223372036854775807"), None);
+        assert_eq!(normalize_decimal("1e-9223372036854775808"), None);
+        assert_eq!(normalize_decimal("1e400"), None);
+        assert_eq!(normalize_decimal("1e-400"), None);
+        assert_eq!(normalize_decimal("1e255").map(|text| text.len()), Some(256));
+    }
+}
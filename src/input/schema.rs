@@ -0,0 +1,155 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use jsonschema::Validator;
+use serde_json::Value as JsonValue;
+
+use crate::core::{Document, Origin};
+use crate::error::{DocpackError, DocpackResult};
+
+/// A compiled JSON Schema read from a local file.
+pub struct Schema {
+    path: PathBuf,
+    validator: Validator,
+}
+
+impl Schema {
+    /// Reads and compiles a schema file. Relative `$ref`s resolve against the
+    /// schema's own location.
+    pub fn load(path: &Path) -> DocpackResult<Self> {
+        let fail = |detail: String| DocpackError::SchemaLoad {
+            path: path.to_path_buf(),
+            detail,
+        };
+        let text = fs::read_to_string(path).map_err(|source| DocpackError::Io {
+            origin: Origin::File(path.to_path_buf()),
+            source,
+        })?;
+        let schema: JsonValue =
+            serde_json::from_str(&text).map_err(|error| fail(error.to_string()))?;
+        let absolute = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let validator = jsonschema::options()
+            .with_base_uri(format!("file://{}", absolute.display()))
+            .should_validate_formats(true)
+            .build(&schema)
+            .map_err(|error| fail(error.to_string()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            validator,
+        })
+    }
+
+    /// Returns the schema file's path as given to [`Schema::load`].
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Checks the normalized value tree, reporting every violation at once as
+    /// `<JSON Pointer>: <message>`, in the order the schema checks them.
+    pub fn validate(&self, doc: &Document) -> DocpackResult<()> {
+        let instance = doc.root.to_json();
+        let violations = self
+            .validator
+            .iter_errors(&instance)
+            .map(|error| {
+                let pointer = error.instance_path.as_str();
+                format!(
+                    "{}: {error}",
+                    if pointer.is_empty() { "/" } else { pointer }
+                )
+            })
+            .collect::<Vec<_>>();
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(DocpackError::SchemaViolation {
+                origin: doc.meta.origin.clone(),
+                schema: self.path.clone(),
+                violations,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use indexmap::IndexMap;
+
+    use super::Schema;
+    use crate::core::{Date, Document, Origin, SourceFormat, SourceMeta, TopLevelShape, Value};
+    use crate::error::DocpackError;
+
+    fn row(region: &str, amount: Value, closed: Value) -> Value {
+        Value::Object(IndexMap::from([
+            ("region".to_string(), Value::String(region.to_string())),
+            ("amount".to_string(), amount),
+            ("closed".to_string(), closed),
+        ]))
+    }
+
+    fn document(rows: Vec<Value>) -> Document {
+        Document {
+            source_id: "sales".to_string(),
+            root: Value::List(rows),
+            meta: SourceMeta {
+                format: SourceFormat::Csv,
+                origin: Origin::Stdin,
+                top_level_shape: TopLevelShape::List,
+                tabular_columns: None,
+                header_present: None,
+                columns: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn reports_every_violation_with_its_pointer() {
+        let dir = std::env::temp_dir().join(format!("docpack-schema-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sales.schema.json");
+        fs::write(
+            &path,
+            r#"{
+  "type": "array",
+  "items": {
+    "type": "object",
+    "required": ["region", "amount"],
+    "properties": {
+      "amount": { "type": "number", "minimum": 0 },
+      "closed": { "type": ["string", "null"], "format": "date" }
+    }
+  }
+}"#,
+        )
+        .unwrap();
+        let schema = Schema::load(&path).unwrap();
+        let date = Value::Date(Date::new(2025, 3, 7).unwrap());
+
+        schema
+            .validate(&document(vec![
+                row("East", Value::Decimal("12.50".to_string()), date.clone()),
+                row("West", Value::Integer(3), Value::Null),
+            ]))
+            .unwrap();
+
+        let error = schema
+            .validate(&document(vec![
+                row("East", Value::Integer(-1), date),
+                row("West", Value::String("n/a".to_string()), Value::Null),
+            ]))
+            .unwrap_err();
+        let DocpackError::SchemaViolation { violations, .. } = &error else {
+            panic!("expected a schema violation, got {error:?}");
+        };
+        assert_eq!(
+            violations,
+            &vec![
+                "/0/amount: -1 is less than the minimum of 0".to_string(),
+                "/1/amount: \"n/a\" is not of type \"number\"".to_string(),
+            ]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
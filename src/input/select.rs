@@ -0,0 +1,134 @@
+use crate::core::{Document, Value};
+use crate::error::{DocpackError, DocpackResult};
+use crate::input::{child_path, infer_shape};
+
+/// Re-roots a document at the value addressed by a JSON Pointer (RFC 6901).
+///
+/// List segments are 0-based indexes, matching the paths the input adapters
+/// report in diagnostics. The empty pointer selects the whole document.
+pub fn select_pointer(doc: Document, pointer: &str) -> DocpackResult<Document> {
+    if pointer.is_empty() {
+        return Ok(doc);
+    }
+    let fail = |resolved: &str, detail: String| DocpackError::PointerNotFound {
+        origin: doc.meta.origin.clone(),
+        pointer: pointer.to_string(),
+        resolved: resolved.to_string(),
+        detail,
+    };
+    let Some(segments) = pointer.strip_prefix('/') else {
+        return Err(fail("", "a JSON Pointer must start with '/'".to_string()));
+    };
+
+    let mut current = &doc.root;
+    let mut resolved = String::new();
+    for segment in segments.split('/') {
+        let key = segment.replace("~1", "/").replace("~0", "~");
+        current = match current {
+            Value::Object(values) => values
+                .get(&key)
+                .ok_or_else(|| fail(&resolved, format!("no key '{key}'")))?,
+            Value::List(values) => {
+                let index = Some(key.as_str())
+                    .filter(|key| key.bytes().all(|byte| byte.is_ascii_digit()))
+                    .filter(|key| *key == "0" || !key.starts_with('0'))
+                    .and_then(|key| key.parse::<usize>().ok())
+                    .ok_or_else(|| fail(&resolved, format!("'{key}' is not a list index")))?;
+                values.get(index).ok_or_else(|| {
+                    fail(
+                        &resolved,
+                        format!(
+                            "index {index} is out of range for a list of {} items",
+                            values.len()
+                        ),
+                    )
+                })?
+            }
+            _ => {
+                return Err(fail(&resolved, format!("a scalar has no child '{key}'")));
+            }
+        };
+        resolved = child_path(&resolved, segment);
+    }
+
+    let root = current.clone();
+    let mut meta = doc.meta;
+    meta.top_level_shape = infer_shape(&root);
+    meta.tabular_columns = None;
+    meta.columns = Vec::new();
+    Ok(Document {
+        source_id: doc.source_id,
+        root,
+        meta,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::select_pointer;
+    use crate::core::{Document, Origin, SourceFormat, SourceMeta, TopLevelShape, Value};
+    use crate::error::DocpackError;
+
+    fn document() -> Document {
+        let quarter = Value::Object(IndexMap::from([(
+            "revenue".to_string(),
+            Value::Integer(120),
+        )]));
+        let year = Value::Object(IndexMap::from([("q1".to_string(), quarter)]));
+        let reports = Value::Object(IndexMap::from([("2025".to_string(), year)]));
+        Document {
+            source_id: "company".to_string(),
+            root: Value::Object(IndexMap::from([
+                ("reports".to_string(), reports),
+                (
+                    "a/b".to_string(),
+                    Value::List(vec![Value::Bool(true), Value::Bool(false)]),
+                ),
+            ])),
+            meta: SourceMeta {
+                format: SourceFormat::Yaml,
+                origin: Origin::Stdin,
+                top_level_shape: TopLevelShape::Object,
+                tabular_columns: None,
+                header_present: None,
+                columns: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn re_roots_at_nested_objects_and_list_items() {
+        let selected = select_pointer(document(), "/reports/2025/q1").unwrap();
+        assert_eq!(
+            selected.root,
+            Value::Object(IndexMap::from([(
+                "revenue".to_string(),
+                Value::Integer(120)
+            )]))
+        );
+        assert_eq!(selected.meta.top_level_shape, TopLevelShape::Object);
+
+        let selected = select_pointer(document(), "/a~1b/1").unwrap();
+        assert_eq!(selected.root, Value::Bool(false));
+        assert_eq!(selected.meta.top_level_shape, TopLevelShape::Scalar);
+    }
+
+    #[test]
+    fn reports_the_deepest_resolved_path() {
+        match select_pointer(document(), "/reports/2025/q3/revenue").unwrap_err() {
+            DocpackError::PointerNotFound {
+                resolved, detail, ..
+            } => {
+                assert_eq!(resolved, "/reports/2025");
+                assert_eq!(detail, "no key 'q3'");
+            }
+            other => panic!("expected PointerNotFound, got {other}"),
+        }
+        match select_pointer(document(), "/a~1b/2").unwrap_err() {
+            DocpackError::PointerNotFound { resolved, .. } => assert_eq!(resolved, "/a~1b"),
+            other => panic!("expected PointerNotFound, got {other}"),
+        }
+    }
+}
@@ -1,6 +1,10 @@
+use std::fmt::{Display, Formatter};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use indexmap::IndexMap;
+use serde::Deserialize;
+
 use crate::core::{Origin, SourceFormat};
 use crate::error::{DocpackError, DocpackResult};
 use crate::input::detect_format;
@@ -14,6 +18,55 @@ pub struct SourceSpec {
     pub bytes: Vec<u8>,
     pub no_header: bool,
     pub sheet: Option<String>,
+    pub options: ParseOptions,
+}
+
+/// Adapter options that change how raw source values are normalized.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Keep every decimal literal as an exact `Value::Decimal` instead of `f64`.
+    pub exact_decimals: bool,
+    /// Keep the source spelling of numbers next to their parsed value.
+    pub keep_numeric_text: bool,
+    /// Declared cell types for text columns, keyed by header name (or 1-based
+    /// column number when the source has no header).
+    pub column_types: IndexMap<String, ColumnType>,
+    /// JSON Pointer to re-root the parsed document at.
+    pub select: Option<String>,
+    /// Promote a list of flat objects to `TabularRecords` after selection.
+    pub tabular: bool,
+}
+
+/// Explicit cell type for a text-based tabular column.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnType {
+    Date,
+    Datetime,
+    Time,
+}
+
+impl ColumnType {
+    /// Parses a column type identifier as written in manifests and CLI flags.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "date" => Some(Self::Date),
+            "datetime" => Some(Self::Datetime),
+            "time" => Some(Self::Time),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ColumnType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Date => "date",
+            Self::Datetime => "datetime",
+            Self::Time => "time",
+        };
+        write!(f, "{value}")
+    }
 }
 
 impl SourceSpec {
@@ -40,6 +93,7 @@ impl SourceSpec {
             bytes,
             no_header,
             sheet,
+            options: ParseOptions::default(),
         })
     }
 
@@ -58,9 +112,16 @@ impl SourceSpec {
             bytes,
             no_header,
             sheet,
+            options: ParseOptions::default(),
         }
     }
 
+    /// Replaces the normalization options applied by the input adapters.
+    pub fn with_options(mut self, options: ParseOptions) -> Self {
+        self.options = options;
+        self
+    }
+
     /// Derives a fallback source identifier from an input path.
     pub fn input_stem(path: &Path) -> String {
         path.file_stem()
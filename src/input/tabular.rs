@@ -0,0 +1,183 @@
+use indexmap::IndexMap;
+
+use crate::core::{Document, TopLevelShape, Value, infer_columns};
+use crate::error::{DocpackError, DocpackResult};
+
+/// Rows reported by name before the rest are summarized as a count.
+const LISTED_PROBLEMS: usize = 10;
+
+/// Promotes a list of flat objects to a `TabularRecords` document.
+///
+/// Columns are the union of the row keys in first-seen order; rows missing a
+/// key get a null cell. Documents that are already record tables pass through.
+pub fn promote_records(doc: Document) -> DocpackResult<Document> {
+    if doc.meta.top_level_shape == TopLevelShape::TabularRecords {
+        return Ok(doc);
+    }
+    let fail = |problems: Vec<String>| DocpackError::NotTabular {
+        origin: doc.meta.origin.clone(),
+        problems,
+    };
+    let Value::List(rows) = &doc.root else {
+        return Err(fail(vec![format!(
+            "the root is {}, not a list of objects",
+            describe(&doc.root)
+        )]));
+    };
+    if rows.is_empty() {
+        return Err(fail(vec![
+            "the list is empty, so no columns can be derived".to_string(),
+        ]));
+    }
+
+    let mut problems = Vec::new();
+    let mut names = IndexMap::new();
+    for (index, row) in rows.iter().enumerate() {
+        let Value::Object(values) = row else {
+            problems.push(format!("/{index} is {}, not an object", describe(row)));
+            continue;
+        };
+        for (key, value) in values {
+            if matches!(value, Value::List(_) | Value::Object(_)) {
+                problems.push(format!(
+                    "/{index}/{key} is {}; table cells must be scalars",
+                    describe(value)
+                ));
+            }
+            names.entry(key.clone()).or_insert(());
+        }
+    }
+    if !problems.is_empty() {
+        let hidden = problems.len().saturating_sub(LISTED_PROBLEMS);
+        problems.truncate(LISTED_PROBLEMS);
+        if hidden > 0 {
+            problems.push(format!("and {hidden} more"));
+        }
+        return Err(fail(problems));
+    }
+
+    let names = names.into_keys().collect::<Vec<_>>();
+    let root = Value::List(
+        rows.iter()
+            .map(|row| match row {
+                Value::Object(values) => Value::Object(
+                    names
+                        .iter()
+                        .map(|name| {
+                            let cell = values.get(name).cloned().unwrap_or(Value::Null);
+                            (name.clone(), cell)
+                        })
+                        .collect(),
+                ),
+                _ => unreachable!("rows were checked above"),
+            })
+            .collect(),
+    );
+    let mut meta = doc.meta;
+    meta.top_level_shape = TopLevelShape::TabularRecords;
+    meta.columns = infer_columns(&root, Some(&names));
+    meta.tabular_columns = Some(names);
+    Ok(Document {
+        source_id: doc.source_id,
+        root,
+        meta,
+    })
+}
+
+fn describe(value: &Value) -> &'static str {
+    match value {
+        Value::List(_) => "a list",
+        Value::Object(_) => "an object",
+        Value::Null => "null",
+        _ => "a scalar",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::promote_records;
+    use crate::core::{Document, Origin, SourceFormat, SourceMeta, TopLevelShape, Value};
+    use crate::error::DocpackError;
+
+    fn list(rows: Vec<Value>) -> Document {
+        Document {
+            source_id: "api".to_string(),
+            root: Value::List(rows),
+            meta: SourceMeta {
+                format: SourceFormat::Json,
+                origin: Origin::Stdin,
+                top_level_shape: TopLevelShape::List,
+                tabular_columns: None,
+                header_present: None,
+                columns: Vec::new(),
+            },
+        }
+    }
+
+    fn object<const N: usize>(entries: [(&str, Value); N]) -> Value {
+        Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect::<IndexMap<_, _>>(),
+        )
+    }
+
+    #[test]
+    fn derives_columns_from_the_key_union_and_fills_gaps() {
+        let doc = promote_records(list(vec![
+            object([
+                ("id", Value::Integer(1)),
+                ("name", Value::String("a".into())),
+            ]),
+            object([("id", Value::Integer(2)), ("tag", Value::Bool(true))]),
+        ]))
+        .unwrap();
+        assert_eq!(doc.meta.top_level_shape, TopLevelShape::TabularRecords);
+        assert_eq!(
+            doc.meta.tabular_columns,
+            Some(vec![
+                "id".to_string(),
+                "name".to_string(),
+                "tag".to_string()
+            ])
+        );
+        assert_eq!(
+            doc.meta.column("name").map(|column| column.nullable),
+            Some(true)
+        );
+        let Value::List(rows) = &doc.root else {
+            panic!("expected a list root");
+        };
+        assert_eq!(
+            rows[1],
+            object([
+                ("id", Value::Integer(2)),
+                ("name", Value::Null),
+                ("tag", Value::Bool(true)),
+            ])
+        );
+    }
+
+    #[test]
+    fn lists_every_row_that_is_not_a_flat_object() {
+        let error = promote_records(list(vec![
+            object([("id", Value::Integer(1))]),
+            Value::String("stray".into()),
+            object([("id", Value::Integer(3)), ("tags", Value::List(Vec::new()))]),
+        ]))
+        .unwrap_err();
+        let DocpackError::NotTabular { problems, .. } = error else {
+            panic!("expected NotTabular, got {error:?}");
+        };
+        assert_eq!(
+            problems,
+            vec![
+                "/1 is a scalar, not an object".to_string(),
+                "/2/tags is a list; table cells must be scalars".to_string(),
+            ]
+        );
+    }
+}
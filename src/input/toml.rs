@@ -1,9 +1,10 @@
-use std::collections::BTreeMap;
+use indexmap::IndexMap;
 
 use toml::Value as TomlValue;
+use toml::value::{Datetime as TomlDatetime, Offset as TomlOffset};
 
-use crate::core::{Document, SourceMeta, Value};
-use crate::error::{DocpackError, DocpackResult};
+use crate::core::{Date, DateTime, Document, SourceMeta, Time, Value};
+use crate::error::{DocpackError, DocpackResult, SourceLocation};
 use crate::input::{SourceSpec, child_path, infer_shape};
 
 pub fn parse(spec: &SourceSpec) -> DocpackResult<Document> {
@@ -12,12 +13,16 @@ pub fn parse(spec: &SourceSpec) -> DocpackResult<Document> {
         origin: spec.origin.clone(),
         detail: error.to_string(),
         path: None,
+        location: None,
     })?;
     let value: TomlValue = toml::from_str(text).map_err(|error| DocpackError::Parse {
         format: spec.format,
         origin: spec.origin.clone(),
-        detail: error.to_string(),
+        detail: error.message().to_string(),
         path: None,
+        location: error
+            .span()
+            .map(|span| Box::new(SourceLocation::from_span(text, span))),
     })?;
     let root = convert_toml(value, "")?;
     Ok(Document {
@@ -28,6 +33,7 @@ pub fn parse(spec: &SourceSpec) -> DocpackResult<Document> {
             top_level_shape: infer_shape(&root),
             tabular_columns: None,
             header_present: None,
+            columns: Vec::new(),
         },
         root,
     })
@@ -39,7 +45,7 @@ fn convert_toml(value: TomlValue, path: &str) -> DocpackResult<Value> {
         TomlValue::Integer(value) => Ok(Value::Integer(value)),
         TomlValue::Float(value) => Ok(Value::Float(value)),
         TomlValue::Boolean(value) => Ok(Value::Bool(value)),
-        TomlValue::Datetime(value) => Ok(Value::String(value.to_string())),
+        TomlValue::Datetime(value) => Ok(convert_datetime(value)),
         TomlValue::Array(values) => {
             let mut result = Vec::with_capacity(values.len());
             for (index, value) in values.into_iter().enumerate() {
@@ -54,33 +60,93 @@ fn convert_toml(value: TomlValue, path: &str) -> DocpackResult<Value> {
                     let path = child_path(path, &key);
                     Ok((key, convert_toml(value, &path)?))
                 })
-                .collect::<DocpackResult<BTreeMap<_, _>>>()?,
+                .collect::<DocpackResult<IndexMap<_, _>>>()?,
         )),
     }
 }
 
+fn convert_datetime(value: TomlDatetime) -> Value {
+    let date = value.date.map(|date| Date {
+        year: i32::from(date.year),
+        month: date.month,
+        day: date.day,
+    });
+    let time = value.time.map(|time| Time {
+        hour: time.hour,
+        minute: time.minute,
+        second: time.second,
+        nanosecond: time.nanosecond,
+    });
+    let offset_minutes = value.offset.map(|offset| match offset {
+        TomlOffset::Z => 0,
+        TomlOffset::Custom { minutes } => minutes,
+    });
+    match (date, time) {
+        (Some(date), Some(time)) => Value::DateTime(DateTime {
+            date,
+            time,
+            offset_minutes,
+        }),
+        (Some(date), None) => Value::Date(date),
+        (None, Some(time)) => Value::Time(time),
+        (None, None) => Value::String(value.to_string()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::parse;
-    use crate::core::{SourceFormat, Value};
+    use crate::core::{Date, SourceFormat, Time, Value};
+    use crate::error::DocpackError;
     use crate::input::SourceSpec;
 
     #[test]
-    fn normalizes_toml_datetime_as_string() {
+    fn normalizes_toml_datetimes_as_typed_values() {
         let spec = SourceSpec::from_stdin(
             "data",
             SourceFormat::Toml,
-            b"created_at = 1979-05-27T07:32:00Z\n".to_vec(),
+            b"created_at = 1979-05-27T07:32:00Z\nday = 1979-05-27\nalarm = 07:32:00\n".to_vec(),
             false,
             None,
         );
         let doc = parse(&spec).unwrap();
         match doc.root {
-            Value::Object(map) => assert_eq!(
-                map.get("created_at"),
-                Some(&Value::String("1979-05-27T07:32:00Z".to_string()))
-            ),
+            Value::Object(map) => {
+                assert_eq!(
+                    map.get("created_at").and_then(Value::scalar_text),
+                    Some("1979-05-27T07:32:00Z".to_string())
+                );
+                assert_eq!(
+                    map.get("day"),
+                    Some(&Value::Date(Date::new(1979, 5, 27).unwrap()))
+                );
+                assert_eq!(
+                    map.get("alarm"),
+                    Some(&Value::Time(Time::new(7, 32, 0, 0).unwrap()))
+                );
+            }
             _ => panic!("expected object root"),
         }
     }
+
+    #[test]
+    fn reports_toml_syntax_error_location() {
+        let spec = SourceSpec::from_stdin(
+            "data",
+            SourceFormat::Toml,
+            b"title = \"Report\"\nyear = 20x5\n".to_vec(),
+            false,
+            None,
+        );
+        match parse(&spec).unwrap_err() {
+            DocpackError::Parse {
+                location: Some(location),
+                ..
+            } => {
+                assert_eq!(location.line, 2);
+                assert_eq!(location.line_text, "year = 20x5");
+            }
+            other => panic!("expected located parse error, got {other}"),
+        }
+    }
 }
@@ -1,9 +1,13 @@
-use std::collections::BTreeMap;
 use std::io::Cursor;
 
-use calamine::{Data, Reader, open_workbook_auto_from_rs};
+use indexmap::IndexMap;
 
-use crate::core::{Document, Origin, SourceMeta, TopLevelShape, Value};
+use calamine::{Data, ExcelDateTime, Reader, open_workbook_auto_from_rs};
+use chrono::{Datelike, Timelike};
+
+use crate::core::{
+    Date, DateTime, Document, Origin, SourceMeta, Time, TopLevelShape, Value, infer_columns,
+};
 use crate::error::{DocpackError, DocpackResult};
 use crate::input::SourceSpec;
 
@@ -15,6 +19,7 @@ pub fn parse(spec: &SourceSpec) -> DocpackResult<Document> {
                 origin: spec.origin.clone(),
                 detail: error.to_string(),
                 path: None,
+                location: None,
             }
         })?;
     let sheet_name = spec.sheet.clone().unwrap_or_else(|| "Sheet1".to_string());
@@ -35,6 +40,7 @@ pub fn parse(spec: &SourceSpec) -> DocpackResult<Document> {
                     available.join(", ")
                 ),
                 path: None,
+                location: None,
             }),
         };
     }
@@ -45,6 +51,7 @@ pub fn parse(spec: &SourceSpec) -> DocpackResult<Document> {
             origin: spec.origin.clone(),
             detail: error.to_string(),
             path: None,
+            location: None,
         })?;
 
     if spec.no_header {
@@ -72,22 +79,25 @@ fn parse_records<'a>(
                 row_index: index + 2,
             });
         }
-        let mut record = BTreeMap::new();
+        let mut record = IndexMap::new();
         for (header, cell) in headers.iter().zip(row.iter()) {
             record.insert(header.clone(), data_to_value(cell));
         }
         values.push(Value::Object(record));
     }
 
+    let root = Value::List(values);
+    let columns = infer_columns(&root, Some(&headers));
     Ok(Document {
         source_id: spec.source_id.clone(),
-        root: Value::List(values),
+        root,
         meta: SourceMeta {
             format: spec.format,
             origin: spec.origin.clone(),
             top_level_shape: TopLevelShape::TabularRecords,
             tabular_columns: Some(headers),
             header_present: Some(true),
+            columns,
         },
     })
 }
@@ -116,15 +126,18 @@ fn parse_matrix<'a>(
         values.push(Value::List(row.iter().map(data_to_value).collect()));
     }
 
+    let root = Value::List(values);
+    let columns = infer_columns(&root, None);
     Ok(Document {
         source_id: spec.source_id.clone(),
-        root: Value::List(values),
+        root,
         meta: SourceMeta {
             format: spec.format,
             origin: spec.origin.clone(),
             top_level_shape: TopLevelShape::TabularMatrix,
             tabular_columns: None,
             header_present: Some(false),
+            columns,
         },
     })
 }
@@ -136,22 +149,82 @@ fn data_to_value(cell: &Data) -> Value {
         Data::Int(value) => Value::Integer(*value),
         Data::Float(value) => Value::Float(*value),
         Data::String(value) => Value::String(value.clone()),
+        Data::DateTime(value) if value.is_datetime() => excel_datetime_to_value(value),
         Data::DateTime(value) => Value::String(value.to_string()),
-        Data::DateTimeIso(value) => Value::String(value.to_string()),
+        Data::DateTimeIso(value) => iso_text_to_value(value),
         Data::DurationIso(value) => Value::String(value.to_string()),
         Data::Error(value) => Value::String(value.to_string()),
     }
 }
 
 fn data_to_text(cell: &Data) -> String {
-    match data_to_value(cell) {
-        Value::Null => String::new(),
-        Value::Bool(value) => value.to_string(),
-        Value::Integer(value) => value.to_string(),
-        Value::Float(value) => value.to_string(),
-        Value::String(value) => value,
-        Value::List(_) | Value::Object(_) => {
-            unreachable!("XLSX cells never normalize to nested values")
-        }
+    data_to_value(cell)
+        .scalar_text()
+        .expect("XLSX cells never normalize to nested values")
+}
+
+fn excel_datetime_to_value(value: &ExcelDateTime) -> Value {
+    let Some(datetime) = value.as_datetime() else {
+        return Value::String(value.to_string());
+    };
+    let date = Date::new(
+        datetime.year(),
+        datetime.month() as u8,
+        datetime.day() as u8,
+    );
+    let time = Time::new(
+        datetime.hour() as u8,
+        datetime.minute() as u8,
+        datetime.second() as u8,
+        datetime.nanosecond(),
+    );
+    match (date, time) {
+        (Some(_), Some(time)) if value.as_f64() < 1.0 => Value::Time(time),
+        (Some(date), Some(_)) if value.as_f64().fract() == 0.0 => Value::Date(date),
+        (Some(date), Some(time)) => Value::DateTime(DateTime {
+            date,
+            time,
+            offset_minutes: None,
+        }),
+        _ => Value::String(value.to_string()),
+    }
+}
+
+fn iso_text_to_value(text: &str) -> Value {
+    if let Some(datetime) = DateTime::parse_iso(text) {
+        Value::DateTime(datetime)
+    } else if let Some(date) = Date::parse_iso(text) {
+        Value::Date(date)
+    } else if let Some(time) = Time::parse_iso(text) {
+        Value::Time(time)
+    } else {
+        Value::String(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use calamine::{ExcelDateTime, ExcelDateTimeType};
+
+    use super::excel_datetime_to_value;
+    use crate::core::{Date, Time, Value};
+
+    #[test]
+    fn converts_excel_serials_into_typed_values() {
+        let date = ExcelDateTime::new(45_658.0, ExcelDateTimeType::DateTime, false);
+        assert_eq!(
+            excel_datetime_to_value(&date),
+            Value::Date(Date::new(2025, 1, 1).unwrap())
+        );
+        let datetime = ExcelDateTime::new(45_658.5, ExcelDateTimeType::DateTime, false);
+        assert_eq!(
+            excel_datetime_to_value(&datetime).scalar_text().unwrap(),
+            "2025-01-01T12:00:00"
+        );
+        let time = ExcelDateTime::new(0.25, ExcelDateTimeType::DateTime, false);
+        assert_eq!(
+            excel_datetime_to_value(&time),
+            Value::Time(Time::new(6, 0, 0, 0).unwrap())
+        );
     }
 }
@@ -1,18 +1,30 @@
-use std::collections::BTreeMap;
+use indexmap::IndexMap;
 
 use serde_yaml::Value as YamlValue;
 
 use crate::core::{Document, SourceMeta, Value};
-use crate::error::{DocpackError, DocpackResult};
-use crate::input::{SourceSpec, child_path, infer_shape};
+use crate::error::{DocpackError, DocpackResult, SourceLocation};
+use crate::input::{SourceSpec, child_path, infer_shape, strip_location};
 
 pub fn parse(spec: &SourceSpec) -> DocpackResult<Document> {
     let value: YamlValue =
         serde_yaml::from_slice(&spec.bytes).map_err(|error| DocpackError::Parse {
             format: spec.format,
             origin: spec.origin.clone(),
-            detail: error.to_string(),
+            detail: match error.location() {
+                Some(location) => {
+                    strip_location(&error.to_string(), location.line(), location.column())
+                }
+                None => error.to_string(),
+            },
             path: None,
+            location: error.location().map(|location| {
+                Box::new(SourceLocation::from_line_column(
+                    &String::from_utf8_lossy(&spec.bytes),
+                    location.line(),
+                    location.column(),
+                ))
+            }),
         })?;
     let root = convert_yaml(value, spec, "")?;
     Ok(Document {
@@ -23,6 +35,7 @@ pub fn parse(spec: &SourceSpec) -> DocpackResult<Document> {
             top_level_shape: infer_shape(&root),
             tabular_columns: None,
             header_present: None,
+            columns: Vec::new(),
         },
         root,
     })
@@ -38,7 +51,7 @@ fn convert_yaml(value: YamlValue, spec: &SourceSpec, path: &str) -> DocpackResul
             } else if let Some(unsigned) = value.as_u64() {
                 match i64::try_from(unsigned) {
                     Ok(integer) => Ok(Value::Integer(integer)),
-                    Err(_) => Ok(Value::Float(unsigned as f64)),
+                    Err(_) => Ok(Value::Decimal(unsigned.to_string())),
                 }
             } else {
                 Ok(Value::Float(value.as_f64().unwrap_or_default()))
@@ -57,7 +70,7 @@ fn convert_yaml(value: YamlValue, spec: &SourceSpec, path: &str) -> DocpackResul
             Ok(Value::List(result))
         }
         YamlValue::Mapping(values) => {
-            let mut result = BTreeMap::new();
+            let mut result = IndexMap::new();
             for (key, value) in values {
                 let Some(key) = key.as_str() else {
                     return Err(DocpackError::UnsupportedKey {
@@ -98,6 +111,7 @@ fn render_yaml_key(key: &YamlValue) -> String {
 mod tests {
     use super::parse;
     use crate::core::SourceFormat;
+    use crate::error::DocpackError;
     use crate::input::SourceSpec;
 
     #[test]
@@ -111,4 +125,25 @@ mod tests {
         );
         assert!(parse(&spec).is_err());
     }
+
+    #[test]
+    fn reports_yaml_syntax_error_location() {
+        let spec = SourceSpec::from_stdin(
+            "data",
+            SourceFormat::Yaml,
+            b"name: Alice\ntags: [a, b\n".to_vec(),
+            false,
+            None,
+        );
+        match parse(&spec).unwrap_err() {
+            DocpackError::Parse {
+                location: Some(location),
+                ..
+            } => {
+                assert_eq!(location.line, 3);
+                assert_eq!(location.line_text, "");
+            }
+            other => panic!("expected located parse error, got {other}"),
+        }
+    }
 }
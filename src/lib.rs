@@ -1,18 +1,32 @@
 //! Public library surface for `docpack`.
 //!
 //! The library is organized around a normalized [`Value`] tree plus source
-//! metadata, with separate input, backend, and manifest layers built on top.
+//! metadata, with separate input, transform, backend, and manifest layers
+//! built on top.
 
 pub mod backend;
 pub mod core;
 pub mod error;
 pub mod input;
 pub mod manifest;
+pub mod transform;
 
 pub use backend::{
-    ArtifactKind, Backend, BackendKind, RenderRequest, RenderedArtifact, render_document,
-    validate_request,
+    ArtifactKind, Backend, BackendKind, ColumnFormat, KeyOrder, Locale, NonFinitePolicy,
+    RenderOptions, RenderRequest, RenderedArtifact, key_value_document, render_document,
+    supports_table_fragment, validate_request,
+};
+pub use core::{
+    ColumnKind, ColumnMeta, Date, DateTime, Document, Origin, SourceFormat, SourceMeta, Time,
+    TopLevelShape, Value, infer_columns,
+};
+pub use error::{DocpackError, DocpackResult, SourceLocation};
+pub use input::{
+    ColumnType, ParseOptions, Schema, SourceSpec, detect_format, parse_source, promote_records,
+    select_pointer,
+};
+pub use transform::{
+    FilterOperator, JoinKind, JoinOptions, JoinOutcome, ListFlatten, ListMerge, Literal,
+    RedactAction, RedactionRule, SortKey, SortOrder, Transform, apply_transforms, join_documents,
+    merge_documents, preview_table, redact_document,
 };
-pub use core::{Document, Origin, SourceFormat, SourceMeta, TopLevelShape, Value};
-pub use error::{DocpackError, DocpackResult};
-pub use input::{SourceSpec, detect_format, parse_source};
@@ -5,10 +5,12 @@ use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
-use cliargs::{BuildArgs, CliArgs, Commands, EmitArgs, InitArgs, InspectArgs, InspectTarget};
+use cliargs::{
+    BuildArgs, CliArgs, Commands, EmitArgs, InitArgs, InspectArgs, InspectTarget, ValidateArgs,
+};
 use docpack::{
-    DocpackError, Origin, SourceFormat, SourceSpec, detect_format, manifest, parse_source,
-    render_document,
+    DocpackError, Origin, ParseOptions, Schema, SourceFormat, SourceSpec, apply_transforms,
+    detect_format, manifest, parse_source, preview_table, render_document,
 };
 
 fn main() -> ExitCode {
@@ -16,6 +18,9 @@ fn main() -> ExitCode {
         Ok(()) => ExitCode::SUCCESS,
         Err(error) => {
             eprintln!("error: {error}");
+            if let Some(excerpt) = error.source_excerpt() {
+                eprint!("{excerpt}");
+            }
             ExitCode::FAILURE
         }
     }
@@ -27,19 +32,32 @@ fn run() -> Result<(), DocpackError> {
         Commands::Build(args) => run_build(args),
         Commands::Emit(args) => run_emit(args),
         Commands::Inspect(args) => run_inspect(args),
+        Commands::Validate(args) => run_validate(args),
         Commands::Init(args) => run_init(args),
     }
 }
 
 fn run_build(args: BuildArgs) -> Result<(), DocpackError> {
-    manifest::build(args.manifest_path.as_deref())?;
+    let report = manifest::build(args.manifest_path.as_deref())?;
+    for warning in &report.warnings {
+        eprintln!("warning: {warning}");
+    }
     Ok(())
 }
 
 fn run_emit(args: EmitArgs) -> Result<(), DocpackError> {
     let input_path = input_path(&args.input);
     let spec = load_source_spec(&args.input, args.format, args.no_header, args.sheet.clone())?;
-    let doc = parse_source(&spec)?;
+    reject_number_text_flag(&spec, "--exact-decimals", args.exact_decimals)?;
+    reject_number_text_flag(&spec, "--keep-numeric-text", args.keep_numeric_text)?;
+    let spec = spec.with_options(ParseOptions {
+        exact_decimals: args.exact_decimals,
+        keep_numeric_text: args.keep_numeric_text,
+        column_types: args.column_types.iter().cloned().collect(),
+        select: args.select.clone(),
+        tabular: args.tabular,
+    });
+    let doc = apply_transforms(parse_source(&spec)?, &args.rows.steps())?;
     let request = manifest::resolve_source_request(
         &doc,
         manifest::SourceRequestOptions {
@@ -49,6 +67,8 @@ fn run_emit(args: EmitArgs) -> Result<(), DocpackError> {
             artifact: args.artifact,
             style: args.style.as_deref(),
             root_name: args.root_name.as_deref(),
+            key_order: args.key_order,
+            non_finite: args.non_finite,
             require_explicit_backend_without_output: true,
         },
     )?;
@@ -82,7 +102,17 @@ fn run_inspect(args: InspectArgs) -> Result<(), DocpackError> {
             let input_path = input_path(&args.input);
             let spec =
                 load_source_spec(&args.input, args.format, args.no_header, args.sheet.clone())?;
-            let doc = parse_source(&spec)?;
+            reject_number_text_flag(&spec, "--exact-decimals", args.exact_decimals)?;
+            reject_number_text_flag(&spec, "--keep-numeric-text", args.keep_numeric_text)?;
+            let spec = spec.with_options(ParseOptions {
+                exact_decimals: args.exact_decimals,
+                keep_numeric_text: args.keep_numeric_text,
+                column_types: args.column_types.iter().cloned().collect(),
+                select: args.select.clone(),
+                tabular: args.tabular,
+            });
+            let steps = args.rows.steps();
+            let doc = apply_transforms(parse_source(&spec)?, &steps)?;
             let request = manifest::resolve_source_request(
                 &doc,
                 manifest::SourceRequestOptions {
@@ -92,20 +122,81 @@ fn run_inspect(args: InspectArgs) -> Result<(), DocpackError> {
                     artifact: args.artifact,
                     style: args.style.as_deref(),
                     root_name: args.root_name.as_deref(),
+                    key_order: args.key_order,
+                    non_finite: args.non_finite,
                     require_explicit_backend_without_output: false,
                 },
             )?;
-            print!("{}", format_source_inspect(&doc, &request));
+            // Without sampling flags, preview a few rows; with them, show
+            // exactly the rows they selected.
+            let preview_rows = if steps.is_empty() {
+                PREVIEW_ROWS
+            } else {
+                usize::MAX
+            };
+            print!("{}", format_source_inspect(&doc, &request, preview_rows));
         }
     }
     Ok(())
 }
 
+fn run_validate(args: ValidateArgs) -> Result<(), DocpackError> {
+    let Some(schema_path) = args.schema else {
+        if infer_inspect_target(&args.input) == InspectTarget::Source {
+            return Err(DocpackError::Inference {
+                detail: format!(
+                    "validating {} requires --schema unless it is a manifest",
+                    args.input
+                ),
+            });
+        }
+        let passed = manifest::validate_sources(Path::new(&args.input))?;
+        if passed.is_empty() {
+            println!("no sources in {} declare a schema", args.input);
+        }
+        for line in passed {
+            println!("{line}");
+        }
+        return Ok(());
+    };
+    let spec = load_source_spec(&args.input, args.format, args.no_header, args.sheet.clone())?;
+    reject_number_text_flag(&spec, "--exact-decimals", args.exact_decimals)?;
+    let spec = spec.with_options(ParseOptions {
+        exact_decimals: args.exact_decimals,
+        keep_numeric_text: false,
+        column_types: args.column_types.iter().cloned().collect(),
+        select: args.select.clone(),
+        tabular: args.tabular,
+    });
+    let doc = parse_source(&spec)?;
+    let schema = Schema::load(&schema_path)?;
+    schema.validate(&doc)?;
+    println!(
+        "{} matches schema {}",
+        doc.meta.origin,
+        schema.path().display()
+    );
+    Ok(())
+}
+
 fn run_init(args: InitArgs) -> Result<(), DocpackError> {
     manifest::init_template(args.path.as_deref(), args.force)?;
     Ok(())
 }
 
+/// Rejects a set flag that needs the source's number text, which YAML, TOML,
+/// and XLSX sources lose by handing the adapters `f64`s. Mirrors the manifest
+/// check on the matching source option.
+fn reject_number_text_flag(spec: &SourceSpec, flag: &str, set: bool) -> Result<(), DocpackError> {
+    match spec.format {
+        SourceFormat::Csv | SourceFormat::Json => Ok(()),
+        _ if !set => Ok(()),
+        other => Err(DocpackError::Inference {
+            detail: format!("{flag} is only valid for csv or json sources (got {other})"),
+        }),
+    }
+}
+
 fn load_source_spec(
     input: &str,
     format: Option<SourceFormat>,
@@ -159,7 +250,14 @@ fn input_path(input: &str) -> Option<&Path> {
     }
 }
 
-fn format_source_inspect(doc: &docpack::Document, request: &docpack::RenderRequest) -> String {
+/// Rows `inspect` previews for a tabular source when no sampling flag is set.
+const PREVIEW_ROWS: usize = 5;
+
+fn format_source_inspect(
+    doc: &docpack::Document,
+    request: &docpack::RenderRequest,
+    preview_rows: usize,
+) -> String {
     let mut output = String::new();
     writeln!(output, "Source").unwrap();
     writeln!(output, "  id: {}", doc.source_id).unwrap();
@@ -189,12 +287,29 @@ fn format_source_inspect(doc: &docpack::Document, request: &docpack::RenderReque
             .unwrap_or_else(|| "n/a".to_string())
     )
     .unwrap();
+    if doc.meta.columns.is_empty() {
+        writeln!(output, "  columns: n/a").unwrap();
+    } else {
+        writeln!(output, "  columns:").unwrap();
+        for column in &doc.meta.columns {
+            writeln!(output, "    - {}", manifest::describe_column(column)).unwrap();
+        }
+    }
     writeln!(output).unwrap();
+    if let Some(preview) = preview_table(doc, preview_rows) {
+        writeln!(output, "Preview").unwrap();
+        for line in preview.lines() {
+            writeln!(output, "  {line}").unwrap();
+        }
+        writeln!(output).unwrap();
+    }
     writeln!(output, "Resolved Render Defaults").unwrap();
     writeln!(output, "  backend: {}", request.backend).unwrap();
     writeln!(output, "  artifact: {}", request.artifact).unwrap();
     writeln!(output, "  style: {}", request.style).unwrap();
     writeln!(output, "  root_name: {}", request.root_name).unwrap();
+    writeln!(output, "  key_order: {}", request.options.key_order).unwrap();
+    writeln!(output, "  non_finite: {}", request.options.non_finite).unwrap();
     output
 }
 
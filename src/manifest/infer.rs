@@ -1,8 +1,9 @@
 use std::path::{Path, PathBuf};
 
 use crate::backend::{
-    ArtifactKind, BackendKind, RenderRequest, default_style, style_implied_artifact,
-    style_implied_backend, style_supported,
+    ArtifactKind, BackendKind, KeyOrder, NonFinitePolicy, RenderOptions, RenderRequest,
+    default_style, style_implied_artifact, style_implied_backend, style_supported,
+    supports_table_fragment,
 };
 use crate::core::Document;
 use crate::error::{DocpackError, DocpackResult};
@@ -26,6 +27,8 @@ pub struct SourceRequestOptions<'a> {
     pub artifact: Option<ArtifactKind>,
     pub style: Option<&'a str>,
     pub root_name: Option<&'a str>,
+    pub key_order: Option<KeyOrder>,
+    pub non_finite: Option<NonFinitePolicy>,
     pub require_explicit_backend_without_output: bool,
 }
 
@@ -37,7 +40,7 @@ pub fn resolve_manifest_output(
     doc: &Document,
 ) -> DocpackResult<ResolvedOutput> {
     let output_path = resolve_output_path(loaded, &output.path);
-    let request = resolve_request(
+    let mut request = resolve_request(
         doc,
         ResolveRequestContext {
             output_path: Some(&output_path),
@@ -45,11 +48,16 @@ pub fn resolve_manifest_output(
             artifact: output.artifact,
             style: output.style.as_deref(),
             root_name: output.root_name.as_deref(),
+            key_order: output.key_order,
+            non_finite: output.non_finite,
             source_id: Some(&source.id),
             input_path: None,
             require_explicit_backend_without_output: false,
         },
     )?;
+    request.options.locale = output.locale.clone().unwrap_or_default();
+    request.options.column_formats = output.column_formats.clone().unwrap_or_default();
+    request.options.css_class = output.css_class.clone();
     Ok(ResolvedOutput {
         output_id: output.id.clone(),
         source_id: source.id.clone(),
@@ -71,6 +79,8 @@ pub fn resolve_source_request(
             artifact: options.artifact,
             style: options.style,
             root_name: options.root_name,
+            key_order: options.key_order,
+            non_finite: options.non_finite,
             source_id: None,
             input_path: options.input_path,
             require_explicit_backend_without_output: options
@@ -117,6 +127,8 @@ struct ResolveRequestContext<'a> {
     artifact: Option<ArtifactKind>,
     style: Option<&'a str>,
     root_name: Option<&'a str>,
+    key_order: Option<KeyOrder>,
+    non_finite: Option<NonFinitePolicy>,
     source_id: Option<&'a str>,
     input_path: Option<&'a Path>,
     require_explicit_backend_without_output: bool,
@@ -137,10 +149,10 @@ fn resolve_request(
 
     let backend = infer_backend(context.output_path, context.backend, context.style)?;
     let artifact = infer_artifact(context.artifact, context.style);
-    if artifact == ArtifactKind::TableFragment && !doc.is_tabular() {
+    if artifact == ArtifactKind::TableFragment && !supports_table_fragment(doc) {
         return Err(DocpackError::Inference {
             detail: format!(
-                "artifact table-fragment requires tabular source metadata, but source shape is {}; use data-module instead",
+                "artifact table-fragment requires tabular source metadata or an object, but source shape is {}; use data-module instead",
                 doc.meta.top_level_shape
             ),
         });
@@ -163,6 +175,11 @@ fn resolve_request(
         artifact,
         style,
         root_name,
+        options: RenderOptions {
+            key_order: context.key_order.unwrap_or_default(),
+            non_finite: context.non_finite.unwrap_or_default(),
+            ..RenderOptions::default()
+        },
     })
 }
 
@@ -194,18 +211,23 @@ fn infer_backend(
         match path.extension().and_then(|ext| ext.to_str()) {
             Some("typ") => return Ok(BackendKind::Typst),
             Some("tex") => return Ok(BackendKind::Latex),
+            Some("md" | "markdown") => return Ok(BackendKind::Markdown),
+            Some("html" | "htm") => return Ok(BackendKind::Html),
+            Some("adoc" | "asciidoc") => return Ok(BackendKind::Asciidoc),
+            Some("rst") => return Ok(BackendKind::Rst),
+            Some("mkiv" | "mkxl") => return Ok(BackendKind::Context),
             _ => {
                 if let Some(backend) = style.and_then(style_implied_backend) {
                     return Ok(backend);
                 }
                 let detail = match path.extension().and_then(|ext| ext.to_str()) {
                     Some(extension) => format!(
-                        "backend could not be inferred from output path '{}': extension '.{}' is not mapped to a backend; use --backend or write to .typ/.tex",
+                        "backend could not be inferred from output path '{}': extension '.{}' is not mapped to a backend; use --backend or write to .typ/.tex/.md/.html/.adoc/.rst/.mkiv",
                         path.display(),
                         extension
                     ),
                     None => format!(
-                        "backend could not be inferred from output path '{}': it has no extension; use --backend or write to .typ/.tex",
+                        "backend could not be inferred from output path '{}': it has no extension; use --backend or write to .typ/.tex/.md/.html/.adoc/.rst/.mkiv",
                         path.display()
                     ),
                 };
@@ -217,8 +239,9 @@ fn infer_backend(
         return Ok(style);
     }
     Err(DocpackError::Inference {
-        detail: "backend could not be inferred; provide --backend or an output path with .typ/.tex"
-            .to_string(),
+        detail:
+            "backend could not be inferred; provide --backend or an output path with .typ/.tex/.md/.html/.adoc/.rst/.mkiv"
+                .to_string(),
     })
 }
 
@@ -254,6 +277,18 @@ fn supported_styles(backend: BackendKind, artifact: ArtifactKind) -> &'static [&
         (BackendKind::Latex, ArtifactKind::TableFragment) => {
             &["latex-booktabs-longtable", "latex-plain-tabular"]
         }
+        (BackendKind::Markdown, ArtifactKind::DataModule) => &["markdown-front-matter"],
+        (BackendKind::Markdown, ArtifactKind::TableFragment) => &["markdown-gfm"],
+        (BackendKind::Html, ArtifactKind::DataModule) => &["html-json-script"],
+        (BackendKind::Html, ArtifactKind::TableFragment) => &["html-table"],
+        (BackendKind::Asciidoc, ArtifactKind::DataModule) => &["asciidoc-attributes"],
+        (BackendKind::Asciidoc, ArtifactKind::TableFragment) => &["asciidoc-table"],
+        (BackendKind::Rst, ArtifactKind::DataModule) => &["rst-substitutions"],
+        (BackendKind::Rst, ArtifactKind::TableFragment) => &["rst-list-table"],
+        (BackendKind::Context, ArtifactKind::DataModule) => &["context-setvariables"],
+        (BackendKind::Context, ArtifactKind::TableFragment) => {
+            &["context-xtable", "context-natural-table"]
+        }
     }
 }
 
@@ -6,7 +6,8 @@ use toml::Value as TomlValue;
 
 use crate::core::SourceFormat;
 use crate::error::{DocpackError, DocpackResult};
-use crate::manifest::{Manifest, SourceEntry};
+use crate::manifest::{JoinEntry, Manifest, MergeEntry, OutputEntry, SourceEntry};
+use crate::transform::{AggregateFunction, Expression, ListMerge, RedactionRule, Transform};
 
 /// Manifest plus resolved path context.
 #[derive(Debug, Clone)]
@@ -17,12 +18,23 @@ pub struct LoadedManifest {
 }
 
 impl LoadedManifest {
-    /// Resolves a source path relative to the manifest directory.
-    pub fn resolve_source_path(&self, source: &SourceEntry) -> PathBuf {
-        if source.path.is_absolute() {
-            source.path.clone()
+    /// Resolves a source path relative to the manifest directory; derived
+    /// sources have none.
+    pub fn resolve_source_path(&self, source: &SourceEntry) -> Option<PathBuf> {
+        source.path.as_deref().map(|path| self.resolve_path(path))
+    }
+
+    /// Resolves a source's JSON Schema path relative to the manifest
+    /// directory.
+    pub fn resolve_schema_path(&self, source: &SourceEntry) -> Option<PathBuf> {
+        source.schema.as_deref().map(|path| self.resolve_path(path))
+    }
+
+    fn resolve_path(&self, path: &Path) -> PathBuf {
+        if path.is_absolute() {
+            path.to_path_buf()
         } else {
-            self.dir.join(&source.path)
+            self.dir.join(path)
         }
     }
 
@@ -87,7 +99,13 @@ fn validate_manifest(path: &Path, manifest: &Manifest) -> DocpackResult<()> {
             problems.push(format!("duplicate source id '{}'", source.id));
         }
         validate_source_options(source, &mut problems);
+        validate_redactions(
+            &format!("source '{}'", source.id),
+            &source.redact,
+            &mut problems,
+        );
     }
+    validate_derived_sources(manifest, &source_ids, &mut problems);
 
     let mut output_ids = HashSet::new();
     for output in &manifest.outputs {
@@ -103,6 +121,37 @@ fn validate_manifest(path: &Path, manifest: &Manifest) -> DocpackResult<()> {
                 output.id, output.source
             ));
         }
+        validate_redactions(
+            &format!("output '{}'", output.id),
+            &output.redact,
+            &mut problems,
+        );
+        validate_transforms(output, &mut problems);
+        for (column, format) in output.column_formats.iter().flatten() {
+            if let Err(detail) = format.validate() {
+                problems.push(format!(
+                    "output '{}' column format for '{column}' is invalid: {detail}",
+                    output.id
+                ));
+            }
+        }
+    }
+
+    for (index, entry) in manifest.checks.iter().enumerate() {
+        if !source_ids.contains(&entry.source) {
+            problems.push(format!(
+                "check {} references missing source '{}'",
+                index + 1,
+                entry.source
+            ));
+        }
+        if let Err(detail) = entry.check.validate(entry.column.as_deref()) {
+            problems.push(format!(
+                "check {} ({}) {detail}",
+                index + 1,
+                entry.check.name()
+            ));
+        }
     }
 
     if problems.is_empty() {
@@ -115,10 +164,233 @@ fn validate_manifest(path: &Path, manifest: &Manifest) -> DocpackResult<()> {
     }
 }
 
+fn validate_redactions(scope: &str, rules: &[RedactionRule], problems: &mut Vec<String>) {
+    for (index, rule) in rules.iter().enumerate() {
+        if let Err(detail) = rule.validate() {
+            problems.push(format!("{scope} redaction {} {detail}", index + 1));
+        }
+    }
+}
+
+fn validate_transforms(output: &OutputEntry, problems: &mut Vec<String>) {
+    for (index, step) in output.transforms.iter().enumerate() {
+        let step_problem = |detail: &str| {
+            format!(
+                "output '{}' transform step {} ({}) {}",
+                output.id,
+                index + 1,
+                step.name(),
+                detail
+            )
+        };
+        match step {
+            Transform::SelectColumns { columns } if columns.is_empty() => {
+                problems.push(step_problem("must list at least one column"));
+            }
+            Transform::Sort { by } if by.is_empty() => {
+                problems.push(step_problem("must list at least one sort key"));
+            }
+            Transform::GroupBy { by, aggregates } => {
+                if by.is_empty() {
+                    problems.push(step_problem("must list at least one `by` column"));
+                }
+                for aggregation in aggregates {
+                    if aggregation.column.is_none()
+                        && aggregation.function != AggregateFunction::Count
+                    {
+                        problems.push(step_problem(&format!(
+                            "uses {} without a column; only count may omit it",
+                            aggregation.function
+                        )));
+                    }
+                }
+            }
+            Transform::Flatten {
+                separator: Some(separator),
+                ..
+            } if separator.is_empty() => {
+                problems.push(step_problem("must not use an empty separator"));
+            }
+            Transform::Every { step: 0, .. } => {
+                problems.push(step_problem("must use a step of at least 1"));
+            }
+            Transform::Pivot { index, .. } if index.is_empty() => {
+                problems.push(step_problem("must list at least one `index` column"));
+            }
+            Transform::Derive { column, expr } => {
+                if column.trim().is_empty() {
+                    problems.push(step_problem("must name the derived column"));
+                }
+                if let Err(detail) = Expression::parse(expr) {
+                    problems.push(step_problem(&format!(
+                        "has an invalid expression: {detail}"
+                    )));
+                }
+            }
+            Transform::Filter {
+                operator, value, ..
+            } => match (operator.takes_value(), value) {
+                (true, None) => problems.push(step_problem(&format!(
+                    "uses operator '{operator}', which requires a value"
+                ))),
+                (false, Some(_)) => problems.push(step_problem(&format!(
+                    "uses operator '{operator}', which does not take a value"
+                ))),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+}
+
+fn validate_derived_sources(
+    manifest: &Manifest,
+    source_ids: &HashSet<String>,
+    problems: &mut Vec<String>,
+) {
+    for source in &manifest.sources {
+        if let Some(join) = &source.join {
+            validate_join(source, join, source_ids, problems);
+        }
+        if let Some(merge) = &source.merge {
+            validate_merge(source, merge, source_ids, problems);
+        }
+    }
+
+    // Walk each derived source's inputs; meeting the start again is a cycle.
+    for source in &manifest.sources {
+        let mut pending = source.derived_inputs();
+        let mut seen = HashSet::new();
+        while let Some(id) = pending.pop() {
+            if id == source.id {
+                problems.push(format!(
+                    "source '{}' depends on itself through derived sources",
+                    source.id
+                ));
+                break;
+            }
+            if seen.insert(id)
+                && let Some(input) = manifest.sources.iter().find(|source| source.id == id)
+            {
+                pending.extend(input.derived_inputs());
+            }
+        }
+    }
+}
+
+fn validate_join(
+    source: &SourceEntry,
+    join: &JoinEntry,
+    source_ids: &HashSet<String>,
+    problems: &mut Vec<String>,
+) {
+    for (side, id) in [("left", &join.left), ("right", &join.right)] {
+        if !source_ids.contains(id) {
+            problems.push(format!(
+                "source '{}' joins missing {side} source '{id}'",
+                source.id
+            ));
+        }
+    }
+    if join.on.is_empty() {
+        problems.push(format!(
+            "source '{}' join must list at least one `on` column",
+            source.id
+        ));
+    }
+    if join.right_keys().len() != join.on.len() {
+        problems.push(format!(
+            "source '{}' join lists {} `on` columns but {} `right_on` columns",
+            source.id,
+            join.on.len(),
+            join.right_keys().len()
+        ));
+    }
+    if let Some([left, right]) = &join.suffixes
+        && left == right
+    {
+        problems.push(format!(
+            "source '{}' join suffixes must differ (both are \"{left}\")",
+            source.id
+        ));
+    }
+}
+
+fn validate_merge(
+    source: &SourceEntry,
+    merge: &MergeEntry,
+    source_ids: &HashSet<String>,
+    problems: &mut Vec<String>,
+) {
+    if merge.layers.is_empty() {
+        problems.push(format!(
+            "source '{}' merge must list at least one layer",
+            source.id
+        ));
+    }
+    for layer in &merge.layers {
+        if !source_ids.contains(layer) {
+            problems.push(format!(
+                "source '{}' merges missing layer '{layer}'",
+                source.id
+            ));
+        }
+    }
+    match (merge.lists, &merge.key) {
+        (ListMerge::MergeByKey, None) => problems.push(format!(
+            "source '{}' merges lists by key, but does not set key",
+            source.id
+        )),
+        (ListMerge::Replace | ListMerge::Append, Some(_)) => problems.push(format!(
+            "source '{}' sets key, but that option only applies to lists = \"merge_by_key\" (got {})",
+            source.id, merge.lists
+        )),
+        _ => {}
+    }
+}
+
 fn validate_source_options(source: &SourceEntry, problems: &mut Vec<String>) {
-    let format = source
-        .format
-        .or_else(|| SourceFormat::from_extension(&source.path));
+    let kinds = [
+        ("path", source.path.is_some()),
+        ("join", source.join.is_some()),
+        ("merge", source.merge.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(kind, set)| set.then_some(kind))
+    .collect::<Vec<_>>();
+    if kinds.len() != 1 {
+        problems.push(format!(
+            "source '{}' must set exactly one of path, join, or merge (got {})",
+            source.id,
+            if kinds.is_empty() {
+                "none".to_string()
+            } else {
+                kinds.join(" and ")
+            }
+        ));
+        return;
+    }
+    let Some(path) = &source.path else {
+        for (option, set) in [
+            ("format", source.format.is_some()),
+            ("no_header", source.no_header.is_some()),
+            ("sheet", source.sheet.is_some()),
+            ("exact_decimals", source.exact_decimals.is_some()),
+            ("keep_numeric_text", source.keep_numeric_text.is_some()),
+            ("column_types", source.column_types.is_some()),
+            ("select", source.select.is_some()),
+            ("tabular", source.tabular.is_some()),
+        ] {
+            if set {
+                problems.push(format!(
+                    "source '{}' sets {option}, but that option only applies to file sources, not derived ones",
+                    source.id
+                ));
+            }
+        }
+        return;
+    };
+    let format = source.format.or_else(|| SourceFormat::from_extension(path));
 
     if source.no_header.unwrap_or(false) {
         match format {
@@ -134,6 +406,74 @@ fn validate_source_options(source: &SourceEntry, problems: &mut Vec<String>) {
         }
     }
 
+    // YAML, TOML, and XLSX numbers reach the adapters already rounded to
+    // `f64`, so only CSV and JSON still have the source digits.
+    if source.exact_decimals.unwrap_or(false) {
+        match format {
+            Some(SourceFormat::Csv | SourceFormat::Json) => {}
+            Some(other) => problems.push(format!(
+                "source '{}' sets exact_decimals, but that option is only valid for csv or json sources (got {})",
+                source.id, other
+            )),
+            None => problems.push(format!(
+                "source '{}' sets exact_decimals, but its format cannot be inferred; set format = \"csv\" or format = \"json\" explicitly",
+                source.id
+            )),
+        }
+    }
+
+    if source.keep_numeric_text.unwrap_or(false) {
+        match format {
+            Some(SourceFormat::Csv | SourceFormat::Json) => {}
+            Some(other) => problems.push(format!(
+                "source '{}' sets keep_numeric_text, but that option is only valid for csv or json sources (got {})",
+                source.id, other
+            )),
+            None => problems.push(format!(
+                "source '{}' sets keep_numeric_text, but its format cannot be inferred; set format = \"csv\" or format = \"json\" explicitly",
+                source.id
+            )),
+        }
+    }
+
+    if source.column_types.is_some() {
+        match format {
+            Some(SourceFormat::Csv) => {}
+            Some(other) => problems.push(format!(
+                "source '{}' sets column_types, but that option is only valid for csv sources (got {})",
+                source.id, other
+            )),
+            None => problems.push(format!(
+                "source '{}' sets column_types, but its format cannot be inferred; set format = \"csv\" explicitly",
+                source.id
+            )),
+        }
+    }
+
+    if let Some(pointer) = &source.select
+        && !pointer.is_empty()
+        && !pointer.starts_with('/')
+    {
+        problems.push(format!(
+            "source '{}' sets select = \"{}\", but a JSON Pointer must be empty or start with '/'",
+            source.id, pointer
+        ));
+    }
+
+    if source.tabular.unwrap_or(false) {
+        match format {
+            Some(SourceFormat::Json | SourceFormat::Yaml | SourceFormat::Toml) => {}
+            Some(other) => problems.push(format!(
+                "source '{}' sets tabular, but that option is only valid for json, yaml, or toml sources (got {})",
+                source.id, other
+            )),
+            None => problems.push(format!(
+                "source '{}' sets tabular, but its format cannot be inferred; set format = \"json\", \"yaml\", or \"toml\" explicitly",
+                source.id
+            )),
+        }
+    }
+
     if source.sheet.is_some() {
         match format {
             Some(SourceFormat::Xlsx) => {}
@@ -7,7 +7,10 @@ pub use infer::{
     sanitize_root_name,
 };
 pub use load::{LoadedManifest, detect_inspect_manifest, load_manifest};
-pub use model::{Manifest, OutputEntry, ProjectConfig, SourceEntry};
+pub use model::{
+    CheckEntry, ColumnAnnotation, JoinEntry, Manifest, MergeEntry, OutputEntry, ProjectConfig,
+    SourceEntry, UnmatchedPolicy,
+};
 
 use std::collections::HashMap;
 use std::fmt::Write;
@@ -16,35 +19,58 @@ use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 
 use crate::backend::render_document;
-use crate::core::{Document, Origin};
+use crate::core::{ColumnMeta, Document, Origin};
 use crate::error::{DocpackError, DocpackResult};
-use crate::input::{SourceSpec, parse_source};
+use crate::input::{ParseOptions, Schema, SourceSpec, parse_source};
+use crate::transform::{
+    CheckFailure, JoinKind, JoinOptions, RedactionRule, Transform, apply_transforms,
+    join_documents, merge_documents, redact_document, run_check,
+};
+
+/// Files written by a manifest build plus non-fatal warnings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildReport {
+    pub written: Vec<PathBuf>,
+    pub warnings: Vec<String>,
+}
 
 /// Builds every output defined in a manifest file.
-pub fn build(manifest_path: Option<&Path>) -> DocpackResult<Vec<PathBuf>> {
+pub fn build(manifest_path: Option<&Path>) -> DocpackResult<BuildReport> {
     let manifest_path = manifest_path.unwrap_or_else(|| Path::new("docpack.toml"));
     let loaded = load_manifest(manifest_path)?;
-    let mut cached_docs: HashMap<String, Document> = HashMap::new();
+    let mut sources = SourceLoader::new(&loaded);
+    check_schemas(&loaded, &mut sources)?;
+    let failures = run_checks(&loaded, &mut sources)?
+        .into_iter()
+        .filter_map(|(entry, failure)| failure.map(|failure| (entry, failure)))
+        .collect::<Vec<_>>();
+    if !failures.is_empty() {
+        let mut report = String::new();
+        for (index, (entry, failure)) in failures.iter().enumerate() {
+            if index > 0 {
+                writeln!(report).unwrap();
+            }
+            writeln!(report, "{}: {}", entry.describe(), failure.detail).unwrap();
+            report.push_str(&failure.table());
+        }
+        return Err(DocpackError::ChecksFailed {
+            failed: failures.len(),
+            total: loaded.manifest.checks.len(),
+            report: report.trim_end().to_string(),
+        });
+    }
     let mut written = Vec::new();
 
     for output in &loaded.manifest.outputs {
         let source = loaded
             .source_by_id(&output.source)
             .expect("validated manifest source");
-        let doc = if let Some(doc) = cached_docs.get(&source.id) {
-            doc.clone()
-        } else {
-            let spec = SourceSpec::from_path(
-                source.id.clone(),
-                loaded.resolve_source_path(source),
-                source.format,
-                source.no_header.unwrap_or(false),
-                source.sheet.clone(),
-            )?;
-            let doc = parse_source(&spec)?;
-            cached_docs.insert(source.id.clone(), doc.clone());
-            doc
-        };
+        let (doc, _) = redact(
+            &format!("output '{}'", output.id),
+            sources.load(&source.id)?,
+            &output.redact,
+        )?;
+        let doc = apply_transforms(doc, &output.transforms)?;
         let resolved = resolve_manifest_output(&loaded, source, output, &doc)?;
         write_output_file(
             &resolved.output_path,
@@ -53,13 +79,59 @@ pub fn build(manifest_path: Option<&Path>) -> DocpackResult<Vec<PathBuf>> {
         written.push(resolved.output_path);
     }
 
-    Ok(written)
+    Ok(BuildReport {
+        written,
+        warnings: sources.warnings,
+    })
+}
+
+/// Validates every manifest source that declares a `schema`, returning one
+/// line per source that passed.
+pub fn validate_sources(path: &Path) -> DocpackResult<Vec<String>> {
+    let loaded = load_manifest(path)?;
+    check_schemas(&loaded, &mut SourceLoader::new(&loaded))
+}
+
+/// Loads every source that declares a `schema`, returning one line per
+/// source that passed. Schema violations are collected from every source
+/// before failing; any other error stops the check.
+fn check_schemas(
+    loaded: &LoadedManifest,
+    sources: &mut SourceLoader,
+) -> DocpackResult<Vec<String>> {
+    let mut passed = Vec::new();
+    let mut violations = Vec::new();
+    let mut total = 0;
+    for source in &loaded.manifest.sources {
+        if let Some(schema) = loaded.resolve_schema_path(source) {
+            total += 1;
+            match sources.load(&source.id) {
+                Ok(_) => passed.push(format!(
+                    "source '{}' matches schema {}",
+                    source.id,
+                    schema.display()
+                )),
+                Err(error @ DocpackError::SchemaViolation { .. }) => {
+                    violations.push(format!("source '{}': {error}", source.id));
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+    if !violations.is_empty() {
+        return Err(DocpackError::SchemasFailed {
+            failed: violations.len(),
+            total,
+            report: violations.join("\n"),
+        });
+    }
+    Ok(passed)
 }
 
 /// Produces a human-readable manifest inspection report.
 pub fn inspect_manifest(path: &Path) -> DocpackResult<String> {
     let loaded = load_manifest(path)?;
-    let mut cached_docs: HashMap<String, Document> = HashMap::new();
+    let mut sources = SourceLoader::new(&loaded);
     let mut output = String::new();
 
     writeln!(output, "Project").unwrap();
@@ -91,39 +163,66 @@ pub fn inspect_manifest(path: &Path) -> DocpackResult<String> {
 
     writeln!(output, "Sources").unwrap();
     for source in &loaded.manifest.sources {
-        let spec = SourceSpec::from_path(
-            source.id.clone(),
+        let doc = sources.load(&source.id)?;
+        let location = match (
             loaded.resolve_source_path(source),
-            source.format,
-            source.no_header.unwrap_or(false),
-            source.sheet.clone(),
-        )?;
-        let doc = parse_source(&spec)?;
+            &source.join,
+            &source.merge,
+        ) {
+            (Some(path), _, _) => path.display().to_string(),
+            (None, Some(join), _) => {
+                format!("{} join of {} and {}", join.how, join.left, join.right)
+            }
+            (None, None, Some(merge)) => format!(
+                "merge of {} (lists: {})",
+                merge.layers.join(", "),
+                merge.lists
+            ),
+            (None, None, None) => unreachable!("validated manifest source"),
+        };
         writeln!(
             output,
             "  - {}: {} (format: {}, shape: {})",
-            source.id,
-            loaded.resolve_source_path(source).display(),
-            doc.meta.format,
-            doc.meta.top_level_shape
+            source.id, location, doc.meta.format, doc.meta.top_level_shape
         )
         .unwrap();
-        if let Some(columns) = &doc.meta.tabular_columns {
-            writeln!(output, "    columns: {}", columns.join(", ")).unwrap();
+        if !doc.meta.columns.is_empty() {
+            writeln!(output, "    columns:").unwrap();
+            for column in &doc.meta.columns {
+                writeln!(output, "      - {}", describe_column(column)).unwrap();
+            }
         }
-        cached_docs.insert(source.id.clone(), doc);
+        if let Some(schema) = loaded.resolve_schema_path(source) {
+            writeln!(output, "    schema: {} (valid)", schema.display()).unwrap();
+        }
+        write_redactions(&mut output, sources.redactions(&source.id));
     }
     writeln!(output).unwrap();
 
-    writeln!(output, "Outputs").unwrap();
+    let mut output_docs = Vec::new();
+    let mut output_redactions = Vec::new();
     for entry in &loaded.manifest.outputs {
+        let (doc, report) = redact(
+            &format!("output '{}'", entry.id),
+            sources.load(&entry.source)?,
+            &entry.redact,
+        )?;
+        output_docs.push(apply_transforms(doc, &entry.transforms)?);
+        output_redactions.push(report);
+    }
+
+    writeln!(output, "Outputs").unwrap();
+    for ((entry, doc), redactions) in loaded
+        .manifest
+        .outputs
+        .iter()
+        .zip(&output_docs)
+        .zip(&output_redactions)
+    {
         let resolved = {
             let source = loaded
                 .source_by_id(&entry.source)
                 .expect("validated manifest source");
-            let doc = cached_docs
-                .get(&source.id)
-                .expect("manifest inspect parses every source");
             resolve_manifest_output(&loaded, source, entry, doc)?
         };
         writeln!(
@@ -134,33 +233,338 @@ pub fn inspect_manifest(path: &Path) -> DocpackResult<String> {
             resolved.output_path.display()
         )
         .unwrap();
+        write_redactions(&mut output, redactions);
+        if !entry.transforms.is_empty() {
+            writeln!(
+                output,
+                "    transforms: {}",
+                entry
+                    .transforms
+                    .iter()
+                    .map(Transform::name)
+                    .collect::<Vec<_>>()
+                    .join(" -> ")
+            )
+            .unwrap();
+        }
+        if !resolved.request.options.column_formats.is_empty() {
+            writeln!(
+                output,
+                "    column_formats: {} (locale: {})",
+                resolved
+                    .request
+                    .options
+                    .column_formats
+                    .keys()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                resolved.request.options.locale
+            )
+            .unwrap();
+        }
+        if let Some(class) = &resolved.request.options.css_class {
+            writeln!(output, "    css_class: {class}").unwrap();
+        }
     }
     writeln!(output).unwrap();
 
     writeln!(output, "Resolved Build Plan").unwrap();
-    for entry in &loaded.manifest.outputs {
+    for (entry, doc) in loaded.manifest.outputs.iter().zip(&output_docs) {
         let source = loaded
             .source_by_id(&entry.source)
             .expect("validated manifest source");
-        let doc = cached_docs
-            .get(&source.id)
-            .expect("manifest inspect parses every source");
         let resolved = resolve_manifest_output(&loaded, source, entry, doc)?;
         writeln!(
             output,
-            "  - {}: backend={}, artifact={}, style={}, root_name={}",
+            "  - {}: backend={}, artifact={}, style={}, root_name={}, key_order={}, non_finite={}",
             resolved.output_id,
             resolved.request.backend,
             resolved.request.artifact,
             resolved.request.style,
-            resolved.request.root_name
+            resolved.request.root_name,
+            resolved.request.options.key_order,
+            resolved.request.options.non_finite
         )
         .unwrap();
     }
 
+    let checks = run_checks(&loaded, &mut sources)?;
+    if !checks.is_empty() {
+        writeln!(output).unwrap();
+        writeln!(output, "Checks").unwrap();
+        for (entry, failure) in checks {
+            match failure {
+                None => writeln!(output, "  - {}: passed", entry.describe()).unwrap(),
+                Some(failure) => writeln!(
+                    output,
+                    "  - {}: failed, {}",
+                    entry.describe(),
+                    failure.detail
+                )
+                .unwrap(),
+            }
+        }
+    }
+
+    if !sources.warnings.is_empty() {
+        writeln!(output).unwrap();
+        writeln!(output, "Warnings").unwrap();
+        for warning in &sources.warnings {
+            writeln!(output, "  - {warning}").unwrap();
+        }
+    }
+
     Ok(output)
 }
 
+/// Evaluates every manifest check against its loaded source, in manifest
+/// order. A check that cannot be evaluated, such as one naming an unknown
+/// column, counts as failed.
+fn run_checks<'m>(
+    loaded: &'m LoadedManifest,
+    sources: &mut SourceLoader,
+) -> DocpackResult<Vec<(&'m CheckEntry, Option<CheckFailure>)>> {
+    let mut results = Vec::new();
+    for entry in &loaded.manifest.checks {
+        let doc = sources.load(&entry.source)?;
+        let failure =
+            run_check(&doc, &entry.check, entry.column.as_deref()).unwrap_or_else(|detail| {
+                Some(CheckFailure {
+                    detail: format!("cannot be evaluated: {detail}"),
+                    columns: Vec::new(),
+                    rows: Vec::new(),
+                })
+            });
+        results.push((entry, failure));
+    }
+    Ok(results)
+}
+
+fn write_redactions(output: &mut String, redactions: &[String]) {
+    if redactions.is_empty() {
+        return;
+    }
+    writeln!(output, "    redacted:").unwrap();
+    for line in redactions {
+        writeln!(output, "      - {line}").unwrap();
+    }
+}
+
+/// Applies redaction rules declared on a source or output, naming the
+/// declaring entry in errors.
+fn redact(
+    scope: &str,
+    doc: Document,
+    rules: &[RedactionRule],
+) -> DocpackResult<(Document, Vec<String>)> {
+    if rules.is_empty() {
+        return Ok((doc, Vec::new()));
+    }
+    redact_document(doc, rules).map_err(|(target, detail)| DocpackError::Redaction {
+        scope: scope.to_string(),
+        target,
+        detail,
+    })
+}
+
+/// Loads manifest sources by id, caching each document and collecting
+/// warnings raised while deriving sources.
+pub struct SourceLoader<'a> {
+    loaded: &'a LoadedManifest,
+    cache: HashMap<String, Document>,
+    redactions: HashMap<String, Vec<String>>,
+    pub warnings: Vec<String>,
+}
+
+impl<'a> SourceLoader<'a> {
+    pub fn new(loaded: &'a LoadedManifest) -> Self {
+        Self {
+            loaded,
+            cache: HashMap::new(),
+            redactions: HashMap::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Describes the redactions applied to a loaded source.
+    pub fn redactions(&self, id: &str) -> &[String] {
+        self.redactions.get(id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the normalized document for a validated source id.
+    pub fn load(&mut self, id: &str) -> DocpackResult<Document> {
+        if let Some(doc) = self.cache.get(id) {
+            return Ok(doc.clone());
+        }
+        let source = self
+            .loaded
+            .source_by_id(id)
+            .expect("validated manifest source");
+        let mut doc = if let Some(join) = &source.join {
+            self.join(source, join)?
+        } else if let Some(merge) = &source.merge {
+            let layers = merge
+                .layers
+                .iter()
+                .map(|layer| self.load(layer))
+                .collect::<DocpackResult<Vec<_>>>()?;
+            merge_documents(&source.id, &layers, merge.lists, merge.key.as_deref())?
+        } else {
+            let path = self
+                .loaded
+                .resolve_source_path(source)
+                .expect("validated manifest source has a path, join, or merge");
+            load_source_document(source, path)?
+        };
+        annotate_columns(self.loaded, source, &mut doc)?;
+        if let Some(path) = self.loaded.resolve_schema_path(source) {
+            Schema::load(&path)?.validate(&doc)?;
+        }
+        let (doc, report) = redact(&format!("source '{}'", source.id), doc, &source.redact)?;
+        self.redactions.insert(id.to_string(), report);
+        self.cache.insert(id.to_string(), doc.clone());
+        Ok(doc)
+    }
+
+    fn join(&mut self, source: &SourceEntry, join: &JoinEntry) -> DocpackResult<Document> {
+        let left = self.load(&join.left)?;
+        let right = self.load(&join.right)?;
+        let [left_suffix, right_suffix] = join
+            .suffixes
+            .clone()
+            .unwrap_or_else(|| [String::new(), "_right".to_string()]);
+        let derive_error = |detail: String| DocpackError::DerivedSource {
+            source_id: source.id.clone(),
+            detail,
+        };
+        let outcome = join_documents(
+            &left,
+            &right,
+            &JoinOptions {
+                left_on: &join.on,
+                right_on: join.right_keys(),
+                kind: join.how,
+                suffixes: (&left_suffix, &right_suffix),
+            },
+        )
+        .map_err(derive_error)?;
+
+        let mut unmatched = Vec::new();
+        if !outcome.unmatched_left.is_empty() {
+            unmatched.push(describe_unmatched(
+                &outcome.unmatched_left,
+                &join.left,
+                &join.right,
+            ));
+        }
+        if join.how == JoinKind::Full && !outcome.unmatched_right.is_empty() {
+            unmatched.push(describe_unmatched(
+                &outcome.unmatched_right,
+                &join.right,
+                &join.left,
+            ));
+        }
+        if !unmatched.is_empty() {
+            match join.unmatched {
+                UnmatchedPolicy::Ignore => {}
+                UnmatchedPolicy::Warn => self.warnings.extend(
+                    unmatched
+                        .into_iter()
+                        .map(|detail| format!("source '{}': {detail}", source.id)),
+                ),
+                UnmatchedPolicy::Error => return Err(derive_error(unmatched.join("; "))),
+            }
+        }
+
+        let mut doc = outcome.doc;
+        doc.source_id = source.id.clone();
+        Ok(doc)
+    }
+}
+
+fn describe_unmatched(keys: &[String], from: &str, to: &str) -> String {
+    const SHOWN: usize = 5;
+    let mut listed = keys
+        .iter()
+        .take(SHOWN)
+        .map(|key| format!("({key})"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if keys.len() > SHOWN {
+        write!(listed, ", and {} more", keys.len() - SHOWN).unwrap();
+    }
+    format!(
+        "{} row(s) of '{from}' have no match in '{to}': {listed}",
+        keys.len()
+    )
+}
+
+/// Reads and normalizes one file-backed manifest source with its declared
+/// options.
+fn load_source_document(source: &SourceEntry, path: PathBuf) -> DocpackResult<Document> {
+    let spec = SourceSpec::from_path(
+        source.id.clone(),
+        path,
+        source.format,
+        source.no_header.unwrap_or(false),
+        source.sheet.clone(),
+    )?
+    .with_options(ParseOptions {
+        exact_decimals: source.exact_decimals.unwrap_or(false),
+        keep_numeric_text: source.keep_numeric_text.unwrap_or(false),
+        column_types: source.column_types.clone().unwrap_or_default(),
+        select: source.select.clone(),
+        tabular: source.tabular.unwrap_or(false),
+    });
+    parse_source(&spec)
+}
+
+fn annotate_columns(
+    loaded: &LoadedManifest,
+    source: &SourceEntry,
+    doc: &mut Document,
+) -> DocpackResult<()> {
+    for (name, annotation) in source.columns.iter().flatten() {
+        let Some(column) = doc
+            .meta
+            .columns
+            .iter_mut()
+            .find(|column| &column.name == name)
+        else {
+            return Err(DocpackError::ManifestInvalid {
+                path: loaded.path.clone(),
+                problems: vec![format!(
+                    "source '{}' annotates unknown column '{}'",
+                    source.id, name
+                )],
+            });
+        };
+        column.label = annotation.label.clone();
+        column.unit = annotation.unit.clone();
+        column.description = annotation.description.clone();
+    }
+    Ok(())
+}
+
+/// Formats one column's metadata as a single inspect line.
+pub fn describe_column(column: &ColumnMeta) -> String {
+    let mut line = format!(
+        "{}: {}, nullable={}, max_width={}",
+        column.name, column.kind, column.nullable, column.max_width
+    );
+    for (key, value) in [
+        ("label", &column.label),
+        ("unit", &column.unit),
+        ("description", &column.description),
+    ] {
+        if let Some(value) = value {
+            write!(line, ", {key}={value:?}").unwrap();
+        }
+    }
+    line
+}
+
 /// Creates a minimal manifest template on disk.
 pub fn init_template(path: Option<&Path>, force: bool) -> DocpackResult<PathBuf> {
     let target = resolve_init_target(path);
@@ -206,6 +610,20 @@ output_dir = "generated"
 #path = "data/sales.csv"
 #format = "csv"
 #no_header = false
+#exact_decimals = false
+#keep_numeric_text = false
+#column_types = { order_date = "date" }
+#columns = { amount = { label = "Amount", unit = "EUR" } }
+#select = "/reports/2025"
+#tabular = false
+
+#[[sources]]
+#id = "sales_with_managers"
+#join = { left = "sales", right = "regions", on = ["region"], how = "left", unmatched = "warn" }
+
+#[[sources]]
+#id = "settings"
+#merge = { layers = ["defaults", "edition"], lists = "replace" }
 
 #[[outputs]]
 #id = "sales_typst"
@@ -215,6 +633,16 @@ output_dir = "generated"
 #artifact = "data-module"
 #style = "typst-official"
 #root_name = "sales"
+#key_order = "sorted"
+#non_finite = "reject"
+#locale = "en"
+#column_formats = { amount = { decimals = 2, thousands = true, prefix = "$" } }
+
+#[[outputs.transforms]]
+#op = "filter"
+#column = "region"
+#operator = "eq"
+#value = "East"
 "#
 }
 
@@ -1,9 +1,12 @@
 use std::path::PathBuf;
 
+use indexmap::IndexMap;
 use serde::Deserialize;
 
-use crate::backend::{ArtifactKind, BackendKind};
+use crate::backend::{ArtifactKind, BackendKind, ColumnFormat, KeyOrder, Locale, NonFinitePolicy};
 use crate::core::SourceFormat;
+use crate::input::ColumnType;
+use crate::transform::{Check, JoinKind, ListMerge, RedactionRule, Transform};
 
 /// Top-level manifest structure loaded from `docpack.toml`.
 #[derive(Debug, Clone, Deserialize)]
@@ -13,6 +16,8 @@ pub struct Manifest {
     pub sources: Vec<SourceEntry>,
     #[serde(default)]
     pub outputs: Vec<OutputEntry>,
+    #[serde(default)]
+    pub checks: Vec<CheckEntry>,
 }
 
 /// Project-level manifest settings.
@@ -23,13 +28,92 @@ pub struct ProjectConfig {
 }
 
 /// Source entry declared in a manifest.
+///
+/// A source either reads a file (`path`) or is derived from other sources
+/// (`join` or `merge`); validation enforces exactly one of them.
 #[derive(Debug, Clone, Deserialize)]
 pub struct SourceEntry {
     pub id: String,
-    pub path: PathBuf,
+    pub path: Option<PathBuf>,
     pub format: Option<SourceFormat>,
     pub no_header: Option<bool>,
     pub sheet: Option<String>,
+    pub exact_decimals: Option<bool>,
+    pub keep_numeric_text: Option<bool>,
+    pub column_types: Option<IndexMap<String, ColumnType>>,
+    pub columns: Option<IndexMap<String, ColumnAnnotation>>,
+    pub select: Option<String>,
+    pub tabular: Option<bool>,
+    pub join: Option<JoinEntry>,
+    pub merge: Option<MergeEntry>,
+    /// Local JSON Schema the loaded document must satisfy.
+    pub schema: Option<PathBuf>,
+    /// Redaction rules applied once the source is loaded, before any output,
+    /// derived source, or transform sees it.
+    #[serde(default)]
+    pub redact: Vec<RedactionRule>,
+}
+
+impl SourceEntry {
+    /// Returns the ids of the sources this derived source reads.
+    pub fn derived_inputs(&self) -> Vec<&str> {
+        match (&self.join, &self.merge) {
+            (Some(join), _) => vec![join.left.as_str(), join.right.as_str()],
+            (None, Some(merge)) => merge.layers.iter().map(String::as_str).collect(),
+            (None, None) => Vec::new(),
+        }
+    }
+}
+
+/// Derived source that joins two other manifest sources on key columns.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JoinEntry {
+    pub left: String,
+    pub right: String,
+    /// Key columns on the left side, and on the right unless `right_on` is set.
+    pub on: Vec<String>,
+    pub right_on: Option<Vec<String>>,
+    #[serde(default)]
+    pub how: JoinKind,
+    /// Suffixes for non-key columns present on both sides; `["", "_right"]` by default.
+    pub suffixes: Option<[String; 2]>,
+    #[serde(default)]
+    pub unmatched: UnmatchedPolicy,
+}
+
+impl JoinEntry {
+    /// Returns the right-side key columns.
+    pub fn right_keys(&self) -> &[String] {
+        self.right_on.as_deref().unwrap_or(&self.on)
+    }
+}
+
+/// Derived source that deep-merges object-shaped sources, later layers winning.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MergeEntry {
+    pub layers: Vec<String>,
+    #[serde(default)]
+    pub lists: ListMerge,
+    /// Identifying field for `lists = "merge_by_key"`.
+    pub key: Option<String>,
+}
+
+/// What a join does with keys that find no partner.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnmatchedPolicy {
+    Ignore,
+    #[default]
+    Warn,
+    Error,
+}
+
+/// User-supplied display metadata for one source column.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ColumnAnnotation {
+    pub label: Option<String>,
+    pub unit: Option<String>,
+    pub description: Option<String>,
 }
 
 /// Output entry declared in a manifest.
@@ -42,4 +126,37 @@ pub struct OutputEntry {
     pub artifact: Option<ArtifactKind>,
     pub style: Option<String>,
     pub root_name: Option<String>,
+    pub key_order: Option<KeyOrder>,
+    pub non_finite: Option<NonFinitePolicy>,
+    pub locale: Option<Locale>,
+    pub column_formats: Option<IndexMap<String, ColumnFormat>>,
+    /// `class` attribute for `html-table` output.
+    pub css_class: Option<String>,
+    /// Redaction rules applied to this output's source document before its
+    /// transforms run.
+    #[serde(default)]
+    pub redact: Vec<RedactionRule>,
+    #[serde(default)]
+    pub transforms: Vec<Transform>,
+}
+
+/// Data-quality assertion declared in a manifest, evaluated before a build
+/// writes any output.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CheckEntry {
+    pub source: String,
+    /// Column the check inspects; `row_count` and `expression` take none.
+    pub column: Option<String>,
+    #[serde(flatten)]
+    pub check: Check,
+}
+
+impl CheckEntry {
+    /// Names the check for reports, e.g. `range on sales.amount`.
+    pub fn describe(&self) -> String {
+        match &self.column {
+            Some(column) => format!("{} on {}.{column}", self.check.name(), self.source),
+            None => format!("{} on {}", self.check.name(), self.source),
+        }
+    }
 }
@@ -0,0 +1,274 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::core::{ColumnKind, ColumnMeta, Value};
+use crate::transform::Literal;
+use crate::transform::compare::{cell_key, compare_cells, numeric};
+use crate::transform::decimal::Exact;
+use crate::transform::table::Table;
+
+/// Fraction digits a decimal `mean` keeps beyond those of its inputs.
+const MEAN_EXTRA_DIGITS: usize = 6;
+
+/// Summary function applied to the cells of one group.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregateFunction {
+    Sum,
+    Mean,
+    Min,
+    Max,
+    Count,
+    CountDistinct,
+}
+
+impl Display for AggregateFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Sum => "sum",
+            Self::Mean => "mean",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Count => "count",
+            Self::CountDistinct => "count_distinct",
+        };
+        write!(f, "{value}")
+    }
+}
+
+/// One output column of a `group_by` step.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Aggregation {
+    /// Input column; only `count` may omit it, counting rows instead.
+    pub column: Option<String>,
+    pub function: AggregateFunction,
+    /// Output column name, defaulting to `<function>_<column>` (or `count`).
+    #[serde(rename = "as")]
+    pub name: Option<String>,
+}
+
+impl Aggregation {
+    /// Returns the output column name.
+    pub fn output_name(&self) -> String {
+        match (&self.name, &self.column) {
+            (Some(name), _) => name.clone(),
+            (None, Some(column)) => format!("{}_{column}", self.function),
+            (None, None) => self.function.to_string(),
+        }
+    }
+}
+
+/// Collapses rows sharing the `by` columns into one row per group, in order of
+/// first appearance.
+pub(crate) fn group_by(
+    table: &Table,
+    by: &[String],
+    aggregations: &[Aggregation],
+) -> Result<Table, String> {
+    let keys = by
+        .iter()
+        .map(|column| table.index_of(column))
+        .collect::<Result<Vec<_>, _>>()?;
+    let inputs = aggregations
+        .iter()
+        .map(|aggregation| match &aggregation.column {
+            Some(column) => Ok(Some(table.index_of(column)?)),
+            None if aggregation.function == AggregateFunction::Count => Ok(None),
+            None => Err(format!("{} needs a column", aggregation.function)),
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let mut columns = keys
+        .iter()
+        .map(|index| table.columns[*index].clone())
+        .collect::<Vec<_>>();
+    for aggregation in aggregations {
+        push_column(&mut columns, aggregation.output_name())?;
+    }
+
+    let groups = group_rows(table, &keys);
+    let mut rows = Vec::with_capacity(groups.len());
+    for members in groups.values() {
+        let mut row = keys
+            .iter()
+            .map(|index| table.rows[members[0]][*index].clone())
+            .collect::<Vec<_>>();
+        for (aggregation, input) in aggregations.iter().zip(&inputs) {
+            row.push(match input {
+                Some(index) => aggregate(aggregation.function, table, members, *index)?,
+                None => Value::Integer(members.len() as i64),
+            });
+        }
+        rows.push(row);
+    }
+    Ok(Table {
+        records: true,
+        columns,
+        rows,
+    })
+}
+
+/// Spreads the distinct values of `columns` into new columns, one row per
+/// distinct `index` key, aggregating `values` per cell.
+pub(crate) fn pivot(
+    table: &Table,
+    index: &[String],
+    columns: &str,
+    values: &str,
+    function: AggregateFunction,
+    fill: Option<&Literal>,
+) -> Result<Table, String> {
+    let keys = index
+        .iter()
+        .map(|column| table.index_of(column))
+        .collect::<Result<Vec<_>, _>>()?;
+    let spread = table.index_of(columns)?;
+    let value_index = table.index_of(values)?;
+
+    let mut output_columns = keys
+        .iter()
+        .map(|index| table.columns[*index].clone())
+        .collect::<Vec<_>>();
+    let mut spread_names = IndexMap::new();
+    for row in &table.rows {
+        let name = row[spread].scalar_text().ok_or_else(|| {
+            format!("pivot column '{columns}' must hold scalar values to name columns")
+        })?;
+        if !spread_names.contains_key(&name) {
+            push_column(&mut output_columns, name.clone())?;
+            spread_names.insert(name, spread_names.len());
+        }
+    }
+
+    let fill = fill.map_or(Value::Null, Literal::to_value);
+    let mut rows = Vec::new();
+    for members in group_rows(table, &keys).values() {
+        let mut cells = vec![Vec::new(); spread_names.len()];
+        for &member in members {
+            let name = table.rows[member][spread]
+                .scalar_text()
+                .expect("pivot names were checked above");
+            cells[spread_names[&name]].push(member);
+        }
+        let mut row = keys
+            .iter()
+            .map(|index| table.rows[members[0]][*index].clone())
+            .collect::<Vec<_>>();
+        for members in cells {
+            row.push(if members.is_empty() {
+                fill.clone()
+            } else {
+                aggregate(function, table, &members, value_index)?
+            });
+        }
+        rows.push(row);
+    }
+    Ok(Table {
+        records: true,
+        columns: output_columns,
+        rows,
+    })
+}
+
+fn push_column(columns: &mut Vec<ColumnMeta>, name: String) -> Result<(), String> {
+    if columns.iter().any(|column| column.name == name) {
+        return Err(format!("output column '{name}' would appear twice"));
+    }
+    columns.push(ColumnMeta::infer(name, []));
+    Ok(())
+}
+
+/// Buckets row indexes by their key cells, preserving first-appearance order.
+fn group_rows(table: &Table, keys: &[usize]) -> IndexMap<Vec<String>, Vec<usize>> {
+    let mut groups: IndexMap<Vec<String>, Vec<usize>> = IndexMap::new();
+    for (row_index, row) in table.rows.iter().enumerate() {
+        let key = keys.iter().map(|index| cell_key(&row[*index])).collect();
+        groups.entry(key).or_default().push(row_index);
+    }
+    groups
+}
+
+fn aggregate(
+    function: AggregateFunction,
+    table: &Table,
+    members: &[usize],
+    column: usize,
+) -> Result<Value, String> {
+    let cells = members
+        .iter()
+        .map(|row| (*row, &table.rows[*row][column]))
+        .filter(|(_, cell)| !matches!(cell, Value::Null));
+    let at = |row: usize| format!("/{row}/{}", table.columns[column].name);
+    match function {
+        AggregateFunction::Count => Ok(Value::Integer(cells.count() as i64)),
+        AggregateFunction::CountDistinct => Ok(Value::Integer(
+            cells
+                .map(|(_, cell)| cell_key(cell))
+                .collect::<HashSet<_>>()
+                .len() as i64,
+        )),
+        AggregateFunction::Sum | AggregateFunction::Mean => {
+            let mut integer_sum = Some(0i64);
+            let mut exact_sum = Exact::parse("0");
+            let mut decimal = false;
+            let mut float_sum = 0.0;
+            let mut count = 0;
+            for (row, cell) in cells {
+                let number = numeric(cell).ok_or_else(|| {
+                    format!(
+                        "{function} expects numbers, got {} at {}",
+                        ColumnKind::of(cell),
+                        at(row)
+                    )
+                })?;
+                let typed = cell.as_typed();
+                integer_sum = match typed {
+                    Value::Integer(value) => integer_sum.and_then(|sum| sum.checked_add(*value)),
+                    _ => None,
+                };
+                decimal |= matches!(typed, Value::Decimal(_));
+                exact_sum = exact_sum
+                    .zip(Exact::of(typed))
+                    .map(|(sum, value)| sum.add(value));
+                float_sum += number;
+                count += 1;
+            }
+            Ok(match (function, count, integer_sum, exact_sum) {
+                (_, 0, _, _) => Value::Null,
+                (AggregateFunction::Sum, _, Some(sum), _) => Value::Integer(sum),
+                (AggregateFunction::Sum, _, None, Some(sum)) if decimal => sum.into_value(),
+                (AggregateFunction::Sum, _, _, _) => Value::Float(float_sum),
+                (_, _, _, Some(sum)) if decimal => {
+                    sum.divide(count, MEAN_EXTRA_DIGITS).into_value()
+                }
+                _ => Value::Float(float_sum / count as f64),
+            })
+        }
+        AggregateFunction::Min | AggregateFunction::Max => {
+            let mut best: Option<&Value> = None;
+            for (row, cell) in cells {
+                let Some(current) = best else {
+                    best = Some(cell);
+                    continue;
+                };
+                let ordering = compare_cells(cell, current).ok_or_else(|| {
+                    format!(
+                        "{function} cannot compare {} with {} at {}",
+                        ColumnKind::of(cell),
+                        ColumnKind::of(current),
+                        at(row)
+                    )
+                })?;
+                if (function == AggregateFunction::Min && ordering.is_lt())
+                    || (function == AggregateFunction::Max && ordering.is_gt())
+                {
+                    best = Some(cell);
+                }
+            }
+            Ok(best.cloned().unwrap_or(Value::Null))
+        }
+    }
+}
@@ -0,0 +1,446 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::core::{Document, Value};
+use crate::transform::compare::{cell_key, compare_cells};
+use crate::transform::table::{Table, text_table};
+use crate::transform::{Expression, Literal};
+
+/// Failing rows listed per check before the report is cut short.
+const SHOWN_ROWS: usize = 10;
+
+/// One data-quality rule of a manifest `[[checks]]` entry.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "check", rename_all = "snake_case")]
+pub enum Check {
+    /// Every cell of the column is non-null.
+    NotNull,
+    /// No two non-null cells of the column are equal.
+    Unique,
+    /// Non-null cells lie within the inclusive bounds.
+    Range {
+        min: Option<Literal>,
+        max: Option<Literal>,
+    },
+    /// Non-null cells contain a match of the regular expression.
+    Matches { pattern: String },
+    /// The source has an inclusive number of rows.
+    RowCount {
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+    /// Non-null cells equal one of the listed values.
+    AllowedValues { values: Vec<Literal> },
+    /// The [`Expression`] evaluates to `true` for every row.
+    Expression { expr: String },
+}
+
+impl Check {
+    /// Returns the manifest `check` name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::NotNull => "not_null",
+            Self::Unique => "unique",
+            Self::Range { .. } => "range",
+            Self::Matches { .. } => "matches",
+            Self::RowCount { .. } => "row_count",
+            Self::AllowedValues { .. } => "allowed_values",
+            Self::Expression { .. } => "expression",
+        }
+    }
+
+    /// Checks the rule's own settings and whether it needs a `column`.
+    pub fn validate(&self, column: Option<&str>) -> Result<(), String> {
+        let column_wanted = !matches!(self, Self::RowCount { .. } | Self::Expression { .. });
+        match (column_wanted, column) {
+            (true, None) => return Err("needs a column".to_string()),
+            (false, Some(_)) => return Err("does not take a column".to_string()),
+            _ => {}
+        }
+        match self {
+            Self::Range {
+                min: None,
+                max: None,
+            }
+            | Self::RowCount {
+                min: None,
+                max: None,
+            } => Err("needs min, max, or both".to_string()),
+            Self::RowCount {
+                min: Some(min),
+                max: Some(max),
+            } if min > max => Err(format!("has min {min} above max {max}")),
+            Self::Range {
+                min: Some(min),
+                max: Some(max),
+            } => {
+                let (min, max) = (min.to_value(), max.to_value());
+                let text = |value: &Value| value.scalar_text().unwrap_or_default();
+                match compare_cells(&min, &max) {
+                    Some(ordering) if ordering.is_gt() => {
+                        Err(format!("has min {} above max {}", text(&min), text(&max)))
+                    }
+                    Some(_) => Ok(()),
+                    None => Err(format!(
+                        "has min {} and max {} that cannot be compared",
+                        text(&min),
+                        text(&max)
+                    )),
+                }
+            }
+            Self::Matches { pattern } => Regex::new(pattern)
+                .map(|_| ())
+                .map_err(|error| format!("has an invalid pattern: {error}")),
+            Self::AllowedValues { values } if values.is_empty() => {
+                Err("needs at least one allowed value".to_string())
+            }
+            Self::Expression { expr } => Expression::parse(expr)
+                .map(|_| ())
+                .map_err(|detail| format!("has an invalid expression: {detail}")),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Rows that broke a check, with the cells needed to print them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckFailure {
+    pub detail: String,
+    pub columns: Vec<String>,
+    /// 0-based row index and the row's cell text.
+    pub rows: Vec<(usize, Vec<String>)>,
+}
+
+impl CheckFailure {
+    /// Formats the failing rows as an aligned text table, listing at most
+    /// ten rows.
+    pub fn table(&self) -> String {
+        if self.rows.is_empty() {
+            return String::new();
+        }
+        let header = std::iter::once("row".to_string())
+            .chain(self.columns.iter().cloned())
+            .collect::<Vec<_>>();
+        let lines = self
+            .rows
+            .iter()
+            .take(SHOWN_ROWS)
+            .map(|(index, cells)| {
+                std::iter::once(index.to_string())
+                    .chain(cells.iter().cloned())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let mut table = text_table(&header, &lines);
+        if self.rows.len() > SHOWN_ROWS {
+            writeln!(
+                table,
+                "... and {} more row(s)",
+                self.rows.len() - SHOWN_ROWS
+            )
+            .unwrap();
+        }
+        table
+    }
+}
+
+/// Evaluates one check against a tabular document or list of objects,
+/// returning `None` when every row passes.
+///
+/// Null cells only fail `not_null`; the other column checks skip them.
+pub fn run_check(
+    doc: &Document,
+    check: &Check,
+    column: Option<&str>,
+) -> Result<Option<CheckFailure>, String> {
+    let table = Table::from_records(doc).or_else(|_| Table::from_document(doc))?;
+    if let Check::RowCount { min, max } = check {
+        let count = table.rows.len();
+        let expected = match (min, max) {
+            (Some(min), _) if count < *min => format!("at least {min}"),
+            (_, Some(max)) if count > *max => format!("at most {max}"),
+            _ => return Ok(None),
+        };
+        return Ok(Some(CheckFailure {
+            detail: format!("has {count} row(s), expected {expected}"),
+            columns: Vec::new(),
+            rows: Vec::new(),
+        }));
+    }
+
+    let failing = match (check, column) {
+        (Check::Expression { expr }, _) => {
+            let expression = Expression::parse(expr)?;
+            let inputs = expression
+                .columns()
+                .into_iter()
+                .map(|name| Ok((name, table.index_of(name)?)))
+                .collect::<Result<Vec<_>, String>>()?;
+            let mut failing = Vec::new();
+            for (index, row) in table.rows.iter().enumerate() {
+                let result = expression
+                    .evaluate(&|name| {
+                        inputs
+                            .iter()
+                            .find(|(input, _)| *input == name)
+                            .map_or(&Value::Null, |(_, position)| &row[*position])
+                    })
+                    .map_err(|detail| format!("at row {index}: {detail}"))?;
+                if result != Value::Bool(true) {
+                    failing.push(index);
+                }
+            }
+            failing
+        }
+        (_, Some(column)) => {
+            let position = table.index_of(column)?;
+            let cells = table.rows.iter().map(|row| &row[position]);
+            column_failures(check, cells)?
+        }
+        (_, None) => return Err(format!("{} needs a column", check.name())),
+    };
+    if failing.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(CheckFailure {
+        detail: format!("{} of {} row(s) fail", failing.len(), table.rows.len()),
+        columns: table.columns.iter().map(|meta| meta.name.clone()).collect(),
+        rows: failing
+            .into_iter()
+            .map(|index| {
+                let cells = table.rows[index]
+                    .iter()
+                    .map(|cell| cell.scalar_text().unwrap_or_else(|| "…".to_string()))
+                    .collect();
+                (index, cells)
+            })
+            .collect(),
+    }))
+}
+
+fn column_failures<'a>(
+    check: &Check,
+    cells: impl Iterator<Item = &'a Value>,
+) -> Result<Vec<usize>, String> {
+    let cells = cells.enumerate();
+    let present = |cell: &&Value| !matches!(cell, Value::Null);
+    Ok(match check {
+        Check::NotNull => cells
+            .filter(|(_, cell)| !present(cell))
+            .map(|(index, _)| index)
+            .collect(),
+        Check::Unique => {
+            let mut seen: HashMap<String, Vec<usize>> = HashMap::new();
+            for (index, cell) in cells.filter(|(_, cell)| present(cell)) {
+                seen.entry(cell_key(cell)).or_default().push(index);
+            }
+            let mut failing = seen
+                .into_values()
+                .filter(|rows| rows.len() > 1)
+                .flatten()
+                .collect::<Vec<_>>();
+            failing.sort_unstable();
+            failing
+        }
+        Check::Range { min, max } => {
+            let (min, max) = (
+                min.as_ref().map(Literal::to_value),
+                max.as_ref().map(Literal::to_value),
+            );
+            cells
+                .filter(|(_, cell)| present(cell))
+                .filter(|(_, cell)| {
+                    let below = min.as_ref().is_some_and(|min| {
+                        compare_cells(cell, min).is_none_or(|ordering| ordering.is_lt())
+                    });
+                    let above = max.as_ref().is_some_and(|max| {
+                        compare_cells(cell, max).is_none_or(|ordering| ordering.is_gt())
+                    });
+                    below || above
+                })
+                .map(|(index, _)| index)
+                .collect()
+        }
+        Check::Matches { pattern } => {
+            let regex = Regex::new(pattern).map_err(|error| error.to_string())?;
+            cells
+                .filter(|(_, cell)| present(cell))
+                .filter(|(_, cell)| !cell.scalar_text().is_some_and(|text| regex.is_match(&text)))
+                .map(|(index, _)| index)
+                .collect()
+        }
+        Check::AllowedValues { values } => {
+            let values = values.iter().map(Literal::to_value).collect::<Vec<_>>();
+            cells
+                .filter(|(_, cell)| present(cell))
+                .filter(|(_, cell)| {
+                    !values.iter().any(|value| {
+                        compare_cells(cell, value).is_some_and(|ordering| ordering.is_eq())
+                    })
+                })
+                .map(|(index, _)| index)
+                .collect()
+        }
+        Check::RowCount { .. } | Check::Expression { .. } => Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::{Check, run_check};
+    use crate::core::{Document, Origin, SourceFormat, SourceMeta, TopLevelShape, Value};
+    use crate::transform::Literal;
+
+    fn sales() -> Document {
+        let row = |id: i64, region: &str, amount: Value| {
+            Value::Object(IndexMap::from([
+                ("id".to_string(), Value::Integer(id)),
+                ("region".to_string(), Value::String(region.to_string())),
+                ("amount".to_string(), amount),
+            ]))
+        };
+        Document {
+            source_id: "sales".to_string(),
+            root: Value::List(vec![
+                row(1, "East", Value::Integer(120)),
+                row(2, "West", Value::Null),
+                row(2, "north", Value::Float(-4.5)),
+                row(3, "East", Value::Integer(80)),
+            ]),
+            meta: SourceMeta {
+                format: SourceFormat::Json,
+                origin: Origin::Stdin,
+                top_level_shape: TopLevelShape::List,
+                tabular_columns: None,
+                header_present: None,
+                columns: Vec::new(),
+            },
+        }
+    }
+
+    fn failing_rows(check: Check, column: Option<&str>) -> Vec<usize> {
+        run_check(&sales(), &check, column)
+            .unwrap()
+            .map(|failure| failure.rows.into_iter().map(|(index, _)| index).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn column_checks_report_failing_rows() {
+        assert_eq!(failing_rows(Check::NotNull, Some("amount")), vec![1]);
+        assert_eq!(failing_rows(Check::Unique, Some("id")), vec![1, 2]);
+        assert_eq!(
+            failing_rows(
+                Check::Range {
+                    min: Some(Literal::Integer(0)),
+                    max: Some(Literal::Float(100.0)),
+                },
+                Some("amount")
+            ),
+            vec![0, 2]
+        );
+        assert_eq!(
+            failing_rows(
+                Check::Matches {
+                    pattern: "^[A-Z]".to_string()
+                },
+                Some("region")
+            ),
+            vec![2]
+        );
+        assert_eq!(
+            failing_rows(
+                Check::AllowedValues {
+                    values: vec![
+                        Literal::String("East".to_string()),
+                        Literal::String("West".to_string())
+                    ]
+                },
+                Some("region")
+            ),
+            vec![2]
+        );
+        assert_eq!(
+            failing_rows(
+                Check::Expression {
+                    expr: "amount > 0 or region == 'West'".to_string()
+                },
+                None
+            ),
+            vec![2]
+        );
+    }
+
+    #[test]
+    fn row_counts_and_reports_render_as_tables() {
+        let failure = run_check(
+            &sales(),
+            &Check::RowCount {
+                min: Some(5),
+                max: None,
+            },
+            None,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(failure.detail, "has 4 row(s), expected at least 5");
+        assert_eq!(failure.table(), "");
+
+        let failure = run_check(&sales(), &Check::Unique, Some("id"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(failure.detail, "2 of 4 row(s) fail");
+        assert_eq!(
+            failure.table(),
+            "row | id | region | amount\n----+----+--------+-------\n1   | 2  | West   |\n2   | 2  | north  | -4.5\n"
+        );
+        assert!(
+            run_check(&sales(), &Check::NotNull, Some("total"))
+                .unwrap_err()
+                .starts_with("unknown column 'total'")
+        );
+    }
+
+    #[test]
+    fn validates_column_use_and_settings() {
+        assert_eq!(Check::NotNull.validate(None).unwrap_err(), "needs a column");
+        assert_eq!(
+            Check::RowCount {
+                min: Some(1),
+                max: None
+            }
+            .validate(Some("id"))
+            .unwrap_err(),
+            "does not take a column"
+        );
+        assert!(
+            Check::Matches {
+                pattern: "(".to_string()
+            }
+            .validate(Some("region"))
+            .unwrap_err()
+            .starts_with("has an invalid pattern")
+        );
+        let range = |min: Literal, max: Literal| {
+            Check::Range {
+                min: Some(min),
+                max: Some(max),
+            }
+            .validate(Some("amount"))
+        };
+        assert_eq!(
+            range(Literal::Integer(10), Literal::Float(2.5)).unwrap_err(),
+            "has min 10 above max 2.5"
+        );
+        assert_eq!(
+            range(Literal::Integer(1), Literal::String("z".to_string())).unwrap_err(),
+            "has min 1 and max z that cannot be compared"
+        );
+        assert_eq!(range(Literal::Integer(2), Literal::Float(2.0)), Ok(()));
+    }
+}
@@ -0,0 +1,288 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+use crate::core::Value;
+use crate::input::normalize_decimal;
+
+/// Compares two cells when they hold comparable kinds.
+///
+/// Numbers compare exactly across `Integer`, `Float`, and `Decimal`, and NaN
+/// is incomparable; temporal values compare with each other or with ISO 8601
+/// strings through their ISO text. Any other pairing of different kinds is
+/// incomparable.
+pub(crate) fn compare_cells(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left.as_typed(), right.as_typed()) {
+        (Value::Null, Value::Null) => Some(Ordering::Equal),
+        (Value::Bool(left), Value::Bool(right)) => Some(left.cmp(right)),
+        (Value::Integer(left), Value::Integer(right)) => Some(left.cmp(right)),
+        (Value::String(left), Value::String(right)) => Some(left.cmp(right)),
+        (left, right) if is_temporal(left) || is_temporal(right) => {
+            let text = |value: &Value| match value {
+                Value::Date(_) | Value::DateTime(_) | Value::Time(_) | Value::String(_) => {
+                    value.scalar_text()
+                }
+                _ => None,
+            };
+            Some(text(left)?.cmp(&text(right)?))
+        }
+        (left, right) => match (Number::of(left)?, Number::of(right)?) {
+            (Number::NaN, _) | (_, Number::NaN) => None,
+            (left, right) => Some(left.cmp(&right)),
+        },
+    }
+}
+
+/// Orders cells for sorting: comparable values by [`compare_cells`], then by
+/// kind (booleans, numbers, temporal values, strings, nested values), with
+/// nulls always last. NaN sorts after every other number.
+pub(crate) fn sort_order(left: &Value, right: &Value) -> Ordering {
+    if let (Some(left), Some(right)) = (Number::of(left), Number::of(right)) {
+        return left.cmp(&right);
+    }
+    compare_cells(left, right).unwrap_or_else(|| rank(left).cmp(&rank(right)))
+}
+
+/// A number cell in exact form, so `Integer(1)`, `Float(1.0)`, and
+/// `Decimal("1.0")` are the same number.
+///
+/// Floats are read from their shortest round-trip digits. The derived order
+/// puts NaN after positive infinity, which makes it total.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Number {
+    NegativeInfinity,
+    Finite(Finite),
+    PositiveInfinity,
+    NaN,
+}
+
+/// A finite number as `[-]integer[.fraction]` digits without redundant
+/// zeros; zero is never negative.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Finite {
+    negative: bool,
+    integer: String,
+    fraction: String,
+}
+
+impl Number {
+    pub(crate) fn of(value: &Value) -> Option<Self> {
+        match value.as_typed() {
+            Value::Integer(value) => Finite::parse(&value.to_string()).map(Self::Finite),
+            Value::Float(value) if value.is_nan() => Some(Self::NaN),
+            Value::Float(value) if value.is_infinite() => Some(if value.is_sign_negative() {
+                Self::NegativeInfinity
+            } else {
+                Self::PositiveInfinity
+            }),
+            Value::Float(value) => Finite::parse(&value.to_string()).map(Self::Finite),
+            Value::Decimal(value) => Finite::parse(value).map(Self::Finite),
+            _ => None,
+        }
+    }
+}
+
+impl Finite {
+    fn parse(text: &str) -> Option<Self> {
+        let normalized = normalize_decimal(text)?;
+        let (negative, unsigned) = match normalized.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, normalized.as_str()),
+        };
+        let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        let fraction = fraction.trim_end_matches('0');
+        Some(Self {
+            negative: negative && (integer != "0" || !fraction.is_empty()),
+            integer: integer.to_string(),
+            fraction: fraction.to_string(),
+        })
+    }
+
+    fn magnitude_cmp(&self, other: &Self) -> Ordering {
+        // Integer parts have no leading zeros, so a longer one is larger, and
+        // fractions without trailing zeros order like their digit strings.
+        self.integer
+            .len()
+            .cmp(&other.integer.len())
+            .then_with(|| self.integer.cmp(&other.integer))
+            .then_with(|| self.fraction.cmp(&other.fraction))
+    }
+}
+
+impl Ord for Finite {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude_cmp(other),
+            (true, true) => other.magnitude_cmp(self),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+        }
+    }
+}
+
+impl Display for Finite {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", self.integer)?;
+        if !self.fraction.is_empty() {
+            write!(f, ".{}", self.fraction)?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialOrd for Finite {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Returns a key under which equal cells match when joining, merging,
+/// grouping, or deduplicating.
+///
+/// Numbers are keyed by their exact value, so `Integer(1)`, `Float(1.0)`, and
+/// `Decimal("1.0")` share a key; every other kind keeps its kind and content,
+/// so `1` and `"1"` stay distinct.
+pub(crate) fn cell_key(value: &Value) -> String {
+    if let Some(number) = Number::of(value) {
+        return match number {
+            Number::NegativeInfinity => "Number(-inf)".to_string(),
+            Number::Finite(finite) => format!("Number({finite})"),
+            Number::PositiveInfinity => "Number(inf)".to_string(),
+            Number::NaN => "Number(NaN)".to_string(),
+        };
+    }
+    match value.as_typed() {
+        Value::List(values) => format!(
+            "List[{}]",
+            values.iter().map(cell_key).collect::<Vec<_>>().join(", ")
+        ),
+        Value::Object(values) => format!(
+            "Object{{{}}}",
+            values
+                .iter()
+                .map(|(key, value)| format!("{key:?}: {}", cell_key(value)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        value => format!("{value:?}"),
+    }
+}
+
+/// Returns the numeric value of a number cell.
+pub(crate) fn numeric(value: &Value) -> Option<f64> {
+    match value.as_typed() {
+        Value::Integer(value) => Some(*value as f64),
+        Value::Float(value) => Some(*value),
+        Value::Decimal(value) => value.parse().ok(),
+        _ => None,
+    }
+}
+
+fn is_temporal(value: &Value) -> bool {
+    matches!(value, Value::Date(_) | Value::DateTime(_) | Value::Time(_))
+}
+
+fn rank(value: &Value) -> u8 {
+    match value.as_typed() {
+        Value::Bool(_) => 0,
+        Value::Integer(_) | Value::Float(_) | Value::Decimal(_) => 1,
+        Value::Date(_) | Value::DateTime(_) | Value::Time(_) => 2,
+        Value::String(_) | Value::Lexical { .. } => 3,
+        Value::List(_) | Value::Object(_) => 4,
+        Value::Null => 5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use indexmap::IndexMap;
+
+    use super::{cell_key, compare_cells, sort_order};
+    use crate::core::Value;
+
+    #[test]
+    fn compares_number_kinds_exactly() {
+        let decimal = |text: &str| Value::Decimal(text.to_string());
+        assert_eq!(
+            compare_cells(&Value::Integer(1), &Value::Float(1.0)),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            compare_cells(&decimal("1.0"), &Value::Integer(1)),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            compare_cells(
+                &decimal("9007199254740993"),
+                &Value::Integer(9007199254740992)
+            ),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            compare_cells(&decimal("0.10000000000000000001"), &Value::Float(0.1)),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            compare_cells(&decimal("-2.5"), &decimal("-2.45")),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_cells(&Value::Float(-0.0), &Value::Integer(0)),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            compare_cells(&Value::Float(f64::NAN), &Value::Integer(1)),
+            None
+        );
+    }
+
+    #[test]
+    fn keys_equal_numbers_alike_across_kinds() {
+        let one = cell_key(&Value::Integer(1));
+        assert_eq!(cell_key(&Value::Float(1.0)), one);
+        assert_eq!(cell_key(&Value::Decimal("1.00".to_string())), one);
+        assert_eq!(
+            cell_key(&Value::Lexical {
+                value: Box::new(Value::Float(1.0)),
+                text: "1.0".to_string(),
+            }),
+            one
+        );
+        assert_ne!(cell_key(&Value::String("1".to_string())), one);
+        assert_ne!(cell_key(&Value::Float(1.5)), one);
+        assert_eq!(
+            cell_key(&Value::Object(IndexMap::from([(
+                "id".to_string(),
+                Value::List(vec![Value::Decimal("2.0".to_string())]),
+            )]))),
+            cell_key(&Value::Object(IndexMap::from([(
+                "id".to_string(),
+                Value::List(vec![Value::Integer(2)]),
+            )])))
+        );
+    }
+
+    #[test]
+    fn sorts_nan_after_other_numbers() {
+        let mut cells = [
+            Value::Float(f64::NAN),
+            Value::Integer(3),
+            Value::Null,
+            Value::Float(f64::INFINITY),
+            Value::Decimal("-1.5".to_string()),
+            Value::Float(f64::NAN),
+            Value::Float(2.5),
+            Value::String("a".to_string()),
+        ];
+        cells.sort_by(sort_order);
+        let text = cells
+            .iter()
+            .map(|cell| cell.scalar_text().unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert_eq!(text, vec!["-1.5", "2.5", "3", "inf", "NaN", "NaN", "a", ""]);
+    }
+}
@@ -0,0 +1,270 @@
+use std::cmp::Ordering;
+
+use crate::core::Value;
+use crate::input::normalize_decimal;
+
+/// An exact decimal number: big-endian digits scaled by `10^-scale`.
+pub(crate) struct Exact {
+    negative: bool,
+    digits: Vec<u8>,
+    scale: usize,
+}
+
+impl Exact {
+    /// Reads an integer or decimal cell; other values have no exact form.
+    pub(crate) fn of(value: &Value) -> Option<Self> {
+        match value {
+            Value::Integer(value) => Self::parse(&value.to_string()),
+            Value::Decimal(value) => Self::parse(value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn parse(text: &str) -> Option<Self> {
+        let normalized = normalize_decimal(text)?;
+        let (negative, unsigned) = match normalized.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, normalized.as_str()),
+        };
+        let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        Some(Self {
+            negative,
+            digits: integer
+                .bytes()
+                .chain(fraction.bytes())
+                .map(|byte| byte - b'0')
+                .collect(),
+            scale: fraction.len(),
+        })
+    }
+
+    pub(crate) fn negated(self) -> Self {
+        Self {
+            negative: !self.negative,
+            ..self
+        }
+    }
+
+    pub(crate) fn abs(self) -> Self {
+        Self {
+            negative: false,
+            ..self
+        }
+    }
+
+    pub(crate) fn add(mut self, mut other: Self) -> Self {
+        let scale = self.scale.max(other.scale);
+        self.digits
+            .resize(self.digits.len() + scale - self.scale, 0);
+        other
+            .digits
+            .resize(other.digits.len() + scale - other.scale, 0);
+        let (negative, digits) = if self.negative == other.negative {
+            (self.negative, add_digits(&self.digits, &other.digits))
+        } else if compare_digits(&self.digits, &other.digits).is_ge() {
+            (self.negative, subtract_digits(&self.digits, &other.digits))
+        } else {
+            (other.negative, subtract_digits(&other.digits, &self.digits))
+        };
+        Self {
+            negative,
+            digits: trim_leading_zeros(digits),
+            scale,
+        }
+    }
+
+    pub(crate) fn multiply(&self, other: &Self) -> Self {
+        let mut product = vec![0u32; self.digits.len() + other.digits.len()];
+        for (left_index, left) in self.digits.iter().enumerate() {
+            for (right_index, right) in other.digits.iter().enumerate() {
+                product[left_index + right_index + 1] += u32::from(*left) * u32::from(*right);
+            }
+        }
+        for index in (1..product.len()).rev() {
+            product[index - 1] += product[index] / 10;
+            product[index] %= 10;
+        }
+        Self {
+            negative: self.negative != other.negative,
+            digits: trim_leading_zeros(product.into_iter().map(|digit| digit as u8).collect()),
+            scale: self.scale + other.scale,
+        }
+    }
+
+    /// Divides by a positive integer, keeping at most `extra` more fraction
+    /// digits than the number has. The last digit rounds half away from zero
+    /// and trailing zeros past the original scale are dropped, so
+    /// `0.30 / 2` is `0.15` rather than `0.15000000`.
+    pub(crate) fn divide(mut self, divisor: u64, extra: usize) -> Self {
+        let (scale, target) = (self.scale, self.scale + extra);
+        self.digits.resize(self.digits.len() + extra + 1, 0);
+        let divisor = u128::from(divisor);
+        let mut remainder = 0u128;
+        let mut quotient = Vec::with_capacity(self.digits.len());
+        for digit in &self.digits {
+            let current = remainder * 10 + u128::from(*digit);
+            quotient.push((current / divisor) as u8);
+            remainder = current % divisor;
+        }
+        let mut result = Self {
+            negative: self.negative,
+            digits: quotient,
+            scale: target + 1,
+        }
+        .round(target as i32);
+        while result.scale > scale && result.digits.last() == Some(&0) {
+            result.digits.pop();
+            result.scale -= 1;
+        }
+        result
+    }
+
+    /// Rounds half away from zero to `digits` fraction digits, or to a power
+    /// of ten when `digits` is negative. A number that already has no more
+    /// than `digits` fraction digits is returned unchanged.
+    pub(crate) fn round(self, digits: i32) -> Self {
+        let dropped = self.scale as i64 - i64::from(digits);
+        if dropped <= 0 {
+            return self;
+        }
+        let dropped = dropped as usize;
+        let mut kept = self.digits;
+        if kept.len() <= dropped {
+            kept.splice(0..0, std::iter::repeat_n(0, dropped + 1 - kept.len()));
+        }
+        let round_up = kept[kept.len() - dropped] >= 5;
+        kept.truncate(kept.len() - dropped);
+        if round_up {
+            kept = add_digits(&kept, &[1]);
+        }
+        let scale = match usize::try_from(digits) {
+            Ok(scale) => scale,
+            Err(_) => {
+                kept.resize(kept.len() + digits.unsigned_abs() as usize, 0);
+                0
+            }
+        };
+        Self {
+            negative: self.negative,
+            digits: trim_leading_zeros(kept),
+            scale,
+        }
+    }
+
+    /// Formats the number as a `Decimal`, keeping its scale so `1.50 + 1`
+    /// stays `2.50`.
+    pub(crate) fn into_value(self) -> Value {
+        let mut digits = self
+            .digits
+            .iter()
+            .map(|digit| char::from(b'0' + digit))
+            .collect::<String>();
+        if digits.len() <= self.scale {
+            digits.insert_str(0, &"0".repeat(self.scale + 1 - digits.len()));
+        }
+        let (integer, fraction) = digits.split_at(digits.len() - self.scale);
+        let integer = match integer.trim_start_matches('0') {
+            "" => "0",
+            trimmed => trimmed,
+        };
+        let zero = integer == "0" && fraction.bytes().all(|byte| byte == b'0');
+        let mut text = String::new();
+        if self.negative && !zero {
+            text.push('-');
+        }
+        text.push_str(integer);
+        if !fraction.is_empty() {
+            text.push('.');
+            text.push_str(fraction);
+        }
+        Value::Decimal(text)
+    }
+}
+
+/// Drops leading zeros so repeated sums do not grow the digit vector.
+fn trim_leading_zeros(mut digits: Vec<u8>) -> Vec<u8> {
+    let start = digits
+        .iter()
+        .position(|digit| *digit != 0)
+        .unwrap_or(digits.len());
+    digits.drain(..start);
+    digits
+}
+
+fn compare_digits(left: &[u8], right: &[u8]) -> Ordering {
+    let trim = |digits: &[u8]| {
+        let start = digits
+            .iter()
+            .position(|digit| *digit != 0)
+            .unwrap_or(digits.len());
+        digits[start..].to_vec()
+    };
+    let (left, right) = (trim(left), trim(right));
+    left.len().cmp(&right.len()).then_with(|| left.cmp(&right))
+}
+
+fn add_digits(left: &[u8], right: &[u8]) -> Vec<u8> {
+    let mut sum = Vec::with_capacity(left.len().max(right.len()) + 1);
+    let mut carry = 0;
+    let (mut left, mut right) = (left.iter().rev(), right.iter().rev());
+    loop {
+        let (a, b) = (left.next(), right.next());
+        if a.is_none() && b.is_none() {
+            break;
+        }
+        let digit = a.copied().unwrap_or(0) + b.copied().unwrap_or(0) + carry;
+        sum.push(digit % 10);
+        carry = digit / 10;
+    }
+    sum.push(carry);
+    sum.reverse();
+    sum
+}
+
+/// Subtracts `right` from `left`, which must be at least as large.
+fn subtract_digits(left: &[u8], right: &[u8]) -> Vec<u8> {
+    let mut difference = Vec::with_capacity(left.len());
+    let mut borrow = 0;
+    let mut right = right.iter().rev();
+    for digit in left.iter().rev() {
+        let subtrahend = right.next().copied().unwrap_or(0) + borrow;
+        if *digit >= subtrahend {
+            difference.push(digit - subtrahend);
+            borrow = 0;
+        } else {
+            difference.push(digit + 10 - subtrahend);
+            borrow = 1;
+        }
+    }
+    difference.reverse();
+    difference
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Exact;
+    use crate::core::Value;
+
+    fn exact(text: &str) -> Exact {
+        Exact::parse(text).unwrap()
+    }
+
+    fn decimal(text: &str) -> Value {
+        Value::Decimal(text.to_string())
+    }
+
+    #[test]
+    fn divides_and_rounds_half_away_from_zero() {
+        assert_eq!(
+            exact("1.00").divide(3, 6).into_value(),
+            decimal("0.33333333")
+        );
+        assert_eq!(exact("-2").divide(3, 2).into_value(), decimal("-0.67"));
+        assert_eq!(exact("0.30").divide(2, 6).into_value(), decimal("0.15"));
+        assert_eq!(exact("2.675").round(2).into_value(), decimal("2.68"));
+        assert_eq!(exact("-0.5").round(0).into_value(), decimal("-1"));
+        assert_eq!(exact("0.04").round(1).into_value(), decimal("0.0"));
+        assert_eq!(exact("1250.5").round(-2).into_value(), decimal("1300"));
+        assert_eq!(exact("1.5").round(3).into_value(), decimal("1.5"));
+    }
+}
@@ -0,0 +1,818 @@
+use std::fmt::{Display, Formatter};
+
+use crate::core::{ColumnKind, Value};
+use crate::transform::compare::{compare_cells, numeric};
+use crate::transform::decimal::Exact;
+
+/// Parsed `derive` expression over the cells of one record row.
+///
+/// The grammar covers number, string, `true`/`false`/`null` literals, column
+/// references (bare identifiers or `` `quoted names` ``), arithmetic
+/// (`+ - * / %`, with `+` also joining two strings), comparisons, `and`/`or`/
+/// `not`, null coalescing (`a ?? b`), and a fixed set of functions: `if`,
+/// `coalesce`, `round`, `abs`, `min`, `max`, `concat`, `lower`, `upper`, and
+/// `trim`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    root: Node,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Literal(Value),
+    Column(String),
+    Negate(Box<Node>),
+    Not(Box<Node>),
+    Binary(BinaryOp, Box<Node>, Box<Node>),
+    Call(Function, Vec<Node>),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+    Coalesce,
+}
+
+impl BinaryOp {
+    fn binding_power(self) -> (u8, u8) {
+        match self {
+            Self::Coalesce => (1, 2),
+            Self::Or => (3, 4),
+            Self::And => (5, 6),
+            Self::Eq | Self::Ne | Self::Lt | Self::Le | Self::Gt | Self::Ge => (7, 8),
+            Self::Add | Self::Subtract => (9, 10),
+            Self::Multiply | Self::Divide | Self::Remainder => (11, 12),
+        }
+    }
+}
+
+impl Display for BinaryOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Add => "+",
+            Self::Subtract => "-",
+            Self::Multiply => "*",
+            Self::Divide => "/",
+            Self::Remainder => "%",
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::And => "and",
+            Self::Or => "or",
+            Self::Coalesce => "??",
+        };
+        write!(f, "{value}")
+    }
+}
+
+const NOT_BINDING_POWER: u8 = 7;
+const NEGATE_BINDING_POWER: u8 = 13;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Function {
+    If,
+    Coalesce,
+    Round,
+    Abs,
+    Min,
+    Max,
+    Concat,
+    Lower,
+    Upper,
+    Trim,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "if" => Some(Self::If),
+            "coalesce" => Some(Self::Coalesce),
+            "round" => Some(Self::Round),
+            "abs" => Some(Self::Abs),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            "concat" => Some(Self::Concat),
+            "lower" => Some(Self::Lower),
+            "upper" => Some(Self::Upper),
+            "trim" => Some(Self::Trim),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::If => "if",
+            Self::Coalesce => "coalesce",
+            Self::Round => "round",
+            Self::Abs => "abs",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Concat => "concat",
+            Self::Lower => "lower",
+            Self::Upper => "upper",
+            Self::Trim => "trim",
+        }
+    }
+
+    /// Accepted argument counts as an inclusive range; `None` is unbounded.
+    fn arity(self) -> (usize, Option<usize>) {
+        match self {
+            Self::If => (3, Some(3)),
+            Self::Round => (1, Some(2)),
+            Self::Abs | Self::Lower | Self::Upper | Self::Trim => (1, Some(1)),
+            Self::Coalesce | Self::Min | Self::Max | Self::Concat => (1, None),
+        }
+    }
+}
+
+impl Expression {
+    /// Parses an expression, reporting the 1-based character column of the
+    /// first syntax error.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let tokens = tokenize(text)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            end: text.chars().count() + 1,
+        };
+        let root = parser.expression(0)?;
+        if let Some((token, column)) = parser.tokens.get(parser.position) {
+            return Err(format!("unexpected {token} at column {column}"));
+        }
+        Ok(Self { root })
+    }
+
+    /// Returns every column the expression reads, in first-use order.
+    pub fn columns(&self) -> Vec<&str> {
+        let mut columns = Vec::new();
+        collect_columns(&self.root, &mut columns);
+        columns
+    }
+
+    /// Evaluates the expression against one row.
+    pub(crate) fn evaluate<'a>(&self, cell: &impl Fn(&str) -> &'a Value) -> Result<Value, String> {
+        evaluate(&self.root, cell)
+    }
+}
+
+fn collect_columns<'a>(node: &'a Node, columns: &mut Vec<&'a str>) {
+    match node {
+        Node::Literal(_) => {}
+        Node::Column(name) => {
+            if !columns.contains(&name.as_str()) {
+                columns.push(name);
+            }
+        }
+        Node::Negate(node) | Node::Not(node) => collect_columns(node, columns),
+        Node::Binary(_, left, right) => {
+            collect_columns(left, columns);
+            collect_columns(right, columns);
+        }
+        Node::Call(_, arguments) => {
+            for argument in arguments {
+                collect_columns(argument, columns);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(Value),
+    String(String),
+    Identifier(String),
+    Column(String),
+    Operator(&'static str),
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(value) => write!(f, "number {}", value.scalar_text().unwrap_or_default()),
+            Self::String(value) => write!(f, "string {value:?}"),
+            Self::Identifier(name) => write!(f, "'{name}'"),
+            Self::Column(name) => write!(f, "`{name}`"),
+            Self::Operator(operator) => write!(f, "'{operator}'"),
+        }
+    }
+}
+
+const OPERATORS: [&str; 18] = [
+    "??", "==", "!=", "<=", ">=", "&&", "||", "+", "-", "*", "/", "%", "<", ">", "!", "(", ")", ",",
+];
+
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>, String> {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let start = index;
+        let column = start + 1;
+        let ch = chars[index];
+        if ch.is_whitespace() {
+            index += 1;
+            continue;
+        }
+        let token = if ch.is_ascii_digit()
+            || (ch == '.' && chars.get(index + 1).is_some_and(char::is_ascii_digit))
+        {
+            while index < chars.len() && (chars[index].is_ascii_digit() || chars[index] == '.') {
+                index += 1;
+            }
+            if index < chars.len() && matches!(chars[index], 'e' | 'E') {
+                index += 1;
+                if index < chars.len() && matches!(chars[index], '+' | '-') {
+                    index += 1;
+                }
+                while index < chars.len() && chars[index].is_ascii_digit() {
+                    index += 1;
+                }
+            }
+            let literal = chars[start..index].iter().collect::<String>();
+            let value = match literal.parse::<i64>() {
+                Ok(value) => Value::Integer(value),
+                Err(_) => Value::Lexical {
+                    value: Box::new(Value::Float(literal.parse().map_err(|_| {
+                        format!("invalid number '{literal}' at column {column}")
+                    })?)),
+                    text: literal,
+                },
+            };
+            Token::Number(value)
+        } else if ch == '"' || ch == '\'' {
+            index += 1;
+            let mut value = String::new();
+            loop {
+                match chars.get(index) {
+                    None => return Err(format!("unterminated string at column {column}")),
+                    Some(&quote) if quote == ch => break,
+                    Some('\\') => {
+                        let escaped = match chars.get(index + 1) {
+                            Some('n') => '\n',
+                            Some('t') => '\t',
+                            Some(&other @ ('\\' | '"' | '\'')) => other,
+                            _ => {
+                                return Err(format!(
+                                    "invalid escape in string at column {}",
+                                    index + 1
+                                ));
+                            }
+                        };
+                        value.push(escaped);
+                        index += 2;
+                    }
+                    Some(&other) => {
+                        value.push(other);
+                        index += 1;
+                    }
+                }
+            }
+            index += 1;
+            Token::String(value)
+        } else if ch == '`' {
+            let Some(length) = chars[index + 1..].iter().position(|&ch| ch == '`') else {
+                return Err(format!("unterminated column name at column {column}"));
+            };
+            index += length + 2;
+            Token::Column(chars[start + 1..index - 1].iter().collect())
+        } else if ch.is_alphabetic() || ch == '_' {
+            while index < chars.len() && (chars[index].is_alphanumeric() || chars[index] == '_') {
+                index += 1;
+            }
+            Token::Identifier(chars[start..index].iter().collect())
+        } else {
+            let rest = chars[index..].iter().take(2).collect::<String>();
+            let Some(operator) = OPERATORS
+                .iter()
+                .find(|operator| rest.starts_with(**operator))
+            else {
+                return Err(format!("unexpected character '{ch}' at column {column}"));
+            };
+            index += operator.len();
+            Token::Operator(operator)
+        };
+        tokens.push((token, column));
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<(Token, usize)> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn expect(&mut self, operator: &str) -> Result<(), String> {
+        match self.next() {
+            Some((Token::Operator(found), _)) if found == operator => Ok(()),
+            Some((token, column)) => Err(format!(
+                "expected '{operator}' at column {column}, found {token}"
+            )),
+            None => Err(format!(
+                "expected '{operator}' at column {}, found end of expression",
+                self.end
+            )),
+        }
+    }
+
+    fn expression(&mut self, min_binding_power: u8) -> Result<Node, String> {
+        let mut left = self.prefix()?;
+        while let Some(operator) = self.peek().and_then(binary_operator) {
+            let (left_power, right_power) = operator.binding_power();
+            if left_power < min_binding_power {
+                break;
+            }
+            self.position += 1;
+            let right = self.expression(right_power)?;
+            left = Node::Binary(operator, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn prefix(&mut self) -> Result<Node, String> {
+        let Some((token, column)) = self.next() else {
+            return Err(format!(
+                "expected a value at column {}, found end of expression",
+                self.end
+            ));
+        };
+        match token {
+            Token::Number(value) => Ok(Node::Literal(value)),
+            Token::String(value) => Ok(Node::Literal(Value::String(value))),
+            Token::Column(name) => Ok(Node::Column(name)),
+            Token::Operator("(") => {
+                let node = self.expression(0)?;
+                self.expect(")")?;
+                Ok(node)
+            }
+            Token::Operator("-") => Ok(Node::Negate(Box::new(
+                self.expression(NEGATE_BINDING_POWER)?,
+            ))),
+            Token::Operator("!") => Ok(Node::Not(Box::new(self.expression(NOT_BINDING_POWER)?))),
+            Token::Identifier(name) => match name.as_str() {
+                "true" => Ok(Node::Literal(Value::Bool(true))),
+                "false" => Ok(Node::Literal(Value::Bool(false))),
+                "null" => Ok(Node::Literal(Value::Null)),
+                "not" => Ok(Node::Not(Box::new(self.expression(NOT_BINDING_POWER)?))),
+                _ if self.peek() == Some(&Token::Operator("(")) => self.call(&name, column),
+                _ => Ok(Node::Column(name)),
+            },
+            token => Err(format!("unexpected {token} at column {column}")),
+        }
+    }
+
+    fn call(&mut self, name: &str, column: usize) -> Result<Node, String> {
+        let function = Function::from_name(name)
+            .ok_or_else(|| format!("unknown function '{name}' at column {column}"))?;
+        self.expect("(")?;
+        let mut arguments = Vec::new();
+        if self.peek() != Some(&Token::Operator(")")) {
+            loop {
+                arguments.push(self.expression(0)?);
+                if self.peek() == Some(&Token::Operator(",")) {
+                    self.position += 1;
+                } else {
+                    break;
+                }
+            }
+        }
+        self.expect(")")?;
+        let (min, max) = function.arity();
+        if arguments.len() < min || max.is_some_and(|max| arguments.len() > max) {
+            let expected = match max {
+                Some(max) if max == min => format!("{min}"),
+                Some(max) => format!("{min} to {max}"),
+                None => format!("at least {min}"),
+            };
+            return Err(format!(
+                "{name}() at column {column} takes {expected} argument(s), got {}",
+                arguments.len()
+            ));
+        }
+        Ok(Node::Call(function, arguments))
+    }
+}
+
+fn binary_operator(token: &Token) -> Option<BinaryOp> {
+    match token {
+        Token::Operator(operator) => match *operator {
+            "+" => Some(BinaryOp::Add),
+            "-" => Some(BinaryOp::Subtract),
+            "*" => Some(BinaryOp::Multiply),
+            "/" => Some(BinaryOp::Divide),
+            "%" => Some(BinaryOp::Remainder),
+            "==" => Some(BinaryOp::Eq),
+            "!=" => Some(BinaryOp::Ne),
+            "<" => Some(BinaryOp::Lt),
+            "<=" => Some(BinaryOp::Le),
+            ">" => Some(BinaryOp::Gt),
+            ">=" => Some(BinaryOp::Ge),
+            "&&" => Some(BinaryOp::And),
+            "||" => Some(BinaryOp::Or),
+            "??" => Some(BinaryOp::Coalesce),
+            _ => None,
+        },
+        Token::Identifier(name) if name == "and" => Some(BinaryOp::And),
+        Token::Identifier(name) if name == "or" => Some(BinaryOp::Or),
+        _ => None,
+    }
+}
+
+fn evaluate<'a>(node: &Node, cell: &impl Fn(&str) -> &'a Value) -> Result<Value, String> {
+    match node {
+        Node::Literal(value) => Ok(value.as_typed().clone()),
+        Node::Column(name) => Ok(cell(name).as_typed().clone()),
+        Node::Negate(node) => match evaluate(node, cell)? {
+            Value::Null => Ok(Value::Null),
+            Value::Integer(value) => value
+                .checked_neg()
+                .map(Value::Integer)
+                .ok_or_else(|| "integer overflow in '-'".to_string()),
+            Value::Decimal(value) => Ok(Exact::parse(&value)
+                .map(|exact| exact.negated().into_value())
+                .unwrap_or(Value::Decimal(value))),
+            value => match numeric(&value) {
+                Some(number) => Ok(Value::Float(-number)),
+                None => Err(format!("cannot negate {}", kind(&value))),
+            },
+        },
+        Node::Not(node) => match evaluate(node, cell)? {
+            Value::Null => Ok(Value::Null),
+            value => Ok(Value::Bool(!truthy(&value, "not")?)),
+        },
+        Node::Binary(BinaryOp::Coalesce, left, right) => match evaluate(left, cell)? {
+            Value::Null => evaluate(right, cell),
+            value => Ok(value),
+        },
+        Node::Binary(operator @ (BinaryOp::And | BinaryOp::Or), left, right) => {
+            let left = truthy(&evaluate(left, cell)?, &operator.to_string())?;
+            if left == (*operator == BinaryOp::Or) {
+                return Ok(Value::Bool(left));
+            }
+            Ok(Value::Bool(truthy(
+                &evaluate(right, cell)?,
+                &operator.to_string(),
+            )?))
+        }
+        Node::Binary(operator, left, right) => {
+            let (mut left_value, mut right_value) = (evaluate(left, cell)?, evaluate(right, cell)?);
+            if let Some(exact) = decimal_literal(left, &right_value) {
+                left_value = exact.into_value();
+            } else if let Some(exact) = decimal_literal(right, &left_value) {
+                right_value = exact.into_value();
+            }
+            binary(*operator, left_value, right_value)
+        }
+        Node::Call(function, arguments) => call(*function, arguments, cell),
+    }
+}
+
+/// Reads a fractional literal from its source text when the other operand
+/// is a decimal, so `price + 0.1` stays exact instead of going through `f64`.
+fn decimal_literal(node: &Node, other: &Value) -> Option<Exact> {
+    if !matches!(other, Value::Decimal(_)) {
+        return None;
+    }
+    match node {
+        Node::Literal(Value::Lexical { text, .. }) => Exact::parse(text),
+        Node::Negate(node) => decimal_literal(node, other).map(Exact::negated),
+        _ => None,
+    }
+}
+
+fn binary(operator: BinaryOp, left: Value, right: Value) -> Result<Value, String> {
+    if matches!(left, Value::Null) || matches!(right, Value::Null) {
+        return Ok(Value::Null);
+    }
+    let mismatch = || {
+        format!(
+            "cannot apply '{operator}' to {} and {}",
+            kind(&left),
+            kind(&right)
+        )
+    };
+    match operator {
+        BinaryOp::Eq | BinaryOp::Ne | BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
+            let ordering = compare_cells(&left, &right).ok_or_else(mismatch)?;
+            Ok(Value::Bool(match operator {
+                BinaryOp::Eq => ordering.is_eq(),
+                BinaryOp::Ne => ordering.is_ne(),
+                BinaryOp::Lt => ordering.is_lt(),
+                BinaryOp::Le => ordering.is_le(),
+                BinaryOp::Gt => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            }))
+        }
+        BinaryOp::Add => match (&left, &right) {
+            (Value::String(left), Value::String(right)) => {
+                Ok(Value::String(format!("{left}{right}")))
+            }
+            _ => arithmetic(operator, &left, &right).ok_or_else(mismatch)?,
+        },
+        _ => arithmetic(operator, &left, &right).ok_or_else(mismatch)?,
+    }
+}
+
+/// Applies an arithmetic operator, returning `None` for non-numeric operands.
+///
+/// `+`, `-`, and `*` are exact when each operand is an integer or a decimal
+/// and at least one is a decimal; `decimal_literal` has already turned a
+/// fractional literal next to a decimal into one. Division and remainder
+/// with a decimal operand, like any float operand, go through `f64` and yield
+/// a float, since a quotient such as `1 / 3` has no finite decimal form.
+fn arithmetic(operator: BinaryOp, left: &Value, right: &Value) -> Option<Result<Value, String>> {
+    if matches!(
+        operator,
+        BinaryOp::Add | BinaryOp::Subtract | BinaryOp::Multiply
+    ) && (matches!(left, Value::Decimal(_)) || matches!(right, Value::Decimal(_)))
+        && let (Some(left), Some(right)) = (Exact::of(left), Exact::of(right))
+    {
+        let result = match operator {
+            BinaryOp::Add => left.add(right),
+            BinaryOp::Subtract => left.add(right.negated()),
+            _ => left.multiply(&right),
+        };
+        return Some(Ok(result.into_value()));
+    }
+    if let (Value::Integer(left), Value::Integer(right)) = (left, right)
+        && operator != BinaryOp::Divide
+    {
+        let result = match operator {
+            BinaryOp::Add => left.checked_add(*right),
+            BinaryOp::Subtract => left.checked_sub(*right),
+            BinaryOp::Multiply => left.checked_mul(*right),
+            _ if *right == 0 => return Some(Err("division by zero".to_string())),
+            _ => left.checked_rem(*right),
+        };
+        return Some(
+            result
+                .map(Value::Integer)
+                .ok_or_else(|| format!("integer overflow in '{operator}'")),
+        );
+    }
+    let (left, right) = (numeric(left)?, numeric(right)?);
+    let result = match operator {
+        BinaryOp::Add => left + right,
+        BinaryOp::Subtract => left - right,
+        BinaryOp::Multiply => left * right,
+        BinaryOp::Divide | BinaryOp::Remainder if right == 0.0 => {
+            return Some(Err("division by zero".to_string()));
+        }
+        BinaryOp::Divide => left / right,
+        _ => left % right,
+    };
+    Some(Ok(Value::Float(result)))
+}
+
+fn call<'a>(
+    function: Function,
+    arguments: &[Node],
+    cell: &impl Fn(&str) -> &'a Value,
+) -> Result<Value, String> {
+    match function {
+        Function::If => {
+            let condition = evaluate(&arguments[0], cell)?;
+            if truthy(&condition, "if()")? {
+                evaluate(&arguments[1], cell)
+            } else {
+                evaluate(&arguments[2], cell)
+            }
+        }
+        Function::Coalesce => {
+            for argument in arguments {
+                let value = evaluate(argument, cell)?;
+                if !matches!(value, Value::Null) {
+                    return Ok(value);
+                }
+            }
+            Ok(Value::Null)
+        }
+        _ => {
+            let values = arguments
+                .iter()
+                .map(|argument| evaluate(argument, cell))
+                .collect::<Result<Vec<_>, _>>()?;
+            call_eager(function, values)
+        }
+    }
+}
+
+fn call_eager(function: Function, values: Vec<Value>) -> Result<Value, String> {
+    let name = function.name();
+    if function == Function::Concat {
+        let mut output = String::new();
+        for value in &values {
+            output.push_str(
+                &value
+                    .scalar_text()
+                    .ok_or_else(|| format!("{name}() cannot join a {} value", kind(value)))?,
+            );
+        }
+        return Ok(Value::String(output));
+    }
+    if values.iter().any(|value| matches!(value, Value::Null)) {
+        return Ok(Value::Null);
+    }
+    let number = |value: &Value| {
+        numeric(value).ok_or_else(|| format!("{name}() expects a number, got {}", kind(value)))
+    };
+    let text = |value: &Value| match value {
+        Value::String(text) => Ok(text.clone()),
+        other => Err(format!("{name}() expects a string, got {}", kind(other))),
+    };
+    match function {
+        Function::Round => {
+            let digits = match values.get(1) {
+                Some(Value::Integer(digits)) if (-15..=15).contains(digits) => *digits as i32,
+                Some(other) => {
+                    return Err(format!(
+                        "{name}() expects an integer digit count between -15 and 15, got {}",
+                        other.scalar_text().unwrap_or_else(|| kind(other))
+                    ));
+                }
+                None => 0,
+            };
+            match &values[0] {
+                Value::Integer(value) if digits >= 0 => Ok(Value::Integer(*value)),
+                value @ Value::Decimal(text) => Ok(Exact::parse(text)
+                    .map(|exact| exact.round(digits).into_value())
+                    .unwrap_or_else(|| value.clone())),
+                value => {
+                    let scale = 10f64.powi(digits);
+                    Ok(Value::Float((number(value)? * scale).round() / scale))
+                }
+            }
+        }
+        Function::Abs => match &values[0] {
+            Value::Integer(value) => value
+                .checked_abs()
+                .map(Value::Integer)
+                .ok_or_else(|| format!("integer overflow in {name}()")),
+            Value::Decimal(value) => Ok(Exact::parse(value)
+                .map(|exact| exact.abs().into_value())
+                .unwrap_or_else(|| Value::Decimal(value.clone()))),
+            value => Ok(Value::Float(number(value)?.abs())),
+        },
+        Function::Min | Function::Max => {
+            let mut best = values[0].clone();
+            number(&best)?;
+            for value in &values[1..] {
+                number(value)?;
+                let ordering = compare_cells(value, &best).unwrap_or(std::cmp::Ordering::Equal);
+                if (function == Function::Min && ordering.is_lt())
+                    || (function == Function::Max && ordering.is_gt())
+                {
+                    best = value.clone();
+                }
+            }
+            Ok(best)
+        }
+        Function::Lower => Ok(Value::String(text(&values[0])?.to_lowercase())),
+        Function::Upper => Ok(Value::String(text(&values[0])?.to_uppercase())),
+        Function::Trim => Ok(Value::String(text(&values[0])?.trim().to_string())),
+        Function::If | Function::Coalesce | Function::Concat => {
+            unreachable!("handled before eager evaluation")
+        }
+    }
+}
+
+fn truthy(value: &Value, context: &str) -> Result<bool, String> {
+    match value {
+        Value::Bool(value) => Ok(*value),
+        Value::Null => Ok(false),
+        other => Err(format!("{context} expects a bool, got {}", kind(other))),
+    }
+}
+
+fn kind(value: &Value) -> String {
+    ColumnKind::of(value).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Expression;
+    use crate::core::Value;
+
+    fn eval(text: &str) -> Result<Value, String> {
+        let revenue = Value::Integer(200);
+        let cost = Value::Integer(150);
+        let name = Value::String("Alice".to_string());
+        let null = Value::Null;
+        Expression::parse(text)?.evaluate(&|column| match column {
+            "revenue" => &revenue,
+            "cost" => &cost,
+            "unit name" | "name" => &name,
+            _ => &null,
+        })
+    }
+
+    #[test]
+    fn evaluates_arithmetic_functions_and_precedence() {
+        assert_eq!(eval("(revenue - cost) / revenue"), Ok(Value::Float(0.25)));
+        assert_eq!(eval("revenue - cost * 2"), Ok(Value::Integer(-100)));
+        assert_eq!(eval("round(2 / 3, 2)"), Ok(Value::Float(0.67)));
+        assert_eq!(
+            eval("if(revenue > cost and not false, 'profit', 'loss')"),
+            Ok(Value::String("profit".to_string()))
+        );
+        assert_eq!(
+            eval("`unit name` + \"!\""),
+            Ok(Value::String("Alice!".to_string()))
+        );
+        assert_eq!(
+            eval("concat(name, ': ', revenue)"),
+            Ok(Value::String("Alice: 200".to_string()))
+        );
+        assert_eq!(eval("missing ?? 0"), Ok(Value::Integer(0)));
+        assert_eq!(eval("missing + 1"), Ok(Value::Null));
+        assert_eq!(eval("max(cost, revenue, 3)"), Ok(Value::Integer(200)));
+    }
+
+    #[test]
+    fn keeps_decimal_arithmetic_exact() {
+        let price = Value::Decimal("0.10".to_string());
+        let fee = Value::Decimal("0.2".to_string());
+        let big = Value::Decimal("12345678901234567.89".to_string());
+        let count = Value::Integer(3);
+        let eval = |text: &str| {
+            Expression::parse(text)
+                .unwrap()
+                .evaluate(&|column| match column {
+                    "price" => &price,
+                    "fee" => &fee,
+                    "big" => &big,
+                    _ => &count,
+                })
+        };
+        let decimal = |text: &str| Ok(Value::Decimal(text.to_string()));
+        assert_eq!(eval("price + fee"), decimal("0.30"));
+        assert_eq!(eval("price - fee"), decimal("-0.10"));
+        assert_eq!(eval("price * count"), decimal("0.30"));
+        assert_eq!(eval("big + 1"), decimal("12345678901234568.89"));
+        assert_eq!(eval("big * fee"), decimal("2469135780246913.578"));
+        assert_eq!(eval("fee - fee"), decimal("0.0"));
+        assert_eq!(eval("-price"), decimal("-0.10"));
+        assert_eq!(eval("abs(price - fee)"), decimal("0.10"));
+        assert_eq!(eval("price / count"), Ok(Value::Float(0.1 / 3.0)));
+        assert_eq!(eval("fee + 0.4"), decimal("0.6"));
+        assert_eq!(eval("price - -0.05"), decimal("0.15"));
+        assert_eq!(eval("0.1 * big"), decimal("1234567890123456.789"));
+        assert_eq!(eval("count + 0.5"), Ok(Value::Float(3.5)));
+        assert_eq!(eval("round(price + fee, 1)"), decimal("0.3"));
+        assert_eq!(eval("round(big, -2)"), decimal("12345678901234600"));
+        assert_eq!(eval("round(-0.25 + fee, 1)"), decimal("-0.1"));
+    }
+
+    #[test]
+    fn reports_type_and_syntax_errors() {
+        assert_eq!(
+            eval("name - cost"),
+            Err("cannot apply '-' to string and integer".to_string())
+        );
+        assert_eq!(eval("revenue / 0"), Err("division by zero".to_string()));
+        assert_eq!(
+            Expression::parse("round(revenue,").unwrap_err(),
+            "expected a value at column 15, found end of expression"
+        );
+        assert_eq!(
+            Expression::parse("sqrt(revenue)").unwrap_err(),
+            "unknown function 'sqrt' at column 1"
+        );
+        assert_eq!(
+            Expression::parse("if(revenue, cost)").unwrap_err(),
+            "if() at column 1 takes 3 argument(s), got 2"
+        );
+    }
+
+    #[test]
+    fn lists_referenced_columns() {
+        let expression = Expression::parse("coalesce(a, b) + a * `c d`").unwrap();
+        assert_eq!(expression.columns(), vec!["a", "b", "c d"]);
+    }
+}
@@ -0,0 +1,163 @@
+use std::fmt::{Display, Formatter};
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::core::{ColumnMeta, Value};
+use crate::transform::table::Table;
+
+/// What a `flatten` step does with list cells.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListFlatten {
+    /// Joins a list of scalars into one text cell.
+    #[default]
+    Join,
+    /// Repeats the row once per list item.
+    Explode,
+}
+
+impl Display for ListFlatten {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Join => "join",
+            Self::Explode => "explode",
+        };
+        write!(f, "{value}")
+    }
+}
+
+/// One candidate output row fragment: flattened column names and cells.
+type Fragment = Vec<(String, Value)>;
+
+/// Rewrites nested record cells into `parent.child` columns.
+///
+/// Columns appear in first-seen order. Exploding several lists in one row
+/// yields every combination of their items; an empty list keeps the row with
+/// a null cell.
+pub(crate) fn flatten(
+    table: &Table,
+    separator: &str,
+    lists: ListFlatten,
+    list_separator: &str,
+) -> Result<Table, String> {
+    let flattener = Flattener {
+        separator,
+        lists,
+        list_separator,
+    };
+    let mut names: IndexMap<String, ()> = IndexMap::new();
+    let mut fragments = Vec::new();
+    for (row_index, row) in table.rows.iter().enumerate() {
+        let mut expanded = vec![Fragment::new()];
+        for (column, cell) in table.columns.iter().zip(row) {
+            let alternatives = flattener.expand(&column.name, cell, row_index)?;
+            expanded = product(expanded, &alternatives);
+        }
+        for fragment in expanded {
+            for (position, (name, _)) in fragment.iter().enumerate() {
+                if fragment[..position].iter().any(|(seen, _)| seen == name) {
+                    return Err(format!(
+                        "flattened column '{name}' collides with another column in row {row_index}"
+                    ));
+                }
+                names.entry(name.clone()).or_insert(());
+            }
+            fragments.push(fragment);
+        }
+    }
+
+    let columns = names
+        .keys()
+        .map(|name| {
+            table
+                .columns
+                .iter()
+                .find(|column| &column.name == name)
+                .cloned()
+                .unwrap_or_else(|| ColumnMeta::infer(name.clone(), []))
+        })
+        .collect();
+    let rows = fragments
+        .into_iter()
+        .map(|fragment| {
+            let mut cells = fragment.into_iter().collect::<IndexMap<_, _>>();
+            names
+                .keys()
+                .map(|name| cells.shift_remove(name).unwrap_or(Value::Null))
+                .collect()
+        })
+        .collect();
+    Ok(Table {
+        records: true,
+        columns,
+        rows,
+    })
+}
+
+struct Flattener<'a> {
+    separator: &'a str,
+    lists: ListFlatten,
+    list_separator: &'a str,
+}
+
+impl Flattener<'_> {
+    /// Returns the alternative fragments one cell expands to; only exploded
+    /// lists produce more than one.
+    fn expand(&self, name: &str, value: &Value, row: usize) -> Result<Vec<Fragment>, String> {
+        match value {
+            Value::Object(values) => {
+                let mut expanded = vec![Fragment::new()];
+                for (key, child) in values {
+                    let alternatives =
+                        self.expand(&format!("{name}{}{key}", self.separator), child, row)?;
+                    expanded = product(expanded, &alternatives);
+                }
+                Ok(expanded)
+            }
+            Value::List(items) => match self.lists {
+                ListFlatten::Join => {
+                    let texts = items
+                        .iter()
+                        .map(|item| match item {
+                            Value::List(_) | Value::Object(_) => None,
+                            scalar => scalar.scalar_text(),
+                        })
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(|| {
+                            format!(
+                                "cannot join the list at /{row}/{name} because it holds nested values; use lists = \"explode\""
+                            )
+                        })?;
+                    Ok(vec![vec![(
+                        name.to_string(),
+                        Value::String(texts.join(self.list_separator)),
+                    )]])
+                }
+                ListFlatten::Explode if items.is_empty() => {
+                    Ok(vec![vec![(name.to_string(), Value::Null)]])
+                }
+                ListFlatten::Explode => {
+                    let mut alternatives = Vec::new();
+                    for item in items {
+                        alternatives.extend(self.expand(name, item, row)?);
+                    }
+                    Ok(alternatives)
+                }
+            },
+            scalar => Ok(vec![vec![(name.to_string(), scalar.clone())]]),
+        }
+    }
+}
+
+fn product(left: Vec<Fragment>, right: &[Fragment]) -> Vec<Fragment> {
+    let mut combined = Vec::with_capacity(left.len() * right.len());
+    for prefix in left {
+        for suffix in right {
+            let mut fragment = prefix.clone();
+            fragment.extend(suffix.iter().cloned());
+            combined.push(fragment);
+        }
+    }
+    combined
+}
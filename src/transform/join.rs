@@ -0,0 +1,455 @@
+use std::fmt::{Display, Formatter};
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::core::{ColumnMeta, Document, Value};
+use crate::transform::compare::cell_key;
+use crate::transform::table::Table;
+
+/// Which rows a join keeps when a key has no partner on the other side.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JoinKind {
+    /// Keep only rows whose key matches on both sides.
+    #[default]
+    Inner,
+    /// Keep every left row, with nulls for missing right columns.
+    Left,
+    /// Keep every row from both sides.
+    Full,
+}
+
+impl Display for JoinKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Inner => "inner",
+            Self::Left => "left",
+            Self::Full => "full",
+        };
+        write!(f, "{value}")
+    }
+}
+
+/// Key columns and naming rules for [`join_documents`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JoinOptions<'a> {
+    pub left_on: &'a [String],
+    pub right_on: &'a [String],
+    pub kind: JoinKind,
+    /// Suffixes appended to non-key columns present on both sides.
+    pub suffixes: (&'a str, &'a str),
+}
+
+/// Joined document plus the keys that found no partner.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JoinOutcome {
+    pub doc: Document,
+    /// Keys of left rows without a right match, as display text.
+    pub unmatched_left: Vec<String>,
+    /// Keys of right rows without a left match, as display text.
+    pub unmatched_right: Vec<String>,
+}
+
+/// Joins the record rows of two documents on key columns.
+///
+/// Both sides must be record-shaped: a `TabularRecords` source or a list of
+/// objects. Output rows follow left order, each followed by its right matches
+/// in right order; a full join then appends the unmatched right rows. Key
+/// columns appear once, under their left names. Null keys never match.
+pub fn join_documents(
+    left: &Document,
+    right: &Document,
+    options: &JoinOptions<'_>,
+) -> Result<JoinOutcome, String> {
+    if options.left_on.is_empty() || options.left_on.len() != options.right_on.len() {
+        return Err(format!(
+            "join needs the same non-zero number of left and right key columns (got {} and {})",
+            options.left_on.len(),
+            options.right_on.len()
+        ));
+    }
+    let left_table = Table::from_records(left).map_err(|detail| format!("left side: {detail}"))?;
+    let right_table =
+        Table::from_records(right).map_err(|detail| format!("right side: {detail}"))?;
+    let left_keys = options
+        .left_on
+        .iter()
+        .map(|column| left_table.index_of(column))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|detail| format!("left side: {detail}"))?;
+    let right_keys = options
+        .right_on
+        .iter()
+        .map(|column| right_table.index_of(column))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|detail| format!("right side: {detail}"))?;
+    let right_values = (0..right_table.columns.len())
+        .filter(|index| !right_keys.contains(index))
+        .collect::<Vec<_>>();
+
+    let columns = joined_columns(
+        &left_table,
+        &left_keys,
+        &right_table,
+        &right_values,
+        options,
+    )?;
+
+    let mut right_index: IndexMap<Vec<String>, Vec<usize>> = IndexMap::new();
+    for (row_index, row) in right_table.rows.iter().enumerate() {
+        if let Some(key) = row_key(row, &right_keys) {
+            right_index.entry(key).or_default().push(row_index);
+        }
+    }
+
+    let mut rows = Vec::new();
+    let mut right_matched = vec![false; right_table.rows.len()];
+    let mut unmatched_left = Vec::new();
+    for row in &left_table.rows {
+        let matches = row_key(row, &left_keys)
+            .and_then(|key| right_index.get(&key))
+            .map_or(&[][..], Vec::as_slice);
+        if matches.is_empty() {
+            unmatched_left.push(key_text(row, &left_keys));
+            if options.kind != JoinKind::Inner {
+                let mut joined = row.clone();
+                joined.extend(right_values.iter().map(|_| Value::Null));
+                rows.push(joined);
+            }
+        }
+        for &matched in matches {
+            right_matched[matched] = true;
+            let mut joined = row.clone();
+            joined.extend(
+                right_values
+                    .iter()
+                    .map(|index| right_table.rows[matched][*index].clone()),
+            );
+            rows.push(joined);
+        }
+    }
+
+    let mut unmatched_right = Vec::new();
+    for (row, matched) in right_table.rows.iter().zip(right_matched) {
+        if matched {
+            continue;
+        }
+        unmatched_right.push(key_text(row, &right_keys));
+        if options.kind == JoinKind::Full {
+            let mut joined = vec![Value::Null; left_table.columns.len()];
+            for (left_key, right_key) in left_keys.iter().zip(&right_keys) {
+                joined[*left_key] = row[*right_key].clone();
+            }
+            joined.extend(right_values.iter().map(|index| row[*index].clone()));
+            rows.push(joined);
+        }
+    }
+
+    let table = Table {
+        records: true,
+        columns,
+        rows,
+    };
+    let mut doc = table.into_document(left.clone());
+    doc.meta.header_present = None;
+    Ok(JoinOutcome {
+        doc,
+        unmatched_left,
+        unmatched_right,
+    })
+}
+
+fn joined_columns(
+    left: &Table,
+    left_keys: &[usize],
+    right: &Table,
+    right_values: &[usize],
+    options: &JoinOptions<'_>,
+) -> Result<Vec<ColumnMeta>, String> {
+    let (left_suffix, right_suffix) = options.suffixes;
+    let conflicts = |name: &str| {
+        right_values
+            .iter()
+            .any(|index| right.columns[*index].name == name)
+    };
+    let mut columns: Vec<ColumnMeta> = Vec::new();
+    let mut push = |column: ColumnMeta| {
+        if columns.iter().any(|existing| existing.name == column.name) {
+            return Err(format!(
+                "joined column '{}' would appear twice; choose different suffixes",
+                column.name
+            ));
+        }
+        columns.push(column);
+        Ok(())
+    };
+    for (index, column) in left.columns.iter().enumerate() {
+        let mut column = column.clone();
+        if !left_keys.contains(&index) && conflicts(&column.name) {
+            column.name = format!("{}{left_suffix}", column.name);
+        }
+        push(column)?;
+    }
+    for index in right_values {
+        let mut column = right.columns[*index].clone();
+        if left.index_of(&column.name).is_ok() {
+            column.name = format!("{}{right_suffix}", column.name);
+        }
+        push(column)?;
+    }
+    Ok(columns)
+}
+
+fn row_key(row: &[Value], keys: &[usize]) -> Option<Vec<String>> {
+    keys.iter()
+        .map(|index| match &row[*index] {
+            Value::Null => None,
+            cell => Some(cell_key(cell)),
+        })
+        .collect()
+}
+
+fn key_text(row: &[Value], keys: &[usize]) -> String {
+    keys.iter()
+        .map(|index| row[*index].scalar_text().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::{JoinKind, JoinOptions, join_documents};
+    use crate::core::{
+        Document, Origin, SourceFormat, SourceMeta, TopLevelShape, Value, infer_columns,
+    };
+
+    fn records(id: &str, columns: &[&str], rows: &[&[Value]]) -> Document {
+        let columns = columns
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+        let root = Value::List(
+            rows.iter()
+                .map(|row| Value::Object(columns.iter().cloned().zip(row.to_vec()).collect()))
+                .collect(),
+        );
+        Document {
+            source_id: id.to_string(),
+            meta: SourceMeta {
+                format: SourceFormat::Csv,
+                origin: Origin::Stdin,
+                top_level_shape: TopLevelShape::TabularRecords,
+                columns: infer_columns(&root, Some(&columns)),
+                tabular_columns: Some(columns),
+                header_present: Some(true),
+            },
+            root,
+        }
+    }
+
+    fn text(value: &str) -> Value {
+        Value::String(value.to_string())
+    }
+
+    fn orders() -> Document {
+        records(
+            "orders",
+            &["region", "amount"],
+            &[
+                &[text("east"), Value::Integer(10)],
+                &[text("north"), Value::Integer(20)],
+                &[text("west"), Value::Integer(30)],
+            ],
+        )
+    }
+
+    fn regions() -> Document {
+        records(
+            "regions",
+            &["code", "amount", "manager"],
+            &[
+                &[text("west"), Value::Integer(1), text("Wu")],
+                &[text("east"), Value::Integer(2), text("Eve")],
+                &[text("south"), Value::Integer(3), text("Sam")],
+            ],
+        )
+    }
+
+    fn join(kind: JoinKind) -> super::JoinOutcome {
+        let on = vec!["region".to_string()];
+        let right_on = vec!["code".to_string()];
+        join_documents(
+            &orders(),
+            &regions(),
+            &JoinOptions {
+                left_on: &on,
+                right_on: &right_on,
+                kind,
+                suffixes: ("", "_region"),
+            },
+        )
+        .unwrap()
+    }
+
+    fn row(doc: &Document, index: usize) -> &IndexMap<String, Value> {
+        match &doc.root {
+            Value::List(rows) => match &rows[index] {
+                Value::Object(values) => values,
+                _ => panic!("expected record rows"),
+            },
+            _ => panic!("expected a list root"),
+        }
+    }
+
+    fn row_count(doc: &Document) -> usize {
+        match &doc.root {
+            Value::List(rows) => rows.len(),
+            _ => panic!("expected a list root"),
+        }
+    }
+
+    #[test]
+    fn inner_join_keeps_left_order_and_suffixes_conflicts() {
+        let outcome = join(JoinKind::Inner);
+        assert_eq!(
+            outcome.doc.meta.tabular_columns,
+            Some(
+                ["region", "amount", "amount_region", "manager"]
+                    .map(str::to_string)
+                    .to_vec()
+            )
+        );
+        assert_eq!(row_count(&outcome.doc), 2);
+        assert_eq!(row(&outcome.doc, 0)["manager"], text("Eve"));
+        assert_eq!(row(&outcome.doc, 1)["amount_region"], Value::Integer(1));
+        assert_eq!(outcome.unmatched_left, vec!["north".to_string()]);
+        assert_eq!(outcome.unmatched_right, vec!["south".to_string()]);
+    }
+
+    #[test]
+    fn left_and_full_joins_fill_missing_sides_with_nulls() {
+        let left = join(JoinKind::Left);
+        assert_eq!(row_count(&left.doc), 3);
+        assert_eq!(row(&left.doc, 1)["manager"], Value::Null);
+
+        let full = join(JoinKind::Full);
+        assert_eq!(row_count(&full.doc), 4);
+        let appended = row(&full.doc, 3);
+        assert_eq!(appended["region"], text("south"));
+        assert_eq!(appended["amount"], Value::Null);
+        assert_eq!(appended["manager"], text("Sam"));
+    }
+
+    #[test]
+    fn joins_lists_of_objects_and_rejects_other_shapes() {
+        let mut people = records(
+            "people",
+            &["region", "name"],
+            &[&[text("east"), text("Ann")]],
+        );
+        people.meta.top_level_shape = TopLevelShape::List;
+        people.meta.tabular_columns = None;
+        let on = vec!["region".to_string()];
+        let options = JoinOptions {
+            left_on: &on,
+            right_on: &on,
+            kind: JoinKind::Inner,
+            suffixes: ("", "_right"),
+        };
+        let outcome = join_documents(&orders(), &people, &options).unwrap();
+        assert_eq!(row(&outcome.doc, 0)["name"], text("Ann"));
+
+        people.root = Value::String("nope".to_string());
+        people.meta.top_level_shape = TopLevelShape::Scalar;
+        let error = join_documents(&orders(), &people, &options).unwrap_err();
+        assert!(
+            error.starts_with("right side: expected record rows"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn matches_equal_numbers_across_kinds() {
+        let invoices = records(
+            "invoices",
+            &["id", "total"],
+            &[
+                &[Value::Integer(1), Value::Integer(10)],
+                &[Value::Decimal("2.0".to_string()), Value::Integer(20)],
+                &[text("3"), Value::Integer(30)],
+            ],
+        );
+        let payments = records(
+            "payments",
+            &["id", "paid"],
+            &[
+                &[Value::Float(1.0), text("yes")],
+                &[Value::Integer(2), text("no")],
+                &[Value::Integer(3), text("late")],
+            ],
+        );
+        let on = vec!["id".to_string()];
+        let outcome = join_documents(
+            &invoices,
+            &payments,
+            &JoinOptions {
+                left_on: &on,
+                right_on: &on,
+                kind: JoinKind::Inner,
+                suffixes: ("", "_payment"),
+            },
+        )
+        .unwrap();
+        assert_eq!(row_count(&outcome.doc), 2);
+        assert_eq!(row(&outcome.doc, 0)["paid"], text("yes"));
+        assert_eq!(row(&outcome.doc, 1)["paid"], text("no"));
+        assert_eq!(outcome.unmatched_left, vec!["3".to_string()]);
+    }
+
+    #[test]
+    fn rejects_suffixes_that_collide_with_existing_columns() {
+        let people = |id: &str, columns: &[&str]| {
+            records(
+                id,
+                columns,
+                &[&[text("east"), text("Ann"), text("Al")][..columns.len()]],
+            )
+        };
+        let on = vec!["region".to_string()];
+        let options = JoinOptions {
+            left_on: &on,
+            right_on: &on,
+            kind: JoinKind::Inner,
+            suffixes: ("_left", "_right"),
+        };
+        for (left, right, duplicate) in [
+            (
+                &["region", "name", "name_left"][..],
+                &["region", "name"][..],
+                "name_left",
+            ),
+            (
+                &["region", "name"][..],
+                &["region", "name", "name_left"][..],
+                "name_left",
+            ),
+            (
+                &["region", "name", "name_right"][..],
+                &["region", "name"][..],
+                "name_right",
+            ),
+        ] {
+            let error = join_documents(&people("left", left), &people("right", right), &options)
+                .unwrap_err();
+            assert_eq!(
+                error,
+                format!(
+                    "joined column '{duplicate}' would appear twice; choose different suffixes"
+                )
+            );
+        }
+    }
+}
@@ -0,0 +1,362 @@
+use std::fmt::{Display, Formatter};
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::core::{ColumnKind, Document, SourceMeta, TopLevelShape, Value};
+use crate::error::{DocpackError, DocpackResult};
+use crate::transform::compare::cell_key;
+
+/// How a later layer's list combines with the list it overrides.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListMerge {
+    /// The later list replaces the earlier one.
+    #[default]
+    Replace,
+    /// The later list's items follow the earlier ones.
+    Append,
+    /// Objects sharing a key field are merged; the rest are appended.
+    MergeByKey,
+}
+
+impl Display for ListMerge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Replace => "replace",
+            Self::Append => "append",
+            Self::MergeByKey => "merge_by_key",
+        };
+        write!(f, "{value}")
+    }
+}
+
+/// Deep-merges object-rooted documents in order, later layers winning.
+///
+/// Objects merge key by key, keeping first-seen key order; scalars and nulls
+/// replace whatever they override. An object or list meeting a value of a
+/// different kind at the same path fails with `MergeConflict`. `key` names
+/// the identifying field used by [`ListMerge::MergeByKey`].
+pub fn merge_documents(
+    source_id: &str,
+    layers: &[Document],
+    lists: ListMerge,
+    key: Option<&str>,
+) -> DocpackResult<Document> {
+    let Some(first) = layers.first() else {
+        return Err(derive_error(source_id, "merge needs at least one layer"));
+    };
+    let merger = Merger {
+        source_id,
+        lists,
+        key,
+    };
+    let mut root = Value::Object(IndexMap::new());
+    for layer in layers {
+        if !matches!(layer.root, Value::Object(_)) {
+            return Err(derive_error(
+                source_id,
+                &format!(
+                    "layer '{}' is a {}, but merge layers must be objects",
+                    layer.source_id, layer.meta.top_level_shape
+                ),
+            ));
+        }
+        merger.merge(&mut root, &layer.root, "", &layer.source_id)?;
+    }
+    Ok(Document {
+        source_id: source_id.to_string(),
+        root,
+        meta: SourceMeta {
+            format: first.meta.format,
+            origin: first.meta.origin.clone(),
+            top_level_shape: TopLevelShape::Object,
+            tabular_columns: None,
+            header_present: None,
+            columns: Vec::new(),
+        },
+    })
+}
+
+struct Merger<'a> {
+    source_id: &'a str,
+    lists: ListMerge,
+    key: Option<&'a str>,
+}
+
+impl Merger<'_> {
+    fn merge(
+        &self,
+        base: &mut Value,
+        overlay: &Value,
+        path: &str,
+        layer: &str,
+    ) -> DocpackResult<()> {
+        match (&mut *base, overlay) {
+            (Value::Object(base), Value::Object(overlay)) => {
+                for (name, value) in overlay {
+                    let child = format!("{path}/{}", name.replace('~', "~0").replace('/', "~1"));
+                    match base.get_mut(name) {
+                        Some(existing) => self.merge(existing, value, &child, layer)?,
+                        None => {
+                            base.insert(name.clone(), value.clone());
+                        }
+                    }
+                }
+                Ok(())
+            }
+            (Value::List(base), Value::List(overlay)) => match self.lists {
+                ListMerge::Replace => {
+                    *base = overlay.clone();
+                    Ok(())
+                }
+                ListMerge::Append => {
+                    base.extend(overlay.iter().cloned());
+                    Ok(())
+                }
+                ListMerge::MergeByKey => self.merge_by_key(base, overlay, path, layer),
+            },
+            (Value::Null, _) | (_, Value::Null) => {
+                *base = overlay.clone();
+                Ok(())
+            }
+            (existing, incoming)
+                if matches!(existing, Value::Object(_) | Value::List(_))
+                    || matches!(incoming, Value::Object(_) | Value::List(_)) =>
+            {
+                Err(DocpackError::MergeConflict {
+                    source_id: self.source_id.to_string(),
+                    layer: layer.to_string(),
+                    path: path.to_string(),
+                    existing: kind_name(existing),
+                    incoming: kind_name(incoming),
+                })
+            }
+            (existing, incoming) => {
+                *existing = incoming.clone();
+                Ok(())
+            }
+        }
+    }
+
+    fn merge_by_key(
+        &self,
+        base: &mut Vec<Value>,
+        overlay: &[Value],
+        path: &str,
+        layer: &str,
+    ) -> DocpackResult<()> {
+        let key = self.key.expect("merge_by_key is validated to name a key");
+        for (index, item) in overlay.iter().enumerate() {
+            let identity = item_key(item, key).ok_or_else(|| {
+                derive_error(
+                    self.source_id,
+                    &format!(
+                        "layer '{layer}' has a list item at {path}/{index} without key field '{key}'"
+                    ),
+                )
+            })?;
+            let existing = base
+                .iter()
+                .position(|candidate| item_key(candidate, key).as_ref() == Some(&identity));
+            match existing {
+                Some(position) => self.merge(
+                    &mut base[position],
+                    item,
+                    &format!("{path}/{position}"),
+                    layer,
+                )?,
+                None => base.push(item.clone()),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn item_key(item: &Value, key: &str) -> Option<String> {
+    match item {
+        Value::Object(values) => values
+            .get(key)
+            .filter(|value| !matches!(value, Value::Null))
+            .map(cell_key),
+        _ => None,
+    }
+}
+
+fn kind_name(value: &Value) -> String {
+    match value {
+        Value::Object(_) => "object".to_string(),
+        Value::List(_) => "list".to_string(),
+        scalar => ColumnKind::of(scalar).to_string(),
+    }
+}
+
+fn derive_error(source_id: &str, detail: &str) -> DocpackError {
+    DocpackError::DerivedSource {
+        source_id: source_id.to_string(),
+        detail: detail.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::{ListMerge, merge_documents};
+    use crate::core::{Document, Origin, SourceFormat, SourceMeta, TopLevelShape, Value};
+    use crate::error::DocpackError;
+
+    fn object<const N: usize>(entries: [(&str, Value); N]) -> Value {
+        Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect::<IndexMap<_, _>>(),
+        )
+    }
+
+    fn text(value: &str) -> Value {
+        Value::String(value.to_string())
+    }
+
+    fn layer(id: &str, root: Value) -> Document {
+        Document {
+            source_id: id.to_string(),
+            root,
+            meta: SourceMeta {
+                format: SourceFormat::Yaml,
+                origin: Origin::Stdin,
+                top_level_shape: TopLevelShape::Object,
+                tabular_columns: None,
+                header_present: None,
+                columns: Vec::new(),
+            },
+        }
+    }
+
+    fn layers() -> Vec<Document> {
+        vec![
+            layer(
+                "defaults",
+                object([
+                    ("title", text("Handbook")),
+                    (
+                        "print",
+                        object([("paper", text("a4")), ("duplex", Value::Bool(true))]),
+                    ),
+                    (
+                        "chapters",
+                        Value::List(vec![
+                            object([("id", text("intro")), ("pages", Value::Integer(4))]),
+                            object([("id", text("setup")), ("pages", Value::Integer(9))]),
+                        ]),
+                    ),
+                ]),
+            ),
+            layer(
+                "edition",
+                object([
+                    ("print", object([("paper", text("letter"))])),
+                    ("edition", Value::Integer(2)),
+                    (
+                        "chapters",
+                        Value::List(vec![
+                            object([("id", text("setup")), ("pages", Value::Integer(12))]),
+                            object([("id", text("faq")), ("pages", Value::Integer(2))]),
+                        ]),
+                    ),
+                ]),
+            ),
+        ]
+    }
+
+    fn merged(lists: ListMerge, key: Option<&str>) -> Value {
+        merge_documents("handbook", &layers(), lists, key)
+            .unwrap()
+            .root
+    }
+
+    fn chapter_ids(root: &Value) -> Vec<Value> {
+        let Value::Object(values) = root else {
+            panic!("expected an object root");
+        };
+        let Value::List(chapters) = &values["chapters"] else {
+            panic!("expected a chapter list");
+        };
+        chapters
+            .iter()
+            .map(|chapter| match chapter {
+                Value::Object(fields) => fields["id"].clone(),
+                _ => panic!("expected chapter objects"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn later_layers_override_nested_values_in_first_seen_order() {
+        let root = merged(ListMerge::Replace, None);
+        assert_eq!(
+            root,
+            object([
+                ("title", text("Handbook")),
+                (
+                    "print",
+                    object([("paper", text("letter")), ("duplex", Value::Bool(true))])
+                ),
+                (
+                    "chapters",
+                    Value::List(vec![
+                        object([("id", text("setup")), ("pages", Value::Integer(12))]),
+                        object([("id", text("faq")), ("pages", Value::Integer(2))]),
+                    ])
+                ),
+                ("edition", Value::Integer(2)),
+            ])
+        );
+    }
+
+    #[test]
+    fn lists_append_or_merge_by_key() {
+        assert_eq!(
+            chapter_ids(&merged(ListMerge::Append, None)),
+            vec![text("intro"), text("setup"), text("setup"), text("faq")]
+        );
+        let root = merged(ListMerge::MergeByKey, Some("id"));
+        assert_eq!(
+            chapter_ids(&root),
+            vec![text("intro"), text("setup"), text("faq")]
+        );
+        let Value::Object(values) = &root else {
+            panic!("expected an object root");
+        };
+        assert_eq!(
+            values["chapters"],
+            Value::List(vec![
+                object([("id", text("intro")), ("pages", Value::Integer(4))]),
+                object([("id", text("setup")), ("pages", Value::Integer(12))]),
+                object([("id", text("faq")), ("pages", Value::Integer(2))]),
+            ])
+        );
+    }
+
+    #[test]
+    fn reports_kind_conflicts_with_their_path() {
+        let mut layers = layers();
+        layers.push(layer(
+            "local",
+            object([(
+                "print",
+                object([("duplex", object([("long_edge", Value::Bool(true))]))]),
+            )]),
+        ));
+        let error = merge_documents("handbook", &layers, ListMerge::Replace, None).unwrap_err();
+        assert!(matches!(
+            &error,
+            DocpackError::MergeConflict { layer, path, .. } if layer == "local" && path == "/print/duplex"
+        ));
+        assert_eq!(
+            error.to_string(),
+            "cannot merge layer 'local' into source 'handbook': /print/duplex is a bool in earlier layers but an object in 'local'"
+        );
+    }
+}
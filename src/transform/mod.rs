@@ -0,0 +1,968 @@
+mod aggregate;
+mod check;
+mod compare;
+mod decimal;
+mod expr;
+mod flatten;
+mod join;
+mod merge;
+mod redact;
+mod sample;
+mod table;
+mod transpose;
+
+pub use aggregate::{AggregateFunction, Aggregation};
+pub use check::{Check, CheckFailure, run_check};
+pub use expr::Expression;
+pub use flatten::ListFlatten;
+pub use join::{JoinKind, JoinOptions, JoinOutcome, join_documents};
+pub use merge::{ListMerge, merge_documents};
+pub use redact::{RedactAction, RedactionRule, redact_document};
+
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::core::{ColumnMeta, Document, Value};
+use crate::error::{DocpackError, DocpackResult};
+use table::Table;
+
+/// One declarative step of an output's transform pipeline.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Transform {
+    /// Keeps only the listed columns, in the listed order.
+    SelectColumns { columns: Vec<String> },
+    /// Renames columns, keyed by their current name.
+    Rename { columns: IndexMap<String, String> },
+    /// Removes the listed columns.
+    Drop { columns: Vec<String> },
+    /// Keeps the rows whose cell satisfies a comparison.
+    Filter {
+        column: String,
+        operator: FilterOperator,
+        value: Option<Literal>,
+    },
+    /// Stably sorts rows by one or more keys.
+    Sort { by: Vec<SortKey> },
+    /// Keeps the first `count` rows; `head` is accepted as an alias.
+    #[serde(alias = "head")]
+    Limit { count: usize },
+    /// Keeps the last `count` rows.
+    Tail { count: usize },
+    /// Keeps `count` rows chosen pseudo-randomly from `seed`, in their
+    /// original order.
+    Sample {
+        count: usize,
+        #[serde(default)]
+        seed: u64,
+    },
+    /// Keeps every `step`-th row, starting at the 0-based `offset`.
+    Every {
+        step: usize,
+        #[serde(default)]
+        offset: usize,
+    },
+    /// Drops rows that repeat an earlier row, optionally comparing only some
+    /// columns.
+    Distinct { columns: Option<Vec<String>> },
+    /// Adds (or replaces) a record column computed by an [`Expression`].
+    Derive { column: String, expr: String },
+    /// Collapses rows into one per distinct `by` key with aggregate columns.
+    GroupBy {
+        by: Vec<String>,
+        aggregates: Vec<Aggregation>,
+    },
+    /// Spreads the distinct values of `columns` into new columns, one row per
+    /// distinct `index` key.
+    Pivot {
+        index: Vec<String>,
+        columns: String,
+        values: String,
+        #[serde(default = "default_pivot_function")]
+        function: AggregateFunction,
+        fill: Option<Literal>,
+    },
+    /// Rewrites nested objects into `parent.child` columns and joins or
+    /// explodes list cells. Unlike other steps, it also accepts a list of
+    /// objects that is not yet tabular.
+    Flatten {
+        /// Joins parent and child names; defaults to `.`.
+        separator: Option<String>,
+        #[serde(default)]
+        lists: ListFlatten,
+        /// Joins list items when `lists = "join"`; defaults to `, `.
+        list_separator: Option<String>,
+    },
+    /// Swaps rows and columns, optionally naming the new columns after the
+    /// cells of a `header` column.
+    Transpose {
+        header: Option<String>,
+        /// Name of the column listing the former column names; defaults to
+        /// `column`.
+        key: Option<String>,
+    },
+}
+
+fn default_pivot_function() -> AggregateFunction {
+    AggregateFunction::Sum
+}
+
+impl Transform {
+    /// Returns the manifest `op` name of the step.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SelectColumns { .. } => "select_columns",
+            Self::Rename { .. } => "rename",
+            Self::Drop { .. } => "drop",
+            Self::Filter { .. } => "filter",
+            Self::Sort { .. } => "sort",
+            Self::Limit { .. } => "limit",
+            Self::Tail { .. } => "tail",
+            Self::Sample { .. } => "sample",
+            Self::Every { .. } => "every",
+            Self::Distinct { .. } => "distinct",
+            Self::Derive { .. } => "derive",
+            Self::GroupBy { .. } => "group_by",
+            Self::Pivot { .. } => "pivot",
+            Self::Flatten { .. } => "flatten",
+            Self::Transpose { .. } => "transpose",
+        }
+    }
+}
+
+/// Comparison used by a `filter` step.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterOperator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+    IsNull,
+    NotNull,
+}
+
+impl FilterOperator {
+    /// Returns `true` when the operator compares against a `value`.
+    pub fn takes_value(self) -> bool {
+        !matches!(self, Self::IsNull | Self::NotNull)
+    }
+}
+
+impl Display for FilterOperator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Eq => "eq",
+            Self::Ne => "ne",
+            Self::Lt => "lt",
+            Self::Le => "le",
+            Self::Gt => "gt",
+            Self::Ge => "ge",
+            Self::Contains => "contains",
+            Self::IsNull => "is_null",
+            Self::NotNull => "not_null",
+        };
+        write!(f, "{value}")
+    }
+}
+
+/// Scalar constant written in a manifest.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum Literal {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+}
+
+impl Literal {
+    /// Converts the constant into a normalized value.
+    pub fn to_value(&self) -> Value {
+        match self {
+            Self::Bool(value) => Value::Bool(*value),
+            Self::Integer(value) => Value::Integer(*value),
+            Self::Float(value) => Value::Float(*value),
+            Self::String(value) => Value::String(value.clone()),
+        }
+    }
+}
+
+/// One key of a `sort` step.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SortKey {
+    pub column: String,
+    #[serde(default)]
+    pub order: SortOrder,
+}
+
+/// Direction of a sort key.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Formats the first `rows` rows of a tabular document as a plain-text table
+/// with 0-based row numbers, or returns `None` for other shapes.
+pub fn preview_table(doc: &Document, rows: usize) -> Option<String> {
+    let table = Table::from_document(doc).ok()?;
+    let header = std::iter::once("row".to_string())
+        .chain(table.columns.iter().map(|column| column.name.clone()))
+        .collect::<Vec<_>>();
+    let lines = table
+        .rows
+        .iter()
+        .take(rows)
+        .enumerate()
+        .map(|(index, cells)| {
+            std::iter::once(index.to_string())
+                .chain(
+                    cells
+                        .iter()
+                        .map(|cell| cell.scalar_text().unwrap_or_else(|| "…".to_string())),
+                )
+                .collect()
+        })
+        .collect::<Vec<_>>();
+    let mut preview = table::text_table(&header, &lines);
+    if table.rows.len() > rows {
+        preview.push_str(&format!(
+            "... and {} more row(s)\n",
+            table.rows.len() - rows
+        ));
+    }
+    Some(preview)
+}
+
+/// Applies transform steps in order, keeping the tabular metadata in sync.
+pub fn apply_transforms(mut doc: Document, steps: &[Transform]) -> DocpackResult<Document> {
+    for (index, step) in steps.iter().enumerate() {
+        doc = apply_step(doc, step).map_err(|detail| DocpackError::Transform {
+            step: index + 1,
+            operation: step.name().to_string(),
+            detail,
+        })?;
+    }
+    Ok(doc)
+}
+
+fn apply_step(doc: Document, step: &Transform) -> Result<Document, String> {
+    let mut table = match step {
+        Transform::Flatten { .. } => Table::from_records(&doc)?,
+        _ => Table::from_document(&doc)?,
+    };
+    match step {
+        Transform::SelectColumns { columns } => {
+            let indexes = columns
+                .iter()
+                .map(|column| table.index_of(column))
+                .collect::<Result<Vec<_>, _>>()?;
+            table.project(&indexes);
+        }
+        Transform::Rename { columns } => {
+            if !table.records {
+                return Err("matrix columns are positional and cannot be renamed".to_string());
+            }
+            for (from, to) in columns {
+                let index = table.index_of(from)?;
+                if from != to && table.index_of(to).is_ok() {
+                    return Err(format!(
+                        "cannot rename '{from}' to '{to}': column '{to}' already exists"
+                    ));
+                }
+                table.columns[index].name = to.clone();
+            }
+        }
+        Transform::Drop { columns } => {
+            let dropped = columns
+                .iter()
+                .map(|column| table.index_of(column))
+                .collect::<Result<HashSet<_>, _>>()?;
+            let kept = (0..table.columns.len())
+                .filter(|index| !dropped.contains(index))
+                .collect::<Vec<_>>();
+            table.project(&kept);
+        }
+        Transform::Filter {
+            column,
+            operator,
+            value,
+        } => {
+            let index = table.index_of(column)?;
+            let value = match (operator.takes_value(), value) {
+                (true, Some(value)) => value.to_value(),
+                (true, None) => return Err(format!("operator '{operator}' requires a value")),
+                (false, _) => Value::Null,
+            };
+            table
+                .rows
+                .retain(|row| matches_filter(&row[index], *operator, &value));
+        }
+        Transform::Sort { by } => {
+            let keys = by
+                .iter()
+                .map(|key| Ok((table.index_of(&key.column)?, key.order)))
+                .collect::<Result<Vec<_>, String>>()?;
+            table.rows.sort_by(|left, right| {
+                keys.iter()
+                    .map(|(index, order)| {
+                        let (left, right) = (&left[*index], &right[*index]);
+                        match (left, right, order) {
+                            // Nulls stay last in either direction.
+                            (Value::Null, _, _) | (_, Value::Null, _) => {
+                                compare::sort_order(left, right)
+                            }
+                            (_, _, SortOrder::Asc) => compare::sort_order(left, right),
+                            (_, _, SortOrder::Desc) => compare::sort_order(right, left),
+                        }
+                    })
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        }
+        Transform::Limit { count } => table.rows.truncate(*count),
+        Transform::Tail { count } => {
+            let skip = table.rows.len().saturating_sub(*count);
+            table.rows.drain(..skip);
+        }
+        Transform::Sample { count, seed } => {
+            let keep = sample::sample_indexes(table.rows.len(), *count, *seed);
+            let mut rows = std::mem::take(&mut table.rows)
+                .into_iter()
+                .map(Some)
+                .collect::<Vec<_>>();
+            table.rows = keep
+                .into_iter()
+                .filter_map(|index| rows[index].take())
+                .collect();
+        }
+        Transform::Every { step, offset } => {
+            if *step == 0 {
+                return Err("step must be at least 1".to_string());
+            }
+            table.rows = std::mem::take(&mut table.rows)
+                .into_iter()
+                .skip(*offset)
+                .step_by(*step)
+                .collect();
+        }
+        Transform::Distinct { columns } => {
+            let indexes = match columns {
+                Some(columns) => columns
+                    .iter()
+                    .map(|column| table.index_of(column))
+                    .collect::<Result<Vec<_>, _>>()?,
+                None => (0..table.columns.len()).collect(),
+            };
+            let mut seen = HashSet::new();
+            table.rows.retain(|row| {
+                let key = indexes
+                    .iter()
+                    .map(|index| compare::cell_key(&row[*index]))
+                    .collect::<Vec<_>>();
+                seen.insert(key)
+            });
+        }
+        Transform::Derive { column, expr } => {
+            if !table.records {
+                return Err("matrix columns are positional; derive needs record rows".to_string());
+            }
+            let expression = Expression::parse(expr)
+                .map_err(|detail| format!("invalid expression: {detail}"))?;
+            let inputs = expression
+                .columns()
+                .into_iter()
+                .map(|name| Ok((name, table.index_of(name)?)))
+                .collect::<Result<Vec<_>, String>>()?;
+            let values = table
+                .rows
+                .iter()
+                .enumerate()
+                .map(|(row_index, row)| {
+                    expression
+                        .evaluate(&|name| {
+                            inputs
+                                .iter()
+                                .find(|(input, _)| *input == name)
+                                .map_or(&Value::Null, |(_, index)| &row[*index])
+                        })
+                        .map_err(|detail| format!("at /{row_index}/{column}: {detail}"))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            match table.index_of(column) {
+                Ok(index) => {
+                    for (row, value) in table.rows.iter_mut().zip(values) {
+                        row[index] = value;
+                    }
+                }
+                Err(_) => {
+                    table.columns.push(ColumnMeta::infer(column.clone(), []));
+                    for (row, value) in table.rows.iter_mut().zip(values) {
+                        row.push(value);
+                    }
+                }
+            }
+        }
+        Transform::GroupBy { by, aggregates } => {
+            table = aggregate::group_by(&table, by, aggregates)?;
+        }
+        Transform::Pivot {
+            index,
+            columns,
+            values,
+            function,
+            fill,
+        } => {
+            table = aggregate::pivot(&table, index, columns, values, *function, fill.as_ref())?;
+        }
+        Transform::Flatten {
+            separator,
+            lists,
+            list_separator,
+        } => {
+            table = flatten::flatten(
+                &table,
+                separator.as_deref().unwrap_or("."),
+                *lists,
+                list_separator.as_deref().unwrap_or(", "),
+            )?;
+        }
+        Transform::Transpose { header, key } => {
+            table = transpose::transpose(&table, header.as_deref(), key.as_deref())?;
+        }
+    }
+    Ok(table.into_document(doc))
+}
+
+fn matches_filter(cell: &Value, operator: FilterOperator, value: &Value) -> bool {
+    let ordering = || compare::compare_cells(cell, value);
+    match operator {
+        FilterOperator::Eq => ordering().is_some_and(|ordering| ordering.is_eq()),
+        FilterOperator::Ne => !ordering().is_some_and(|ordering| ordering.is_eq()),
+        FilterOperator::Lt => ordering().is_some_and(|ordering| ordering.is_lt()),
+        FilterOperator::Le => ordering().is_some_and(|ordering| ordering.is_le()),
+        FilterOperator::Gt => ordering().is_some_and(|ordering| ordering.is_gt()),
+        FilterOperator::Ge => ordering().is_some_and(|ordering| ordering.is_ge()),
+        FilterOperator::Contains => match (cell, cell.scalar_text(), value.scalar_text()) {
+            (Value::Null, _, _) => false,
+            (_, Some(cell), Some(value)) => cell.contains(&value),
+            _ => false,
+        },
+        FilterOperator::IsNull => matches!(cell, Value::Null),
+        FilterOperator::NotNull => !matches!(cell, Value::Null),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::{
+        AggregateFunction, Aggregation, FilterOperator, ListFlatten, Literal, SortKey, SortOrder,
+        Transform, apply_transforms,
+    };
+    use crate::core::{
+        ColumnKind, Document, Origin, SourceFormat, SourceMeta, TopLevelShape, Value, infer_columns,
+    };
+    use crate::error::DocpackError;
+
+    fn sales() -> Document {
+        let columns = ["region", "rep", "amount"].map(str::to_string).to_vec();
+        let rows = [
+            ("East", "Alice", Value::Integer(120)),
+            ("West", "Bob", Value::Null),
+            ("East", "Carol", Value::Integer(300)),
+            ("West", "Dan", Value::Integer(75)),
+            ("East", "Alice", Value::Integer(120)),
+        ]
+        .into_iter()
+        .map(|(region, rep, amount)| {
+            Value::Object(IndexMap::from([
+                ("region".to_string(), Value::String(region.to_string())),
+                ("rep".to_string(), Value::String(rep.to_string())),
+                ("amount".to_string(), amount),
+            ]))
+        })
+        .collect();
+        let root = Value::List(rows);
+        Document {
+            source_id: "sales".to_string(),
+            meta: SourceMeta {
+                format: SourceFormat::Csv,
+                origin: Origin::Stdin,
+                top_level_shape: TopLevelShape::TabularRecords,
+                columns: infer_columns(&root, Some(&columns)),
+                tabular_columns: Some(columns),
+                header_present: Some(true),
+            },
+            root,
+        }
+    }
+
+    fn cells(doc: &Document, column: &str) -> Vec<Value> {
+        match &doc.root {
+            Value::List(rows) => rows
+                .iter()
+                .map(|row| match row {
+                    Value::Object(values) => values[column].clone(),
+                    _ => panic!("expected record rows"),
+                })
+                .collect(),
+            _ => panic!("expected a list root"),
+        }
+    }
+
+    #[test]
+    fn filters_sorts_and_limits_records() {
+        let doc = apply_transforms(
+            sales(),
+            &[
+                Transform::Filter {
+                    column: "amount".to_string(),
+                    operator: FilterOperator::Ge,
+                    value: Some(Literal::Integer(100)),
+                },
+                Transform::Distinct { columns: None },
+                Transform::Sort {
+                    by: vec![SortKey {
+                        column: "amount".to_string(),
+                        order: SortOrder::Desc,
+                    }],
+                },
+                Transform::Limit { count: 1 },
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(cells(&doc, "rep"), vec![Value::String("Carol".to_string())]);
+        assert_eq!(doc.meta.columns[2].max_width, 3);
+    }
+
+    #[test]
+    fn keeps_column_metadata_in_step_with_projection() {
+        let mut doc = sales();
+        doc.meta.columns[2].unit = Some("EUR".to_string());
+        let doc = apply_transforms(
+            doc,
+            &[
+                Transform::Rename {
+                    columns: IndexMap::from([("amount".to_string(), "total".to_string())]),
+                },
+                Transform::SelectColumns {
+                    columns: vec!["total".to_string(), "region".to_string()],
+                },
+                Transform::Filter {
+                    column: "total".to_string(),
+                    operator: FilterOperator::NotNull,
+                    value: None,
+                },
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            doc.meta.tabular_columns,
+            Some(vec!["total".to_string(), "region".to_string()])
+        );
+        assert_eq!(doc.meta.columns[0].name, "total");
+        assert_eq!(doc.meta.columns[0].unit.as_deref(), Some("EUR"));
+        assert_eq!(doc.meta.columns[0].kind, ColumnKind::Integer);
+        assert!(!doc.meta.columns[0].nullable);
+        match &doc.root {
+            Value::List(rows) => {
+                assert_eq!(rows.len(), 4);
+                let Value::Object(first) = &rows[0] else {
+                    panic!("expected record rows");
+                };
+                assert_eq!(first.keys().collect::<Vec<_>>(), ["total", "region"]);
+            }
+            _ => panic!("expected a list root"),
+        }
+    }
+
+    #[test]
+    fn sorts_nulls_last_in_both_directions() {
+        for order in [SortOrder::Asc, SortOrder::Desc] {
+            let doc = apply_transforms(
+                sales(),
+                &[Transform::Sort {
+                    by: vec![SortKey {
+                        column: "amount".to_string(),
+                        order,
+                    }],
+                }],
+            )
+            .unwrap();
+            assert_eq!(cells(&doc, "amount").last(), Some(&Value::Null));
+        }
+    }
+
+    #[test]
+    fn reports_unknown_columns_with_step_number() {
+        let error = apply_transforms(
+            sales(),
+            &[
+                Transform::Limit { count: 3 },
+                Transform::Drop {
+                    columns: vec!["price".to_string()],
+                },
+            ],
+        )
+        .unwrap_err();
+
+        match &error {
+            DocpackError::Transform {
+                step, operation, ..
+            } => {
+                assert_eq!(*step, 2);
+                assert_eq!(operation, "drop");
+            }
+            other => panic!("expected a transform error, got {other}"),
+        }
+        assert_eq!(
+            error.to_string(),
+            "transform step 2 (drop) failed: unknown column 'price'; available columns: region, rep, amount"
+        );
+    }
+
+    #[test]
+    fn derives_columns_and_reports_row_errors() {
+        let doc = apply_transforms(
+            sales(),
+            &[Transform::Derive {
+                column: "label".to_string(),
+                expr: "concat(rep, ' (', region, ')') ".to_string(),
+            }],
+        )
+        .unwrap();
+        assert_eq!(
+            cells(&doc, "label")[0],
+            Value::String("Alice (East)".to_string())
+        );
+        assert_eq!(doc.meta.columns[3].kind, ColumnKind::String);
+
+        let error = apply_transforms(
+            sales(),
+            &[Transform::Derive {
+                column: "share".to_string(),
+                expr: "amount / rep".to_string(),
+            }],
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "transform step 1 (derive) failed: at /0/share: cannot apply '/' to integer and string"
+        );
+    }
+
+    #[test]
+    fn groups_rows_with_aggregates_in_first_seen_order() {
+        let aggregation = |column: Option<&str>, function, name: Option<&str>| Aggregation {
+            column: column.map(str::to_string),
+            function,
+            name: name.map(str::to_string),
+        };
+        let doc = apply_transforms(
+            sales(),
+            &[Transform::GroupBy {
+                by: vec!["region".to_string()],
+                aggregates: vec![
+                    aggregation(Some("amount"), AggregateFunction::Sum, Some("total")),
+                    aggregation(Some("amount"), AggregateFunction::Mean, None),
+                    aggregation(Some("rep"), AggregateFunction::CountDistinct, Some("reps")),
+                    aggregation(None, AggregateFunction::Count, Some("rows")),
+                ],
+            }],
+        )
+        .unwrap();
+
+        assert_eq!(doc.meta.top_level_shape, TopLevelShape::TabularRecords);
+        assert_eq!(
+            doc.meta.tabular_columns,
+            Some(
+                ["region", "total", "mean_amount", "reps", "rows"]
+                    .map(str::to_string)
+                    .to_vec()
+            )
+        );
+        assert_eq!(
+            cells(&doc, "region"),
+            vec![
+                Value::String("East".to_string()),
+                Value::String("West".to_string())
+            ]
+        );
+        assert_eq!(
+            cells(&doc, "total"),
+            vec![Value::Integer(540), Value::Integer(75)]
+        );
+        assert_eq!(
+            cells(&doc, "mean_amount"),
+            vec![Value::Float(180.0), Value::Float(75.0)]
+        );
+        assert_eq!(
+            cells(&doc, "reps"),
+            vec![Value::Integer(2), Value::Integer(2)]
+        );
+        assert_eq!(
+            cells(&doc, "rows"),
+            vec![Value::Integer(3), Value::Integer(2)]
+        );
+    }
+
+    #[test]
+    fn sums_and_averages_decimals_exactly() {
+        let mut doc = sales();
+        if let Value::List(rows) = &mut doc.root {
+            let amounts =
+                ["0.1", "0.05", "0.2", "0.10"].map(|text| Value::Decimal(text.to_string()));
+            for (row, amount) in rows
+                .iter_mut()
+                .zip(amounts.into_iter().chain([Value::Null]))
+            {
+                if let Value::Object(values) = row {
+                    values.insert("amount".to_string(), amount);
+                }
+            }
+        }
+        let aggregation = |function| Aggregation {
+            column: Some("amount".to_string()),
+            function,
+            name: None,
+        };
+        let doc = apply_transforms(
+            doc,
+            &[Transform::GroupBy {
+                by: vec!["region".to_string()],
+                aggregates: vec![
+                    aggregation(AggregateFunction::Sum),
+                    aggregation(AggregateFunction::Mean),
+                ],
+            }],
+        )
+        .unwrap();
+
+        let decimals =
+            |texts: [&str; 2]| texts.map(|text| Value::Decimal(text.to_string())).to_vec();
+        assert_eq!(cells(&doc, "sum_amount"), decimals(["0.3", "0.15"]));
+        assert_eq!(cells(&doc, "mean_amount"), decimals(["0.15", "0.075"]));
+    }
+
+    #[test]
+    fn pivots_distinct_values_into_columns() {
+        let doc = apply_transforms(
+            sales(),
+            &[Transform::Pivot {
+                index: vec!["rep".to_string()],
+                columns: "region".to_string(),
+                values: "amount".to_string(),
+                function: AggregateFunction::Sum,
+                fill: Some(Literal::Integer(0)),
+            }],
+        )
+        .unwrap();
+
+        assert_eq!(
+            doc.meta.tabular_columns,
+            Some(["rep", "East", "West"].map(str::to_string).to_vec())
+        );
+        assert_eq!(
+            cells(&doc, "East"),
+            vec![
+                Value::Integer(240),
+                Value::Integer(0),
+                Value::Integer(300),
+                Value::Integer(0)
+            ]
+        );
+        assert_eq!(
+            cells(&doc, "West"),
+            vec![
+                Value::Integer(0),
+                Value::Null,
+                Value::Integer(0),
+                Value::Integer(75)
+            ]
+        );
+    }
+
+    fn nested_people() -> Document {
+        let object = |entries: Vec<(&str, Value)>| {
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key.to_string(), value))
+                    .collect(),
+            )
+        };
+        let text = |value: &str| Value::String(value.to_string());
+        let root = Value::List(vec![
+            object(vec![
+                ("name", text("Ada")),
+                (
+                    "address",
+                    object(vec![("city", text("Oslo")), ("zip", text("0150"))]),
+                ),
+                ("tags", Value::List(vec![text("admin"), text("ops")])),
+            ]),
+            object(vec![
+                ("name", text("Bo")),
+                ("address", object(vec![("city", text("Rome"))])),
+                ("tags", Value::List(Vec::new())),
+            ]),
+        ]);
+        Document {
+            source_id: "people".to_string(),
+            meta: SourceMeta {
+                format: SourceFormat::Json,
+                origin: Origin::Stdin,
+                top_level_shape: TopLevelShape::List,
+                tabular_columns: None,
+                header_present: None,
+                columns: Vec::new(),
+            },
+            root,
+        }
+    }
+
+    #[test]
+    fn flattens_nested_objects_and_joins_lists() {
+        let doc = apply_transforms(
+            nested_people(),
+            &[Transform::Flatten {
+                separator: None,
+                lists: ListFlatten::Join,
+                list_separator: Some("; ".to_string()),
+            }],
+        )
+        .unwrap();
+
+        assert_eq!(doc.meta.top_level_shape, TopLevelShape::TabularRecords);
+        assert_eq!(
+            doc.meta.tabular_columns,
+            Some(
+                ["name", "address.city", "address.zip", "tags"]
+                    .map(str::to_string)
+                    .to_vec()
+            )
+        );
+        assert_eq!(
+            cells(&doc, "address.zip"),
+            vec![Value::String("0150".to_string()), Value::Null]
+        );
+        assert_eq!(
+            cells(&doc, "tags"),
+            vec![
+                Value::String("admin; ops".to_string()),
+                Value::String(String::new())
+            ]
+        );
+    }
+
+    #[test]
+    fn explodes_list_cells_into_rows() {
+        let doc = apply_transforms(
+            nested_people(),
+            &[Transform::Flatten {
+                separator: Some("_".to_string()),
+                lists: ListFlatten::Explode,
+                list_separator: None,
+            }],
+        )
+        .unwrap();
+
+        assert_eq!(
+            cells(&doc, "name"),
+            ["Ada", "Ada", "Bo"]
+                .map(|name| Value::String(name.to_string()))
+                .to_vec()
+        );
+        assert_eq!(
+            cells(&doc, "tags"),
+            vec![
+                Value::String("admin".to_string()),
+                Value::String("ops".to_string()),
+                Value::Null
+            ]
+        );
+        assert_eq!(
+            cells(&doc, "address_city")[2],
+            Value::String("Rome".to_string())
+        );
+    }
+
+    #[test]
+    fn transposes_records_around_a_header_column() {
+        let doc = apply_transforms(
+            sales(),
+            &[
+                Transform::Limit { count: 2 },
+                Transform::Transpose {
+                    header: Some("rep".to_string()),
+                    key: Some("field".to_string()),
+                },
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            doc.meta.tabular_columns,
+            Some(["field", "Alice", "Bob"].map(str::to_string).to_vec())
+        );
+        assert_eq!(
+            cells(&doc, "field"),
+            vec![
+                Value::String("region".to_string()),
+                Value::String("amount".to_string())
+            ]
+        );
+        assert_eq!(
+            cells(&doc, "Bob"),
+            vec![Value::String("West".to_string()), Value::Null]
+        );
+
+        let error = apply_transforms(
+            sales(),
+            &[Transform::Transpose {
+                header: Some("region".to_string()),
+                key: None,
+            }],
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "transform step 1 (transpose) failed: transposed column 'East' would appear twice"
+        );
+    }
+
+    #[test]
+    fn samples_rows_by_position_and_seed() {
+        let reps = |steps: &[Transform]| {
+            cells(&apply_transforms(sales(), steps).unwrap(), "rep")
+                .into_iter()
+                .map(|cell| cell.scalar_text().unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(reps(&[Transform::Tail { count: 2 }]), ["Dan", "Alice"]);
+        assert_eq!(
+            reps(&[Transform::Every { step: 2, offset: 1 }]),
+            ["Bob", "Dan"]
+        );
+        let sampled = reps(&[Transform::Sample { count: 3, seed: 11 }]);
+        assert_eq!(sampled.len(), 3);
+        assert_eq!(sampled, reps(&[Transform::Sample { count: 3, seed: 11 }]));
+        assert_eq!(
+            reps(&[Transform::Sample { count: 9, seed: 11 }]),
+            ["Alice", "Bob", "Carol", "Dan", "Alice"]
+        );
+    }
+}
@@ -0,0 +1,494 @@
+use std::fmt::{Display, Formatter};
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::core::{ColumnKind, Document, Value};
+use crate::transform::table::Table;
+
+/// What a redaction rule does to the values it targets.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactAction {
+    /// Removes the column, object key, or list item; a single cell of a
+    /// tabular document becomes null so rows keep their width.
+    Drop,
+    /// Replaces scalars with a salted SHA-256 digest, stable across builds.
+    Hash,
+    /// Replaces all but the last `keep` characters with `*`.
+    Mask,
+    /// Replaces the value with fixed text.
+    Replace,
+}
+
+impl Display for RedactAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Drop => "drop",
+            Self::Hash => "hash",
+            Self::Mask => "mask",
+            Self::Replace => "replace",
+        };
+        write!(f, "{value}")
+    }
+}
+
+/// One redaction rule, targeting either a table column or a JSON Pointer.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RedactionRule {
+    /// Column of a tabular document; every row's cell is redacted.
+    pub column: Option<String>,
+    /// JSON Pointer (RFC 6901) to one value of the document.
+    pub path: Option<String>,
+    pub action: RedactAction,
+    /// Trailing characters left visible by `mask`; defaults to 4.
+    pub keep: Option<usize>,
+    /// Text written by `replace`.
+    pub replacement: Option<String>,
+    /// Literal salt for `hash`.
+    pub salt: Option<String>,
+    /// Environment variable holding the salt for `hash`, so it stays out of
+    /// the manifest.
+    pub salt_env: Option<String>,
+}
+
+impl RedactionRule {
+    /// Returns the rule's target as shown in diagnostics and `inspect`.
+    pub fn target(&self) -> String {
+        match (&self.column, &self.path) {
+            (Some(column), _) => format!("column '{column}'"),
+            (None, Some(path)) => path.clone(),
+            (None, None) => "(no target)".to_string(),
+        }
+    }
+
+    /// Checks that the rule names one target and only the options its action
+    /// uses.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.column.is_some() == self.path.is_some() {
+            return Err("must set exactly one of column or path".to_string());
+        }
+        if let Some(path) = &self.path
+            && !path.starts_with('/')
+        {
+            return Err(format!(
+                "path '{path}' must be a JSON Pointer starting with '/'"
+            ));
+        }
+        for (option, set, action) in [
+            ("keep", self.keep.is_some(), RedactAction::Mask),
+            (
+                "replacement",
+                self.replacement.is_some(),
+                RedactAction::Replace,
+            ),
+            ("salt", self.salt.is_some(), RedactAction::Hash),
+            ("salt_env", self.salt_env.is_some(), RedactAction::Hash),
+        ] {
+            if set && self.action != action {
+                return Err(format!(
+                    "sets {option}, which only applies to action '{action}'"
+                ));
+            }
+        }
+        match self.action {
+            RedactAction::Replace if self.replacement.is_none() => {
+                Err("action 'replace' needs a replacement".to_string())
+            }
+            RedactAction::Hash if self.salt.is_some() == self.salt_env.is_some() => {
+                Err("action 'hash' needs exactly one of salt or salt_env".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Applies redaction rules in order, returning the redacted document and one
+/// line per rule describing what was changed.
+///
+/// A target that does not exist is an error rather than a no-op, so a renamed
+/// column cannot silently leak into an artifact.
+pub fn redact_document(
+    doc: Document,
+    rules: &[RedactionRule],
+) -> Result<(Document, Vec<String>), (String, String)> {
+    let mut doc = doc;
+    let mut report = Vec::new();
+    for rule in rules {
+        let fail = |detail: String| (rule.target(), detail);
+        let redactor = Redactor::new(rule).map_err(fail)?;
+        let count = match (&rule.column, &rule.path) {
+            (Some(column), _) => {
+                let mut table = Table::from_document(&doc).map_err(|_| {
+                    fail(format!(
+                        "column targets need a tabular source, got {}",
+                        doc.meta.top_level_shape
+                    ))
+                })?;
+                let index = table.index_of(column).map_err(fail)?;
+                let mut count = 0;
+                if rule.action == RedactAction::Drop {
+                    let keep = (0..table.columns.len())
+                        .filter(|position| *position != index)
+                        .collect::<Vec<_>>();
+                    table.project(&keep);
+                } else {
+                    for (row, cells) in table.rows.iter_mut().enumerate() {
+                        if redactor
+                            .apply(&mut cells[index])
+                            .map_err(|detail| fail(format!("{detail} at row {row}")))?
+                        {
+                            count += 1;
+                        }
+                    }
+                }
+                doc = table.into_document(doc);
+                count
+            }
+            (None, Some(path)) => {
+                let count = redact_pointer(&mut doc.root, path, &redactor, doc.meta.is_tabular())
+                    .map_err(fail)?;
+                if doc.meta.is_tabular() {
+                    let table = Table::from_document(&doc).map_err(fail)?;
+                    doc = table.into_document(doc);
+                }
+                count
+            }
+            (None, None) => return Err(fail("must set exactly one of column or path".into())),
+        };
+        report.push(match rule.action {
+            RedactAction::Drop => format!("{}: drop", rule.target()),
+            action => format!("{}: {action} ({count} value(s))", rule.target()),
+        });
+    }
+    Ok((doc, report))
+}
+
+struct Redactor<'a> {
+    rule: &'a RedactionRule,
+    salt: String,
+}
+
+impl<'a> Redactor<'a> {
+    fn new(rule: &'a RedactionRule) -> Result<Self, String> {
+        let salt = match (&rule.salt, &rule.salt_env) {
+            (Some(salt), _) => salt.clone(),
+            (None, Some(name)) if rule.action == RedactAction::Hash => std::env::var(name)
+                .map_err(|_| format!("salt environment variable '{name}' is not set"))?,
+            _ => String::new(),
+        };
+        Ok(Self { rule, salt })
+    }
+
+    /// Redacts one value in place, returning whether it held anything.
+    /// Nulls stay null since they carry nothing to hide.
+    fn apply(&self, value: &mut Value) -> Result<bool, String> {
+        if matches!(value, Value::Null) {
+            return Ok(false);
+        }
+        let current = &*value;
+        let text = || {
+            current.scalar_text().ok_or_else(|| {
+                format!(
+                    "{} needs scalar values, got {}",
+                    self.rule.action,
+                    match current {
+                        Value::Object(_) => "an object".to_string(),
+                        Value::List(_) => "a list".to_string(),
+                        scalar => ColumnKind::of(scalar).to_string(),
+                    }
+                )
+            })
+        };
+        let redacted = match self.rule.action {
+            RedactAction::Drop => Value::Null,
+            RedactAction::Replace => {
+                Value::String(self.rule.replacement.clone().unwrap_or_default())
+            }
+            RedactAction::Hash => {
+                let mut hasher = Sha256::new();
+                hasher.update(self.salt.as_bytes());
+                hasher.update([0]);
+                hasher.update(text()?.as_bytes());
+                let digest = hasher.finalize();
+                Value::String(
+                    digest[..8]
+                        .iter()
+                        .map(|byte| format!("{byte:02x}"))
+                        .collect(),
+                )
+            }
+            RedactAction::Mask => {
+                let text = text()?;
+                let length = text.chars().count();
+                let keep = self.rule.keep.unwrap_or(4);
+                let keep = if length > keep { keep } else { 0 };
+                Value::String(
+                    text.chars()
+                        .enumerate()
+                        .map(|(index, ch)| if index < length - keep { '*' } else { ch })
+                        .collect(),
+                )
+            }
+        };
+        *value = redacted;
+        Ok(true)
+    }
+}
+
+/// Redacts the value a JSON Pointer addresses, failing when it does not
+/// resolve. In a tabular document only a whole row (`/N`) can be dropped;
+/// dropping a cell nulls it instead, so rows stay the same width.
+fn redact_pointer(
+    root: &mut Value,
+    pointer: &str,
+    redactor: &Redactor,
+    tabular: bool,
+) -> Result<usize, String> {
+    let segments = pointer
+        .strip_prefix('/')
+        .ok_or_else(|| "a JSON Pointer must start with '/'".to_string())?
+        .split('/')
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .collect::<Vec<_>>();
+    let (last, parents) = segments.split_last().expect("split yields a segment");
+
+    let mut current = root;
+    for segment in parents {
+        current = child(current, segment)?;
+    }
+    let table_cell = tabular && parents.len() == 1;
+    if redactor.rule.action == RedactAction::Drop && !table_cell {
+        match current {
+            Value::Object(values) => {
+                values
+                    .shift_remove(last)
+                    .ok_or_else(|| format!("no key '{last}'"))?;
+            }
+            Value::List(values) => {
+                let index = list_index(last, values.len())?;
+                values.remove(index);
+            }
+            _ => return Err(format!("a scalar has no child '{last}'")),
+        }
+        return Ok(1);
+    }
+    let target = child(current, last)?;
+    Ok(usize::from(redactor.apply(target)?))
+}
+
+fn child<'v>(value: &'v mut Value, key: &str) -> Result<&'v mut Value, String> {
+    match value {
+        Value::Object(values) => values.get_mut(key).ok_or_else(|| format!("no key '{key}'")),
+        Value::List(values) => {
+            let index = list_index(key, values.len())?;
+            Ok(&mut values[index])
+        }
+        _ => Err(format!("a scalar has no child '{key}'")),
+    }
+}
+
+fn list_index(key: &str, len: usize) -> Result<usize, String> {
+    let index = Some(key)
+        .filter(|key| key.bytes().all(|byte| byte.is_ascii_digit()))
+        .filter(|key| *key == "0" || !key.starts_with('0'))
+        .and_then(|key| key.parse::<usize>().ok())
+        .ok_or_else(|| format!("'{key}' is not a list index"))?;
+    if index >= len {
+        return Err(format!(
+            "index {index} is out of range for a list of {len} items"
+        ));
+    }
+    Ok(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::{RedactAction, RedactionRule, redact_document};
+    use crate::core::{Document, Origin, SourceFormat, SourceMeta, TopLevelShape, Value};
+
+    fn rule(column: Option<&str>, path: Option<&str>, action: RedactAction) -> RedactionRule {
+        RedactionRule {
+            column: column.map(str::to_string),
+            path: path.map(str::to_string),
+            action,
+            keep: None,
+            replacement: None,
+            salt: None,
+            salt_env: None,
+        }
+    }
+
+    fn people() -> Document {
+        let person = |name: &str, card: &str| {
+            Value::Object(IndexMap::from([
+                ("name".to_string(), Value::String(name.to_string())),
+                ("card".to_string(), Value::String(card.to_string())),
+                (
+                    "email".to_string(),
+                    Value::String(format!("{name}@example.com")),
+                ),
+            ]))
+        };
+        let root = Value::List(vec![person("ada", "4111222233334444"), person("bo", "55")]);
+        Document {
+            source_id: "people".to_string(),
+            meta: SourceMeta {
+                format: SourceFormat::Csv,
+                origin: Origin::Stdin,
+                top_level_shape: TopLevelShape::TabularRecords,
+                tabular_columns: Some(vec![
+                    "name".to_string(),
+                    "card".to_string(),
+                    "email".to_string(),
+                ]),
+                header_present: Some(true),
+                columns: Vec::new(),
+            },
+            root,
+        }
+    }
+
+    fn cells(doc: &Document, column: &str) -> Vec<Value> {
+        let Value::List(rows) = &doc.root else {
+            panic!("expected rows");
+        };
+        rows.iter()
+            .map(|row| match row {
+                Value::Object(values) => values[column].clone(),
+                _ => panic!("expected records"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn masks_hashes_and_drops_columns() {
+        let mut hash = rule(Some("name"), None, RedactAction::Hash);
+        hash.salt = Some("pepper".to_string());
+        let rules = [
+            rule(Some("card"), None, RedactAction::Mask),
+            hash.clone(),
+            rule(Some("email"), None, RedactAction::Drop),
+        ];
+        let (doc, report) = redact_document(people(), &rules).unwrap();
+        assert_eq!(
+            cells(&doc, "card"),
+            vec![
+                Value::String("************4444".to_string()),
+                Value::String("**".to_string())
+            ]
+        );
+        assert_eq!(
+            doc.meta.tabular_columns,
+            Some(vec!["name".to_string(), "card".to_string()])
+        );
+        assert_eq!(
+            report,
+            vec![
+                "column 'card': mask (2 value(s))",
+                "column 'name': hash (2 value(s))",
+                "column 'email': drop"
+            ]
+        );
+
+        let (again, _) = redact_document(people(), &[hash.clone()]).unwrap();
+        assert_eq!(cells(&doc, "name"), cells(&again, "name"));
+        hash.salt = Some("salt".to_string());
+        let (resalted, _) = redact_document(people(), &[hash]).unwrap();
+        assert_ne!(cells(&doc, "name"), cells(&resalted, "name"));
+        let Value::String(digest) = &cells(&doc, "name")[0] else {
+            panic!("expected a digest");
+        };
+        assert_eq!(digest.len(), 16);
+    }
+
+    #[test]
+    fn redacts_pointer_paths_and_rejects_missing_targets() {
+        let mut replace = rule(None, Some("/1/email"), RedactAction::Replace);
+        replace.replacement = Some("[redacted]".to_string());
+        let (doc, report) = redact_document(people(), &[replace]).unwrap();
+        assert_eq!(
+            cells(&doc, "email"),
+            vec![
+                Value::String("ada@example.com".to_string()),
+                Value::String("[redacted]".to_string())
+            ]
+        );
+        assert_eq!(report, vec!["/1/email: replace (1 value(s))"]);
+
+        let (doc, _) = redact_document(
+            people(),
+            &[rule(None, Some("/0/email"), RedactAction::Drop)],
+        )
+        .unwrap();
+        assert_eq!(
+            cells(&doc, "email"),
+            vec![Value::Null, Value::String("bo@example.com".to_string())]
+        );
+        let (doc, _) =
+            redact_document(people(), &[rule(None, Some("/0"), RedactAction::Drop)]).unwrap();
+        assert_eq!(cells(&doc, "name"), vec![Value::String("bo".to_string())]);
+
+        let error = redact_document(
+            people(),
+            &[rule(None, Some("/2/email"), RedactAction::Drop)],
+        )
+        .unwrap_err();
+        assert_eq!(
+            error,
+            (
+                "/2/email".to_string(),
+                "index 2 is out of range for a list of 2 items".to_string()
+            )
+        );
+        let error = redact_document(people(), &[rule(Some("phone"), None, RedactAction::Drop)])
+            .unwrap_err();
+        assert!(error.1.starts_with("unknown column 'phone'"));
+    }
+
+    #[test]
+    fn drops_matrix_cells_without_shifting_rows() {
+        let row = |cells: [i64; 3]| Value::List(cells.map(Value::Integer).to_vec());
+        let matrix = Document {
+            source_id: "grid".to_string(),
+            meta: SourceMeta {
+                format: SourceFormat::Csv,
+                origin: Origin::Stdin,
+                top_level_shape: TopLevelShape::TabularMatrix,
+                tabular_columns: None,
+                header_present: Some(false),
+                columns: Vec::new(),
+            },
+            root: Value::List(vec![row([1, 2, 3]), row([4, 5, 6])]),
+        };
+        let (doc, _) =
+            redact_document(matrix, &[rule(None, Some("/0/1"), RedactAction::Drop)]).unwrap();
+        assert_eq!(
+            doc.root,
+            Value::List(vec![
+                Value::List(vec![Value::Integer(1), Value::Null, Value::Integer(3)]),
+                row([4, 5, 6]),
+            ])
+        );
+    }
+
+    #[test]
+    fn validates_action_options() {
+        let mut hash = rule(Some("name"), None, RedactAction::Hash);
+        assert!(hash.validate().unwrap_err().contains("salt or salt_env"));
+        hash.salt = Some("pepper".to_string());
+        hash.keep = Some(2);
+        assert_eq!(
+            hash.validate().unwrap_err(),
+            "sets keep, which only applies to action 'mask'"
+        );
+        assert!(
+            rule(Some("a"), Some("/a"), RedactAction::Drop)
+                .validate()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,44 @@
+/// Picks `count` distinct row indexes out of `len`, returned in ascending
+/// order so sampled rows keep their source order.
+///
+/// Uses SplitMix64 and integer-only arithmetic, so a seed selects the same
+/// rows on every platform and release.
+pub(crate) fn sample_indexes(len: usize, count: usize, seed: u64) -> Vec<usize> {
+    if count >= len {
+        return (0..len).collect();
+    }
+    let mut state = seed;
+    let mut next = move || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut mixed = state;
+        mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        mixed ^ (mixed >> 31)
+    };
+    // Partial Fisher-Yates shuffle over the index range.
+    let mut indexes = (0..len).collect::<Vec<_>>();
+    for position in 0..count {
+        let remaining = (len - position) as u64;
+        let offset = ((u128::from(next()) * u128::from(remaining)) >> 64) as usize;
+        indexes.swap(position, position + offset);
+    }
+    indexes.truncate(count);
+    indexes.sort_unstable();
+    indexes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sample_indexes;
+
+    #[test]
+    fn samples_are_fixed_by_seed_and_ordered() {
+        assert_eq!(sample_indexes(10, 4, 7), sample_indexes(10, 4, 7));
+        assert_eq!(sample_indexes(10, 4, 7), vec![1, 3, 7, 9]);
+        assert_ne!(sample_indexes(1000, 5, 7), sample_indexes(1000, 5, 8));
+        assert_eq!(sample_indexes(3, 5, 1), vec![0, 1, 2]);
+        let many = sample_indexes(1000, 100, 42);
+        assert_eq!(many.len(), 100);
+        assert!(many.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
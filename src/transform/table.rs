@@ -0,0 +1,235 @@
+use indexmap::IndexMap;
+
+use crate::core::{ColumnMeta, Document, TopLevelShape, Value, infer_columns};
+
+/// Row-major view of a tabular document that transform steps edit in place.
+///
+/// Record tables address columns by header name; matrix tables address them
+/// by 1-based position, matching `SourceMeta.columns` naming.
+pub(crate) struct Table {
+    pub records: bool,
+    pub columns: Vec<ColumnMeta>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl Table {
+    /// Splits a tabular document into columns and rows.
+    pub fn from_document(doc: &Document) -> Result<Self, String> {
+        let Value::List(rows) = &doc.root else {
+            return Err(not_tabular(doc));
+        };
+        match (&doc.meta.top_level_shape, &doc.meta.tabular_columns) {
+            (TopLevelShape::TabularRecords, Some(names)) => {
+                let columns = names
+                    .iter()
+                    .map(|name| {
+                        doc.meta
+                            .column(name)
+                            .cloned()
+                            .unwrap_or_else(|| ColumnMeta::infer(name.clone(), []))
+                    })
+                    .collect();
+                let rows = rows
+                    .iter()
+                    .map(|row| match row {
+                        Value::Object(values) => names
+                            .iter()
+                            .map(|name| values.get(name).cloned().unwrap_or(Value::Null))
+                            .collect(),
+                        _ => vec![Value::Null; names.len()],
+                    })
+                    .collect();
+                Ok(Self {
+                    records: true,
+                    columns,
+                    rows,
+                })
+            }
+            (TopLevelShape::TabularMatrix, _) => {
+                let columns = if doc.meta.columns.is_empty() {
+                    infer_columns(&doc.root, None)
+                } else {
+                    doc.meta.columns.clone()
+                };
+                let rows = rows
+                    .iter()
+                    .map(|row| match row {
+                        Value::List(cells) => cells.clone(),
+                        other => vec![other.clone()],
+                    })
+                    .collect();
+                Ok(Self {
+                    records: false,
+                    columns,
+                    rows,
+                })
+            }
+            _ => Err(not_tabular(doc)),
+        }
+    }
+
+    /// Splits record rows out of a tabular source or a list of objects, whose
+    /// columns are the union of their keys in first-seen order.
+    pub fn from_records(doc: &Document) -> Result<Self, String> {
+        if doc.meta.top_level_shape == TopLevelShape::TabularRecords {
+            return Self::from_document(doc);
+        }
+        let records = match &doc.root {
+            Value::List(rows) => rows
+                .iter()
+                .map(|row| match row {
+                    Value::Object(values) => Some(values),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>(),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            format!(
+                "expected record rows (a tabular source or a list of objects), got {}",
+                doc.meta.top_level_shape
+            )
+        })?;
+        let mut names = IndexMap::new();
+        for record in &records {
+            for key in record.keys() {
+                names.entry(key.clone()).or_insert(());
+            }
+        }
+        Ok(Self {
+            records: true,
+            columns: names
+                .keys()
+                .map(|name| ColumnMeta::infer(name.clone(), []))
+                .collect(),
+            rows: records
+                .iter()
+                .map(|record| {
+                    names
+                        .keys()
+                        .map(|name| record.get(name).cloned().unwrap_or(Value::Null))
+                        .collect()
+                })
+                .collect(),
+        })
+    }
+
+    /// Writes the table back into `doc`, refreshing the inferred column
+    /// metadata while keeping user-supplied labels, units, and descriptions.
+    pub fn into_document(self, mut doc: Document) -> Document {
+        let names = self
+            .columns
+            .iter()
+            .map(|column| column.name.clone())
+            .collect::<Vec<_>>();
+        let rows = self
+            .rows
+            .into_iter()
+            .map(|cells| {
+                if self.records {
+                    Value::Object(names.iter().cloned().zip(cells).collect::<IndexMap<_, _>>())
+                } else {
+                    Value::List(cells)
+                }
+            })
+            .collect();
+        doc.root = Value::List(rows);
+        let mut columns = if self.records {
+            infer_columns(&doc.root, Some(&names))
+        } else {
+            // An empty matrix has no first row to measure, so keep the width.
+            let mut columns = infer_columns(&doc.root, None);
+            if columns.is_empty() {
+                columns = (1..=names.len())
+                    .map(|index| ColumnMeta::infer(index.to_string(), []))
+                    .collect();
+            }
+            columns
+        };
+        for (column, previous) in columns.iter_mut().zip(&self.columns) {
+            column.label = previous.label.clone();
+            column.unit = previous.unit.clone();
+            column.description = previous.description.clone();
+        }
+        doc.meta.top_level_shape = if self.records {
+            TopLevelShape::TabularRecords
+        } else {
+            TopLevelShape::TabularMatrix
+        };
+        doc.meta.tabular_columns = self.records.then_some(names);
+        doc.meta.columns = columns;
+        doc
+    }
+
+    /// Resolves a column reference to its position.
+    pub fn index_of(&self, column: &str) -> Result<usize, String> {
+        self.columns
+            .iter()
+            .position(|meta| meta.name == column)
+            .ok_or_else(|| {
+                format!(
+                    "unknown column '{column}'; available columns: {}",
+                    self.columns
+                        .iter()
+                        .map(|meta| meta.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+
+    /// Keeps only the columns at `indexes`, in that order.
+    pub fn project(&mut self, indexes: &[usize]) {
+        self.columns = indexes
+            .iter()
+            .map(|index| self.columns[*index].clone())
+            .collect();
+        for row in &mut self.rows {
+            *row = indexes.iter().map(|index| row[*index].clone()).collect();
+        }
+        if !self.records {
+            for (position, column) in self.columns.iter_mut().enumerate() {
+                column.name = (position + 1).to_string();
+            }
+        }
+    }
+}
+
+/// Lays out rows of cell text as a left-aligned plain-text table with a
+/// header rule, for terminal reports.
+pub(crate) fn text_table(header: &[String], rows: &[Vec<String>]) -> String {
+    let widths = (0..header.len())
+        .map(|position| {
+            std::iter::once(header)
+                .chain(rows.iter().map(Vec::as_slice))
+                .map(|line| line.get(position).map_or(0, |cell| cell.chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    let render = |line: &[String]| {
+        let cells = line
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>();
+        format!("{}\n", cells.join(" | ").trim_end())
+    };
+    let mut table = render(header);
+    let rule = widths
+        .iter()
+        .map(|width| "-".repeat(*width))
+        .collect::<Vec<_>>();
+    table.push_str(&format!("{}\n", rule.join("-+-")));
+    for line in rows {
+        table.push_str(&render(line));
+    }
+    table
+}
+
+fn not_tabular(doc: &Document) -> String {
+    format!(
+        "expected a tabular source, got {}",
+        doc.meta.top_level_shape
+    )
+}
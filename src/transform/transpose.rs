@@ -0,0 +1,69 @@
+use std::collections::HashSet;
+
+use crate::core::{ColumnMeta, Value};
+use crate::transform::table::Table;
+
+/// Swaps rows and columns.
+///
+/// A matrix without `header` or `key` transposes into a matrix. Otherwise the
+/// result holds records: a `key` column (default `column`) naming each former
+/// column, then one column per former row, named by that row's `header` cell
+/// or by its 1-based position.
+pub(crate) fn transpose(
+    table: &Table,
+    header: Option<&str>,
+    key: Option<&str>,
+) -> Result<Table, String> {
+    if !table.records && header.is_none() && key.is_none() {
+        let width = table.rows.len();
+        return Ok(Table {
+            records: false,
+            columns: (1..=width)
+                .map(|index| ColumnMeta::infer(index.to_string(), []))
+                .collect(),
+            rows: (0..table.columns.len())
+                .map(|column| table.rows.iter().map(|row| row[column].clone()).collect())
+                .collect(),
+        });
+    }
+
+    let key = key.unwrap_or("column");
+    let header = header.map(|column| table.index_of(column)).transpose()?;
+    let mut names = HashSet::from([key.to_string()]);
+    let mut columns = vec![ColumnMeta::infer(key.to_string(), [])];
+    for (row_index, row) in table.rows.iter().enumerate() {
+        let name = match header {
+            Some(index) => row[index]
+                .scalar_text()
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| {
+                    format!(
+                        "header column '{}' needs a non-empty scalar at row {row_index}",
+                        table.columns[index].name
+                    )
+                })?,
+            None => (row_index + 1).to_string(),
+        };
+        if !names.insert(name.clone()) {
+            return Err(format!("transposed column '{name}' would appear twice"));
+        }
+        columns.push(ColumnMeta::infer(name, []));
+    }
+
+    let rows = table
+        .columns
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| Some(*index) != header)
+        .map(|(index, column)| {
+            let mut row = vec![Value::String(column.name.clone())];
+            row.extend(table.rows.iter().map(|cells| cells[index].clone()));
+            row
+        })
+        .collect();
+    Ok(Table {
+        records: true,
+        columns,
+        rows,
+    })
+}
@@ -171,6 +171,90 @@ path = "people.typ"
     assert!(stderr.contains("only valid for csv or xlsx sources"));
 }
 
+#[test]
+fn build_rejects_exact_decimals_for_yaml_sources() {
+    let dir = temp_dir("exact-decimals-yaml");
+    let manifest = dir.join("docpack.toml");
+    let input = dir.join("data.yaml");
+
+    write_file(&input, "price: 12345678901234567.89\n");
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "prices"
+path = "data.yaml"
+exact_decimals = true
+
+[[outputs]]
+id = "prices_typst"
+source = "prices"
+path = "prices.typ"
+"#,
+    );
+
+    let output = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("exact_decimals"));
+    assert!(stderr.contains("only valid for csv or json sources (got yaml)"));
+}
+
+#[test]
+fn emit_inspect_and_validate_reject_exact_decimals_for_yaml_input() {
+    let dir = temp_dir("exact-decimals-yaml-flag");
+    let input = dir.join("data.yaml");
+    let schema = dir.join("schema.json");
+    write_file(&input, "price: 12345678901234567.89\n");
+    write_file(&schema, "{}");
+    let input = input.to_str().unwrap();
+
+    for args in [
+        vec!["emit", input, "--backend", "typst"],
+        vec!["inspect", input],
+        vec!["validate", input, "--schema", schema.to_str().unwrap()],
+    ] {
+        let output = Command::new(binary())
+            .args(args)
+            .arg("--exact-decimals")
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(
+            stderr.contains("--exact-decimals is only valid for csv or json sources (got yaml)"),
+            "{stderr}"
+        );
+    }
+}
+
+#[test]
+fn emit_and_inspect_reject_keep_numeric_text_for_toml_input() {
+    let dir = temp_dir("keep-numeric-text-toml-flag");
+    let input = dir.join("data.toml");
+    write_file(&input, "price = 1.50\n");
+    let input = input.to_str().unwrap();
+
+    for args in [
+        vec!["emit", input, "--backend", "typst"],
+        vec!["inspect", input],
+    ] {
+        let output = Command::new(binary())
+            .args(args)
+            .arg("--keep-numeric-text")
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(
+            stderr.contains("--keep-numeric-text is only valid for csv or json sources (got toml)"),
+            "{stderr}"
+        );
+    }
+}
+
 #[test]
 fn emit_xlsx_reports_missing_sheet() {
     let dir = temp_dir("xlsx-missing-sheet");
@@ -217,7 +301,163 @@ fn emit_reports_unsupported_output_extension_for_backend_inference() {
     assert!(!output.status.success());
     let stderr = String::from_utf8(output.stderr).unwrap();
     assert!(stderr.contains("extension '.txt' is not mapped to a backend"));
-    assert!(stderr.contains("use --backend or write to .typ/.tex"));
+    assert!(stderr.contains("use --backend or write to .typ/.tex/.md/.html/.adoc/.rst/.mkiv"));
+}
+
+#[test]
+fn build_infers_markdown_from_md_outputs() {
+    let dir = temp_dir("markdown-outputs");
+    let manifest = dir.join("docpack.toml");
+    write_file(
+        &dir.join("sales.csv"),
+        "region,amount\nEast,1234.5\nWest | North,-20\n",
+    );
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "sales"
+path = "sales.csv"
+
+[[outputs]]
+id = "sales_table"
+source = "sales"
+path = "sales-table.md"
+artifact = "table-fragment"
+column_formats = { amount = { decimals = 2, thousands = true } }
+
+[[outputs]]
+id = "sales_data"
+source = "sales"
+path = "sales.md"
+"#,
+    );
+
+    let output = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("sales-table.md")).unwrap(),
+        "| region | amount |\n| --- | ---: |\n| East | 1,234.50 |\n| West \\| North | -20.00 |\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("sales.md")).unwrap(),
+        "---\nsales:\n  - amount: 1234.5\n    region: \"East\"\n  - amount: -20\n    region: \"West | North\"\n---\n"
+    );
+}
+
+#[test]
+fn build_infers_html_from_html_outputs() {
+    let dir = temp_dir("html-outputs");
+    let manifest = dir.join("docpack.toml");
+    write_file(&dir.join("sales.csv"), "region,amount\nEast & Co,12\n");
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "sales"
+path = "sales.csv"
+
+[[outputs]]
+id = "sales_table"
+source = "sales"
+path = "sales-table.html"
+artifact = "table-fragment"
+css_class = "report"
+
+[[outputs]]
+id = "sales_data"
+source = "sales"
+path = "sales.html"
+"#,
+    );
+
+    let output = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("sales-table.html")).unwrap(),
+        "<table class=\"report\">\n  <thead>\n    <tr><th>region</th><th>amount</th></tr>\n  </thead>\n  <tbody>\n    <tr><td>East &amp; Co</td><td>12</td></tr>\n  </tbody>\n</table>\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("sales.html")).unwrap(),
+        "<script type=\"application/json\" id=\"sales\">\n[\n  {\n    \"amount\": 12,\n    \"region\": \"East & Co\"\n  }\n]\n</script>\n"
+    );
+
+    let inspect = Command::new(binary())
+        .args(["inspect", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(
+        String::from_utf8(inspect.stdout)
+            .unwrap()
+            .contains("    css_class: report\n")
+    );
+
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "sales"
+path = "sales.csv"
+
+[[outputs]]
+id = "sales_typst"
+source = "sales"
+path = "sales.typ"
+css_class = "report"
+"#,
+    );
+    let output = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("css_class only applies to html table-fragment output")
+    );
+}
+
+#[test]
+fn emit_infers_text_backends_from_output_extensions() {
+    let dir = temp_dir("text-backend-outputs");
+    let input = dir.join("sales.json");
+    write_file(&input, r#"{"total":42}"#);
+
+    for (file, expected) in [
+        ("sales.adoc", ":sales-total: 42\n"),
+        ("sales.rst", ".. |sales-total| replace:: 42\n"),
+        ("sales.mkiv", "\\setvariables[sales][\n  total={42},\n]\n"),
+        ("sales.mkxl", "\\setvariables[sales][\n  total={42},\n]\n"),
+    ] {
+        let path = dir.join(file);
+        let output = Command::new(binary())
+            .args([
+                "emit",
+                input.to_str().unwrap(),
+                "--output",
+                path.to_str().unwrap(),
+            ])
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert_eq!(fs::read_to_string(path).unwrap(), expected);
+    }
 }
 
 #[test]
@@ -225,7 +465,7 @@ fn emit_reports_non_tabular_table_fragment_request() {
     let dir = temp_dir("non-tabular-fragment");
     let input = dir.join("input.json");
 
-    write_file(&input, r#"{"name":"Alice"}"#);
+    write_file(&input, r#"["Alice", "Bob"]"#);
 
     let output = Command::new(binary())
         .args([
@@ -242,7 +482,7 @@ fn emit_reports_non_tabular_table_fragment_request() {
         .unwrap();
     assert!(!output.status.success());
     let stderr = String::from_utf8(output.stderr).unwrap();
-    assert!(stderr.contains("source shape is object"));
+    assert!(stderr.contains("source shape is list"));
     assert!(stderr.contains("use data-module instead"));
 }
 
@@ -269,3 +509,1232 @@ fn emit_reports_supported_styles_for_invalid_style_choice() {
     assert!(stderr.contains("style 'latex-expl3' is not valid for typst data-module"));
     assert!(stderr.contains("supported styles: typst-official"));
 }
+
+#[test]
+fn emit_key_order_source_keeps_declaration_order() {
+    let mut child = Command::new(binary())
+        .args([
+            "emit",
+            "-",
+            "--format",
+            "json",
+            "--backend",
+            "typst",
+            "--key-order",
+            "source",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(br#"{"name":"Alice","age":30}"#)
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout, "#let data = (\"name\": \"Alice\", \"age\": 30)\n");
+}
+
+#[test]
+fn emit_reports_parse_error_location_with_excerpt() {
+    let dir = temp_dir("parse-location");
+    let input = dir.join("config.yaml");
+    write_file(&input, "title: Report\nauthors: [Alice, Bob\nyear: 2025\n");
+
+    let output = Command::new(binary())
+        .args(["emit", input.to_str().unwrap(), "--backend", "typst"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("failed to parse yaml"));
+    assert!(stderr.contains("at line 3, column 5"));
+    assert!(stderr.contains("3 | year: 2025\n  |     ^\n"));
+}
+
+#[test]
+fn build_uses_manifest_column_annotations_in_table_headers() {
+    let dir = temp_dir("column-annotations");
+    let manifest = dir.join("docpack.toml");
+    write_file(&dir.join("sales.csv"), "region,amount\nEast,1200\nWest,\n");
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "sales"
+path = "sales.csv"
+
+[sources.columns.amount]
+label = "Amount"
+unit = "EUR"
+description = "Net revenue"
+
+[[outputs]]
+id = "sales_table"
+source = "sales"
+path = "sales.typ"
+artifact = "table-fragment"
+"#,
+    );
+
+    let status = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let built = fs::read_to_string(dir.join("sales.typ")).unwrap();
+    assert!(built.contains("table.header[region][Amount (EUR)],"));
+
+    let output = Command::new(binary())
+        .args(["inspect", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("      - region: string, nullable=false, max_width=4\n"));
+    assert!(stdout.contains(
+        "      - amount: integer, nullable=true, max_width=4, label=\"Amount\", unit=\"EUR\", description=\"Net revenue\"\n"
+    ));
+}
+
+#[test]
+fn build_rejects_annotations_for_unknown_columns() {
+    let dir = temp_dir("unknown-column-annotation");
+    let manifest = dir.join("docpack.toml");
+    write_file(&dir.join("sales.csv"), "region,amount\nEast,1200\n");
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "sales"
+path = "sales.csv"
+columns = { price = { unit = "EUR" } }
+
+[[outputs]]
+id = "sales_table"
+source = "sales"
+path = "sales.typ"
+"#,
+    );
+
+    let output = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("source 'sales' annotates unknown column 'price'"));
+}
+
+#[test]
+fn emit_select_re_roots_source_at_pointer() {
+    let dir = temp_dir("select");
+    let input = dir.join("company.yaml");
+    write_file(
+        &input,
+        "reports:\n  \"2025\":\n    q1:\n      revenue: 120\n      cost: 80\n",
+    );
+
+    let output = Command::new(binary())
+        .args([
+            "emit",
+            input.to_str().unwrap(),
+            "--backend",
+            "typst",
+            "--select",
+            "/reports/2025/q1",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout, "#let company = (\"cost\": 80, \"revenue\": 120)\n");
+
+    let output = Command::new(binary())
+        .args([
+            "emit",
+            input.to_str().unwrap(),
+            "--backend",
+            "typst",
+            "--select",
+            "/reports/2025/q3",
+        ])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("cannot select /reports/2025/q3 in "));
+    assert!(stderr.contains("no key 'q3'; deepest resolved path is /reports/2025"));
+}
+
+#[test]
+fn emit_tabular_promotes_lists_of_objects_to_table_fragments() {
+    let dir = temp_dir("tabular");
+    let input = dir.join("users.json");
+    write_file(
+        &input,
+        r#"{"data": [{"id": 1, "name": "Ada"}, {"id": 2, "email": "bo@example.com"}]}"#,
+    );
+
+    let output = Command::new(binary())
+        .args([
+            "emit",
+            input.to_str().unwrap(),
+            "--backend",
+            "typst",
+            "--artifact",
+            "table-fragment",
+            "--select",
+            "/data",
+            "--tabular",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "#table(\n  columns: 3,\n  table.header[id][name][email],\n  [1], [Ada], [],\n  [2], [], [#\"bo@example.com\"],\n)\n"
+    );
+
+    write_file(&input, r#"[{"id": 1}, "stray", {"id": 3, "tags": ["a"]}]"#);
+    let output = Command::new(binary())
+        .args(["inspect", input.to_str().unwrap(), "--tabular"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(
+        "as a table: /1 is a scalar, not an object; /2/tags is a list; table cells must be scalars"
+    ));
+}
+
+#[test]
+fn build_applies_output_transforms_before_rendering_tables() {
+    let dir = temp_dir("transforms");
+    let manifest = dir.join("docpack.toml");
+    write_file(
+        &dir.join("sales.csv"),
+        "region,rep,amount\nEast,Alice,120\nWest,Bob,90\nEast,Carol,300\nEast,Dan,\n",
+    );
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "sales"
+path = "sales.csv"
+
+[[outputs]]
+id = "east"
+source = "sales"
+path = "east.tex"
+artifact = "table-fragment"
+style = "latex-plain-tabular"
+
+[[outputs.transforms]]
+op = "filter"
+column = "region"
+operator = "eq"
+value = "East"
+
+[[outputs.transforms]]
+op = "sort"
+by = [{ column = "amount", order = "desc" }]
+
+[[outputs.transforms]]
+op = "select_columns"
+columns = ["rep", "amount"]
+
+[[outputs.transforms]]
+op = "rename"
+columns = { rep = "Representative" }
+"#,
+    );
+
+    let status = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let built = fs::read_to_string(dir.join("east.tex")).unwrap();
+    assert_eq!(
+        built,
+        "\\begin{tabular}{ll}\nRepresentative & amount \\\\\n\\hline\nCarol & 300 \\\\\nAlice & 120 \\\\\nDan &  \\\\\n\\end{tabular}\n"
+    );
+
+    let output = Command::new(binary())
+        .args(["inspect", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("    transforms: filter -> sort -> select_columns -> rename\n"));
+}
+
+#[test]
+fn build_rejects_filter_steps_without_a_value() {
+    let dir = temp_dir("transform-validation");
+    let manifest = dir.join("docpack.toml");
+    write_file(&dir.join("sales.csv"), "region,amount\nEast,120\n");
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "sales"
+path = "sales.csv"
+
+[[outputs]]
+id = "east"
+source = "sales"
+path = "east.typ"
+
+[[outputs.transforms]]
+op = "filter"
+column = "region"
+operator = "eq"
+"#,
+    );
+
+    let output = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(
+        "output 'east' transform step 1 (filter) uses operator 'eq', which requires a value"
+    ));
+}
+
+#[test]
+fn build_derives_computed_columns() {
+    let dir = temp_dir("derive");
+    let manifest = dir.join("docpack.toml");
+    write_file(
+        &dir.join("pnl.csv"),
+        "unit,revenue,cost\nNorth,200,150\nSouth,,80\n",
+    );
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "pnl"
+path = "pnl.csv"
+
+[[outputs]]
+id = "margins"
+source = "pnl"
+path = "margins.typ"
+artifact = "table-fragment"
+
+[[outputs.transforms]]
+op = "derive"
+column = "margin"
+expr = "round((revenue - cost) / revenue, 2) ?? 'n/a'"
+
+[[outputs.transforms]]
+op = "select_columns"
+columns = ["unit", "margin"]
+"#,
+    );
+
+    let status = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let built = fs::read_to_string(dir.join("margins.typ")).unwrap();
+    assert_eq!(
+        built,
+        "#table(\n  columns: 2,\n  table.header[unit][margin],\n  [North], [0.25],\n  [South], [n/a],\n)\n"
+    );
+}
+
+#[test]
+fn build_pivots_transactions_into_summary_tables() {
+    let dir = temp_dir("pivot");
+    let manifest = dir.join("docpack.toml");
+    write_file(
+        &dir.join("orders.csv"),
+        "region,quarter,amount\nEast,Q1,100\nWest,Q1,40\nEast,Q2,60\nEast,Q1,25\n",
+    );
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "orders"
+path = "orders.csv"
+
+[[outputs]]
+id = "by_quarter"
+source = "orders"
+path = "by_quarter.tex"
+artifact = "table-fragment"
+
+[[outputs.transforms]]
+op = "pivot"
+index = ["region"]
+columns = "quarter"
+values = "amount"
+fill = 0
+
+[[outputs]]
+id = "totals"
+source = "orders"
+path = "totals.typ"
+artifact = "table-fragment"
+
+[[outputs.transforms]]
+op = "group_by"
+by = ["region"]
+aggregates = [
+  { column = "amount", function = "sum", as = "total" },
+  { function = "count", as = "orders" },
+]
+"#,
+    );
+
+    let status = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let pivot = fs::read_to_string(dir.join("by_quarter.tex")).unwrap();
+    assert!(pivot.contains("region & Q1 & Q2 \\\\\n"));
+    assert!(pivot.contains("East & 125 & 60 \\\\\nWest & 40 & 0 \\\\\n"));
+    let totals = fs::read_to_string(dir.join("totals.typ")).unwrap();
+    assert_eq!(
+        totals,
+        "#table(\n  columns: 3,\n  table.header[region][total][orders],\n  [East], [185], [3],\n  [West], [40], [1],\n)\n"
+    );
+}
+
+#[test]
+fn build_joins_sources_and_warns_about_unmatched_keys() {
+    let dir = temp_dir("join");
+    let manifest = dir.join("docpack.toml");
+    write_file(
+        &dir.join("orders.csv"),
+        "region,amount\nEast,100\nNorth,40\nWest,60\n",
+    );
+    write_file(
+        &dir.join("regions.json"),
+        r#"[{"code": "West", "manager": "Wu"}, {"code": "East", "manager": "Eve"}]"#,
+    );
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "orders"
+path = "orders.csv"
+
+[[sources]]
+id = "regions"
+path = "regions.json"
+
+[[sources]]
+id = "staffed"
+columns = { manager = { label = "Manager" } }
+
+[sources.join]
+left = "orders"
+right = "regions"
+on = ["region"]
+right_on = ["code"]
+how = "left"
+
+[[outputs]]
+id = "staffed"
+source = "staffed"
+path = "staffed.typ"
+artifact = "table-fragment"
+"#,
+    );
+
+    let output = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "warning: source 'staffed': 1 row(s) of 'orders' have no match in 'regions': (North)\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("staffed.typ")).unwrap(),
+        "#table(\n  columns: 3,\n  table.header[region][amount][Manager],\n  [East], [100], [Eve],\n  [North], [40], [],\n  [West], [60], [Wu],\n)\n"
+    );
+
+    let inspect = Command::new(binary())
+        .args(["inspect", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(inspect.stdout).unwrap();
+    assert!(stdout.contains(
+        "  - staffed: left join of orders and regions (format: csv, shape: tabular-records)"
+    ));
+    assert!(stdout.contains("Warnings\n  - source 'staffed': 1 row(s)"));
+}
+
+#[test]
+fn build_fails_on_unmatched_join_keys_when_configured() {
+    let dir = temp_dir("join-strict");
+    let manifest = dir.join("docpack.toml");
+    write_file(&dir.join("a.csv"), "id,x\n1,a\n2,b\n");
+    write_file(&dir.join("b.csv"), "id,y\n1,c\n3,d\n");
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "a"
+path = "a.csv"
+
+[[sources]]
+id = "b"
+path = "b.csv"
+
+[[sources]]
+id = "ab"
+join = { left = "a", right = "b", on = ["id"], how = "full", unmatched = "error" }
+
+[[outputs]]
+id = "ab"
+source = "ab"
+path = "ab.typ"
+"#,
+    );
+
+    let output = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "error: cannot derive source 'ab': 1 row(s) of 'a' have no match in 'b': (2); 1 row(s) of 'b' have no match in 'a': (3)\n"
+    );
+}
+
+#[test]
+fn build_rejects_join_cycles_and_file_options_on_derived_sources() {
+    let dir = temp_dir("join-invalid");
+    let manifest = dir.join("docpack.toml");
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "a"
+format = "csv"
+join = { left = "b", right = "b", on = ["id"] }
+
+[[sources]]
+id = "b"
+join = { left = "a", right = "missing", on = ["id"] }
+"#,
+    );
+
+    let output = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(
+        "source 'a' sets format, but that option only applies to file sources, not derived ones"
+    ));
+    assert!(stderr.contains("source 'b' joins missing right source 'missing'"));
+    assert!(stderr.contains("source 'a' depends on itself through derived sources"));
+}
+
+#[test]
+fn build_deep_merges_layered_sources() {
+    let dir = temp_dir("merge");
+    let manifest = dir.join("docpack.toml");
+    write_file(
+        &dir.join("defaults.yaml"),
+        "title: Handbook\nprint:\n  paper: a4\n  duplex: true\nchapters:\n  - id: intro\n    pages: 4\n  - id: setup\n    pages: 9\n",
+    );
+    write_file(
+        &dir.join("edition.toml"),
+        "edition = 2\n\n[print]\npaper = \"letter\"\n\n[[chapters]]\nid = \"setup\"\npages = 12\n",
+    );
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "defaults"
+path = "defaults.yaml"
+
+[[sources]]
+id = "edition"
+path = "edition.toml"
+
+[[sources]]
+id = "handbook"
+merge = { layers = ["defaults", "edition"], lists = "merge_by_key", key = "id" }
+
+[[outputs]]
+id = "handbook"
+source = "handbook"
+path = "handbook.typ"
+key_order = "source"
+"#,
+    );
+
+    let status = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert_eq!(
+        fs::read_to_string(dir.join("handbook.typ")).unwrap(),
+        "#let handbook = (\"title\": \"Handbook\", \"print\": (\"paper\": \"letter\", \"duplex\": true), \"chapters\": ((\"id\": \"intro\", \"pages\": 4), (\"id\": \"setup\", \"pages\": 12)), \"edition\": 2)\n"
+    );
+
+    write_file(&dir.join("local.yaml"), "print: duplex\n");
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "defaults"
+path = "defaults.yaml"
+
+[[sources]]
+id = "local"
+path = "local.yaml"
+
+[[sources]]
+id = "handbook"
+merge = { layers = ["defaults", "local"] }
+
+[[outputs]]
+id = "handbook"
+source = "handbook"
+path = "handbook.typ"
+"#,
+    );
+    let output = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "error: cannot merge layer 'local' into source 'handbook': /print is an object in earlier layers but a string in 'local'\n"
+    );
+}
+
+#[test]
+fn build_flattens_nested_json_records_into_tables() {
+    let dir = temp_dir("flatten");
+    let manifest = dir.join("docpack.toml");
+    write_file(
+        &dir.join("people.json"),
+        r#"[
+  {"name": "Ada", "address": {"city": "Oslo"}, "roles": ["admin", "ops"]},
+  {"name": "Bo", "address": {"city": "Rome"}, "roles": ["dev"]}
+]"#,
+    );
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "people"
+path = "people.json"
+
+[[outputs]]
+id = "people"
+source = "people"
+path = "people.tex"
+artifact = "table-fragment"
+
+[[outputs.transforms]]
+op = "flatten"
+lists = "explode"
+"#,
+    );
+
+    let status = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let table = fs::read_to_string(dir.join("people.tex")).unwrap();
+    assert!(table.contains("name & address.city & roles \\\\\n"));
+    assert!(
+        table.contains("Ada & Oslo & admin \\\\\nAda & Oslo & ops \\\\\nBo & Rome & dev \\\\\n")
+    );
+}
+
+#[test]
+fn build_transposes_tables_around_a_header_column() {
+    let dir = temp_dir("transpose");
+    let manifest = dir.join("docpack.toml");
+    write_file(
+        &dir.join("plans.csv"),
+        "plan,price,seats\nBasic,10,1\nTeam,50,10\n",
+    );
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "plans"
+path = "plans.csv"
+
+[[outputs]]
+id = "plans"
+source = "plans"
+path = "plans.typ"
+artifact = "table-fragment"
+
+[[outputs.transforms]]
+op = "transpose"
+header = "plan"
+key = "feature"
+"#,
+    );
+
+    let status = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert_eq!(
+        fs::read_to_string(dir.join("plans.typ")).unwrap(),
+        "#table(\n  columns: 3,\n  table.header[feature][Basic][Team],\n  [price], [10], [50],\n  [seats], [1], [10],\n)\n"
+    );
+}
+
+#[test]
+fn build_applies_locale_aware_column_formats_to_table_cells() {
+    let dir = temp_dir("column-formats");
+    let manifest = dir.join("docpack.toml");
+    write_file(
+        &dir.join("sales.csv"),
+        "region,amount,share,closed\nEast,1234567.5,0.30000000000000004,2025-03-07\nWest,,0.125,\n",
+    );
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "sales"
+path = "sales.csv"
+column_types = { closed = "date" }
+
+[[outputs]]
+id = "sales_typst"
+source = "sales"
+path = "sales.typ"
+artifact = "table-fragment"
+locale = "de"
+
+[outputs.column_formats]
+amount = { decimals = 2, thousands = true, suffix = " €", null = "–" }
+share = { decimals = 1, percent = true }
+closed = { date = "%d.%m.%Y", null = "offen" }
+
+[[outputs]]
+id = "sales_latex"
+source = "sales"
+path = "sales.tex"
+artifact = "table-fragment"
+column_formats = { amount = { decimals = 0, thousands = true, prefix = "$" }, share = { percent = true } }
+"#,
+    );
+
+    let output = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("sales.typ")).unwrap(),
+        "#table(\n  columns: 4,\n  table.header[region][amount][share][closed],\n  [East], [#\"1.234.567,50 €\"], [#\"30,0\u{a0}%\"], [07.03.2025],\n  [West], [#\"–\"], [#\"12,5\u{a0}%\"], [offen],\n)\n"
+    );
+    let latex = fs::read_to_string(dir.join("sales.tex")).unwrap();
+    assert!(latex.contains("East & \\$1,234,568 & 30.000000000000004\\% & 2025-03-07 \\\\\n"));
+    assert!(latex.contains("West &  & 12.5\\% &  \\\\\n"));
+}
+
+#[test]
+fn build_rejects_column_formats_for_unknown_columns() {
+    let dir = temp_dir("column-formats-unknown");
+    let manifest = dir.join("docpack.toml");
+    write_file(&dir.join("sales.csv"), "region,amount\nEast,1\nWest,2\n");
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "sales"
+path = "sales.csv"
+
+[[outputs]]
+id = "sales"
+source = "sales"
+path = "sales.typ"
+artifact = "table-fragment"
+column_formats = { amout = { decimals = 2 } }
+"#,
+    );
+
+    let output = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("column format for unknown column 'amout'")
+    );
+}
+
+#[test]
+fn build_redacts_sensitive_fields_before_rendering() {
+    let dir = temp_dir("redact");
+    let manifest = dir.join("docpack.toml");
+    write_file(
+        &dir.join("customers.csv"),
+        "name,card,email,tier\nAda,4111222233334444,ada@example.com,gold\nBo,5500,bo@example.com,silver\n",
+    );
+    write_file(
+        &dir.join("account.json"),
+        r#"{"owner": {"name": "Ada", "ssn": "123-45-6789"}, "plan": "pro"}"#,
+    );
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "customers"
+path = "customers.csv"
+
+[[sources.redact]]
+column = "card"
+action = "mask"
+
+[[sources.redact]]
+column = "email"
+action = "drop"
+
+[[sources]]
+id = "account"
+path = "account.json"
+
+[[outputs]]
+id = "customers"
+source = "customers"
+path = "customers.typ"
+artifact = "table-fragment"
+
+[[outputs.redact]]
+column = "name"
+action = "hash"
+salt_env = "DOCPACK_TEST_SALT"
+
+[[outputs]]
+id = "account"
+source = "account"
+path = "account.typ"
+key_order = "source"
+
+[[outputs.redact]]
+path = "/owner/ssn"
+action = "replace"
+replacement = "[redacted]"
+"#,
+    );
+
+    let build = || {
+        Command::new(binary())
+            .args(["build", manifest.to_str().unwrap()])
+            .env("DOCPACK_TEST_SALT", "pepper")
+            .output()
+            .unwrap()
+    };
+    let output = build();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let customers = fs::read_to_string(dir.join("customers.typ")).unwrap();
+    assert!(customers.contains("table.header[name][card][tier]"));
+    assert!(customers.contains("[#\"************4444\"], [gold]"));
+    assert!(customers.contains("[#\"****\"], [silver]"));
+    assert!(!customers.contains("Ada") && !customers.contains("example.com"));
+    assert_eq!(
+        fs::read_to_string(dir.join("account.typ")).unwrap(),
+        "#let account = (\"owner\": (\"name\": \"Ada\", \"ssn\": \"[redacted]\"), \"plan\": \"pro\")\n"
+    );
+    assert!(build().status.success());
+    assert_eq!(
+        fs::read_to_string(dir.join("customers.typ")).unwrap(),
+        customers
+    );
+
+    let inspect = Command::new(binary())
+        .args(["inspect", manifest.to_str().unwrap()])
+        .env("DOCPACK_TEST_SALT", "pepper")
+        .output()
+        .unwrap();
+    let report = String::from_utf8(inspect.stdout).unwrap();
+    assert!(report.contains(
+        "    redacted:\n      - column 'card': mask (2 value(s))\n      - column 'email': drop\n"
+    ));
+    assert!(report.contains("    redacted:\n      - column 'name': hash (2 value(s))\n"));
+    assert!(report.contains("    redacted:\n      - /owner/ssn: replace (1 value(s))\n"));
+
+    let manifest_text = fs::read_to_string(&manifest).unwrap();
+    write_file(
+        &manifest,
+        &manifest_text.replace("/owner/ssn", "/owner/tax_id"),
+    );
+    let output = build();
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "error: cannot redact /owner/tax_id in output 'account': no key 'tax_id'\n"
+    );
+}
+
+#[test]
+fn build_and_validate_check_sources_against_json_schemas() {
+    let dir = temp_dir("schema");
+    let manifest = dir.join("docpack.toml");
+    write_file(
+        &dir.join("schemas/sales.schema.json"),
+        r#"{
+  "type": "array",
+  "items": {
+    "type": "object",
+    "required": ["region", "amount"],
+    "properties": {
+      "region": { "enum": ["East", "West"] },
+      "amount": { "type": "number", "minimum": 0 }
+    }
+  }
+}"#,
+    );
+    write_file(
+        &dir.join("sales.csv"),
+        "region,amount\nEast,120.5\nWest,80\n",
+    );
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "sales"
+path = "sales.csv"
+schema = "schemas/sales.schema.json"
+
+[[outputs]]
+id = "sales"
+source = "sales"
+path = "sales.typ"
+"#,
+    );
+
+    let output = Command::new(binary())
+        .args(["validate", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!(
+            "source 'sales' matches schema {}\n",
+            dir.join("schemas/sales.schema.json").display()
+        )
+    );
+    assert!(
+        Command::new(binary())
+            .args(["build", manifest.to_str().unwrap()])
+            .status()
+            .unwrap()
+            .success()
+    );
+
+    write_file(
+        &dir.join("sales.csv"),
+        "region,amount\nEast,-3\nNorth,n/a\n",
+    );
+    let output = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        format!(
+            "error: 1 of 1 source(s) do not match their schema:\nsource 'sales': {} does not match schema {}:\n- /0/amount: -3 is less than the minimum of 0\n- /1/region: \"North\" is not one of [\"East\",\"West\"]\n- /1/amount: \"n/a\" is not of type \"number\"\n",
+            dir.join("sales.csv").display(),
+            dir.join("schemas/sales.schema.json").display()
+        )
+    );
+
+    let schema = dir.join("schemas/sales.schema.json");
+    let output = Command::new(binary())
+        .args([
+            "validate",
+            dir.join("sales.csv").to_str().unwrap(),
+            "--schema",
+            schema.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("- /0/amount: -3 is less than the minimum of 0\n")
+    );
+
+    write_file(&dir.join("sales.csv"), "region,amount\nEast,3\n");
+    let output = Command::new(binary())
+        .args([
+            "validate",
+            dir.join("sales.csv").to_str().unwrap(),
+            "--schema",
+            schema.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!(
+            "{} matches schema {}\n",
+            dir.join("sales.csv").display(),
+            schema.display()
+        )
+    );
+}
+
+#[test]
+fn build_and_validate_report_schema_violations_from_every_source() {
+    let dir = temp_dir("schema-all");
+    let manifest = dir.join("docpack.toml");
+    let schema = dir.join("amounts.schema.json");
+    write_file(
+        &schema,
+        r#"{ "type": "array", "items": { "properties": { "amount": { "minimum": 0 } } } }"#,
+    );
+    write_file(&dir.join("east.csv"), "amount\n-1\n");
+    write_file(&dir.join("north.csv"), "amount\n4\n");
+    write_file(&dir.join("west.csv"), "amount\n5\n-2\n");
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "east"
+path = "east.csv"
+schema = "amounts.schema.json"
+
+[[sources]]
+id = "north"
+path = "north.csv"
+schema = "amounts.schema.json"
+
+[[sources]]
+id = "west"
+path = "west.csv"
+schema = "amounts.schema.json"
+
+[[outputs]]
+id = "east"
+source = "east"
+path = "east.typ"
+"#,
+    );
+
+    for command in ["validate", "build"] {
+        let output = Command::new(binary())
+            .args([command, manifest.to_str().unwrap()])
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        assert_eq!(
+            String::from_utf8(output.stderr).unwrap(),
+            format!(
+                "error: 2 of 3 source(s) do not match their schema:\nsource 'east': {} does not match schema {}:\n- /0/amount: -1 is less than the minimum of 0\nsource 'west': {} does not match schema {}:\n- /1/amount: -2 is less than the minimum of 0\n",
+                dir.join("east.csv").display(),
+                schema.display(),
+                dir.join("west.csv").display(),
+                schema.display()
+            )
+        );
+    }
+    assert!(!dir.join("east.typ").exists());
+}
+
+#[test]
+fn build_runs_data_checks_before_writing_outputs() {
+    let dir = temp_dir("checks");
+    let manifest = dir.join("docpack.toml");
+    write_file(
+        &dir.join("sales.csv"),
+        "id,region,amount\n1,East,120\n2,West,\n2,North,-4\n",
+    );
+    let manifest_text = r#"[[sources]]
+id = "sales"
+path = "sales.csv"
+
+[[outputs]]
+id = "sales"
+source = "sales"
+path = "sales.typ"
+
+[[checks]]
+source = "sales"
+column = "id"
+check = "unique"
+
+[[checks]]
+source = "sales"
+column = "amount"
+check = "range"
+min = 0
+
+[[checks]]
+source = "sales"
+column = "region"
+check = "allowed_values"
+values = ["East", "West", "North"]
+
+[[checks]]
+source = "sales"
+check = "row_count"
+min = 1
+max = 100
+"#;
+    write_file(&manifest, manifest_text);
+
+    let output = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(!dir.join("sales.typ").exists());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "error: 2 of 4 data check(s) failed:\n\
+         unique on sales.id: 2 of 3 row(s) fail\n\
+         row | id | region | amount\n\
+         ----+----+--------+-------\n\
+         1   | 2  | West   |\n\
+         2   | 2  | North  | -4\n\
+         \n\
+         range on sales.amount: 1 of 3 row(s) fail\n\
+         row | id | region | amount\n\
+         ----+----+--------+-------\n\
+         2   | 2  | North  | -4\n"
+    );
+
+    let inspect = Command::new(binary())
+        .args(["inspect", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(inspect.status.success());
+    assert!(String::from_utf8(inspect.stdout).unwrap().contains(
+        "Checks\n  - unique on sales.id: failed, 2 of 3 row(s) fail\n  - range on sales.amount: failed, 1 of 3 row(s) fail\n  - allowed_values on sales.region: passed\n  - row_count on sales: passed\n"
+    ));
+
+    write_file(
+        &dir.join("sales.csv"),
+        "id,region,amount\n1,East,120\n2,West,\n3,North,4\n",
+    );
+    let status = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert!(dir.join("sales.typ").exists());
+
+    write_file(&manifest, &manifest_text.replace("column = \"id\"\n", ""));
+    let output = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("- check 1 (unique) needs a column")
+    );
+}
+
+#[test]
+fn emit_inspect_and_build_sample_rows_deterministically() {
+    let dir = temp_dir("sampling");
+    let input = dir.join("big.csv");
+    let mut csv = String::from("id,value\n");
+    for id in 0..50 {
+        csv.push_str(&format!("{id},{}\n", id * 10));
+    }
+    write_file(&input, &csv);
+
+    let emit = |flags: &[&str]| {
+        let output = Command::new(binary())
+            .args(["emit", input.to_str().unwrap(), "--backend", "typst"])
+            .args(["--artifact", "table-fragment"])
+            .args(flags)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8(output.stdout).unwrap()
+    };
+    assert_eq!(
+        emit(&["--head", "2"]),
+        "#table(\n  columns: 2,\n  table.header[id][value],\n  [0], [0],\n  [1], [10],\n)\n"
+    );
+    assert_eq!(
+        emit(&["--every", "20", "--tail", "2"]),
+        "#table(\n  columns: 2,\n  table.header[id][value],\n  [20], [200],\n  [40], [400],\n)\n"
+    );
+    let sampled = emit(&["--sample", "3", "--seed", "7"]);
+    assert_eq!(sampled.matches("\n  [").count(), 3);
+    assert_eq!(emit(&["--sample", "3", "--seed", "7"]), sampled);
+    assert_ne!(emit(&["--sample", "3", "--seed", "8"]), sampled);
+    for command in ["emit", "inspect"] {
+        let help = Command::new(binary())
+            .args([command, "--help"])
+            .output()
+            .unwrap();
+        let help = String::from_utf8(help.stdout).unwrap();
+        assert!(help.contains("--every <N>"));
+        assert!(!help.contains("Row sampling"));
+    }
+
+    let inspect = Command::new(binary())
+        .args(["inspect", input.to_str().unwrap(), "--backend", "typst"])
+        .output()
+        .unwrap();
+    assert!(String::from_utf8(inspect.stdout).unwrap().contains(
+        "Preview\n  row | id | value\n  ----+----+------\n  0   | 0  | 0\n  1   | 1  | 10\n  2   | 2  | 20\n  3   | 3  | 30\n  4   | 4  | 40\n  ... and 45 more row(s)\n\n"
+    ));
+
+    let manifest = dir.join("docpack.toml");
+    write_file(
+        &manifest,
+        r#"[[sources]]
+id = "big"
+path = "big.csv"
+
+[[outputs]]
+id = "appendix"
+source = "big"
+path = "appendix.typ"
+artifact = "table-fragment"
+
+[[outputs.transforms]]
+op = "sample"
+count = 3
+seed = 7
+"#,
+    );
+    let status = Command::new(binary())
+        .args(["build", manifest.to_str().unwrap()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert_eq!(
+        fs::read_to_string(dir.join("appendix.typ")).unwrap(),
+        sampled
+    );
+}
@@ -1,8 +1,8 @@
 mod common;
 
 use docpack::{
-    ArtifactKind, BackendKind, DocpackError, Origin, RenderRequest, SourceFormat, SourceSpec,
-    parse_source, render_document,
+    ArtifactKind, BackendKind, DocpackError, KeyOrder, NonFinitePolicy, Origin, ParseOptions,
+    RenderOptions, RenderRequest, SourceFormat, SourceSpec, parse_source, render_document,
 };
 
 use common::make_xlsx;
@@ -18,12 +18,18 @@ fn json_object_matches_reference_outputs() {
         &doc,
         "#let data = (\"active\": true, \"age\": 30, \"name\": \"Alice\")\n",
     );
-    assert_table_error(&doc, BackendKind::Typst);
+    assert_typst_table(
+        &doc,
+        "#table(\n  columns: 2,\n  table.header[Key][Value],\n  [active], [true],\n  [age], [30],\n  [name], [Alice],\n)\n",
+    );
     assert_latex_data(
         &doc,
         "\\ExplSyntaxOn\n\\prop_new:N \\g_docpack_data_prop\n\\prop_gput:Nnn \\g_docpack_data_prop {active} {true}\n\\prop_gput:Nnn \\g_docpack_data_prop {age} {30}\n\\prop_gput:Nnn \\g_docpack_data_prop {name} {Alice}\n\\ExplSyntaxOff\n",
     );
-    assert_table_error(&doc, BackendKind::Latex);
+    assert_latex_table(
+        &doc,
+        "\\begin{longtable}{ll}\n\\toprule\nKey & Value \\\\\n\\midrule\n\\endfirsthead\n\\toprule\nKey & Value \\\\\n\\midrule\n\\endhead\nactive & true \\\\\nage & 30 \\\\\nname & Alice \\\\\n\\bottomrule\n\\end{longtable}\n",
+    );
 }
 
 #[test]
@@ -48,14 +54,40 @@ fn toml_datetime_matches_reference_outputs() {
 
     assert_typst_data(
         &doc,
-        "#let data = (\"title\": \"Report\", \"when\": \"2025-01-01T12:00:00Z\")\n",
+        "#let data = (\"title\": \"Report\", \"when\": datetime(year: 2025, month: 1, day: 1, hour: 12, minute: 0, second: 0))\n",
+    );
+    assert_typst_table(
+        &doc,
+        "#table(\n  columns: 2,\n  table.header[Key][Value],\n  [title], [Report],\n  [when], [2025-01-01T12:00:00Z],\n)\n",
     );
-    assert_table_error(&doc, BackendKind::Typst);
     assert_latex_data(
         &doc,
         "\\ExplSyntaxOn\n\\prop_new:N \\g_docpack_data_prop\n\\prop_gput:Nnn \\g_docpack_data_prop {title} {Report}\n\\prop_gput:Nnn \\g_docpack_data_prop {when} {2025-01-01T12:00:00Z}\n\\ExplSyntaxOff\n",
     );
-    assert_table_error(&doc, BackendKind::Latex);
+    assert_latex_table(
+        &doc,
+        "\\begin{longtable}{ll}\n\\toprule\nKey & Value \\\\\n\\midrule\n\\endfirsthead\n\\toprule\nKey & Value \\\\\n\\midrule\n\\endhead\ntitle & Report \\\\\nwhen & 2025-01-01T12:00:00Z \\\\\n\\bottomrule\n\\end{longtable}\n",
+    );
+    let shifted = parse_text_fixture(
+        SourceFormat::Toml,
+        b"local = 2025-01-01T12:00:00\nwhen = 2025-01-01T01:30:00+02:00\n",
+    );
+    assert_typst_data(
+        &shifted,
+        "#let data = (\"local\": datetime(year: 2025, month: 1, day: 1, hour: 12, minute: 0, second: 0), \"when\": datetime(year: 2024, month: 12, day: 31, hour: 23, minute: 30, second: 0))\n",
+    );
+
+    let precise = parse_text_fixture(
+        SourceFormat::Toml,
+        b"[log]\nwhen = 2025-01-01T12:00:00.250Z\n",
+    );
+    match render_document(&precise, &typst_request(ArtifactKind::DataModule)).unwrap_err() {
+        DocpackError::Render { detail, .. } => assert_eq!(
+            detail,
+            "Typst datetimes have no sub-second precision, but /log/when holds 2025-01-01T12:00:00.25Z"
+        ),
+        other => panic!("expected render error, got {other}"),
+    }
 }
 
 #[test]
@@ -149,12 +181,34 @@ fn nested_object_matches_reference_outputs() {
         &doc,
         "#let data = (\"profile\": (\"name\": \"Alice\", \"tags\": (\"a\", \"b\")))\n",
     );
-    assert_table_error(&doc, BackendKind::Typst);
+    // Key-value tables flatten nested objects, but list values stay nested.
+    assert_table_error_detail(
+        &doc,
+        BackendKind::Typst,
+        "table-fragment only accepts scalar cell values",
+    );
     assert_latex_data(
         &doc,
         "\\ExplSyntaxOn\n\\prop_new:N \\g_docpack_data_prop\n\\prop_gput:Nnn \\g_docpack_data_prop {profile/name} {Alice}\n\\prop_gput:Nnn \\g_docpack_data_prop {profile/tags/1} {a}\n\\prop_gput:Nnn \\g_docpack_data_prop {profile/tags/2} {b}\n\\prop_gput:Nnn \\g_docpack_data_prop {profile/tags/__len__} {2}\n\\ExplSyntaxOff\n",
     );
-    assert_table_error(&doc, BackendKind::Latex);
+    assert_table_error_detail(
+        &doc,
+        BackendKind::Latex,
+        "table-fragment only accepts scalar cell values",
+    );
+}
+
+#[test]
+fn nested_object_renders_as_dotted_key_value_table() {
+    let doc = parse_text_fixture(
+        SourceFormat::Yaml,
+        b"name: Box\ndimensions:\n  width: 10\n  height: 20\n",
+    );
+
+    assert_typst_table(
+        &doc,
+        "#table(\n  columns: 2,\n  table.header[Key][Value],\n  [dimensions.height], [20],\n  [dimensions.width], [10],\n  [name], [Box],\n)\n",
+    );
 }
 
 #[test]
@@ -170,6 +224,373 @@ fn nested_array_matches_reference_outputs() {
     assert_table_error(&doc, BackendKind::Latex);
 }
 
+#[test]
+fn source_key_order_follows_declaration_order() {
+    let doc = parse_text_fixture(
+        SourceFormat::Yaml,
+        b"title: Report\nsubtitle: Q1\nauthors:\n  - Alice\n  - Bob\n",
+    );
+    let mut typst = typst_request(ArtifactKind::DataModule);
+    typst.options.key_order = KeyOrder::Source;
+    assert_eq!(
+        render_document(&doc, &typst).unwrap().body,
+        "#let data = (\"title\": \"Report\", \"subtitle\": \"Q1\", \"authors\": (\"Alice\", \"Bob\"))\n"
+    );
+    let mut latex = latex_request(ArtifactKind::DataModule);
+    latex.options.key_order = KeyOrder::Source;
+    assert_eq!(
+        render_document(&doc, &latex).unwrap().body,
+        "\\ExplSyntaxOn\n\\prop_new:N \\g_docpack_data_prop\n\\prop_gput:Nnn \\g_docpack_data_prop {title} {Report}\n\\prop_gput:Nnn \\g_docpack_data_prop {subtitle} {Q1}\n\\prop_gput:Nnn \\g_docpack_data_prop {authors/1} {Alice}\n\\prop_gput:Nnn \\g_docpack_data_prop {authors/2} {Bob}\n\\prop_gput:Nnn \\g_docpack_data_prop {authors/__len__} {2}\n\\ExplSyntaxOff\n"
+    );
+
+    let records = parse_text_fixture(SourceFormat::Csv, b"name,age\nAlice,30\nBob,25\n");
+    let mut typst = typst_request(ArtifactKind::DataModule);
+    typst.options.key_order = KeyOrder::Source;
+    assert_eq!(
+        render_document(&records, &typst).unwrap().body,
+        "#let data = ((\"name\": \"Alice\", \"age\": 30), (\"name\": \"Bob\", \"age\": 25))\n"
+    );
+}
+
+#[test]
+fn exact_decimals_match_reference_outputs() {
+    let spec = SourceSpec::from_stdin(
+        "data",
+        SourceFormat::Csv,
+        b"item,amount\nfee,0.10\nreserve,98765432109876543210\n".to_vec(),
+        false,
+        None,
+    )
+    .with_options(ParseOptions {
+        exact_decimals: true,
+        ..ParseOptions::default()
+    });
+    let doc = parse_source(&spec).unwrap();
+
+    assert_typst_data(
+        &doc,
+        "#let data = ((\"amount\": decimal(\"0.10\"), \"item\": \"fee\"), (\"amount\": decimal(\"98765432109876543210\"), \"item\": \"reserve\"))\n",
+    );
+    assert_typst_table(
+        &doc,
+        "#table(\n  columns: 2,\n  table.header[item][amount],\n  [fee], [0.10],\n  [reserve], [98765432109876543210],\n)\n",
+    );
+    assert_latex_data(
+        &doc,
+        "\\ExplSyntaxOn\n\\prop_new:N \\g_docpack_data_prop\n\\prop_gput:Nnn \\g_docpack_data_prop {1/item} {fee}\n\\prop_gput:Nnn \\g_docpack_data_prop {1/amount} {0.10}\n\\prop_gput:Nnn \\g_docpack_data_prop {2/item} {reserve}\n\\prop_gput:Nnn \\g_docpack_data_prop {2/amount} {98765432109876543210}\n\\prop_gput:Nnn \\g_docpack_data_prop {__len__} {2}\n\\ExplSyntaxOff\n",
+    );
+
+    let spec = SourceSpec::from_stdin(
+        "data",
+        SourceFormat::Json,
+        b"{\"rate\": 0.123456789012345678901234567890}".to_vec(),
+        false,
+        None,
+    )
+    .with_options(ParseOptions {
+        exact_decimals: true,
+        ..ParseOptions::default()
+    });
+    let precise = parse_source(&spec).unwrap();
+    match render_document(&precise, &typst_request(ArtifactKind::DataModule)).unwrap_err() {
+        DocpackError::Render { detail, .. } => assert_eq!(
+            detail,
+            "Typst decimals hold at most 28 significant digits, but /rate holds 0.123456789012345678901234567890"
+        ),
+        other => panic!("expected render error, got {other}"),
+    }
+    assert_typst_table(
+        &precise,
+        "#table(\n  columns: 2,\n  table.header[Key][Value],\n  [rate], [0.123456789012345678901234567890],\n)\n",
+    );
+}
+
+#[test]
+fn numeric_source_text_matches_reference_outputs() {
+    let spec = SourceSpec::from_stdin(
+        "data",
+        SourceFormat::Csv,
+        b"price,code,count\n1.50,007,1e3\n2,8,9\n".to_vec(),
+        false,
+        None,
+    )
+    .with_options(ParseOptions {
+        keep_numeric_text: true,
+        ..ParseOptions::default()
+    });
+    let doc = parse_source(&spec).unwrap();
+
+    assert_typst_data(
+        &doc,
+        "#let data = ((\"code\": 7, \"count\": 1000, \"price\": 1.5), (\"code\": 8, \"count\": 9, \"price\": 2))\n",
+    );
+    assert_typst_table(
+        &doc,
+        "#table(\n  columns: 3,\n  table.header[price][code][count],\n  [1.50], [007], [1e3],\n  [2], [8], [9],\n)\n",
+    );
+    assert_latex_data(
+        &doc,
+        "\\ExplSyntaxOn\n\\prop_new:N \\g_docpack_data_prop\n\\prop_gput:Nnn \\g_docpack_data_prop {1/price} {1.5}\n\\prop_gput:Nnn \\g_docpack_data_prop {1/code} {7}\n\\prop_gput:Nnn \\g_docpack_data_prop {1/count} {1000}\n\\prop_gput:Nnn \\g_docpack_data_prop {2/price} {2}\n\\prop_gput:Nnn \\g_docpack_data_prop {2/code} {8}\n\\prop_gput:Nnn \\g_docpack_data_prop {2/count} {9}\n\\prop_gput:Nnn \\g_docpack_data_prop {__len__} {2}\n\\ExplSyntaxOff\n",
+    );
+    assert_latex_table(
+        &doc,
+        "\\begin{longtable}{lll}\n\\toprule\nprice & code & count \\\\\n\\midrule\n\\endfirsthead\n\\toprule\nprice & code & count \\\\\n\\midrule\n\\endhead\n1.50 & 007 & 1e3 \\\\\n2 & 8 & 9 \\\\\n\\bottomrule\n\\end{longtable}\n",
+    );
+}
+
+#[test]
+fn non_finite_floats_are_rejected_by_default() {
+    let doc = parse_text_fixture(SourceFormat::Csv, b"metric,value\nratio,NaN\nceiling,inf\n");
+
+    for request in [
+        typst_request(ArtifactKind::DataModule),
+        typst_request(ArtifactKind::TableFragment),
+        latex_request(ArtifactKind::DataModule),
+        latex_request(ArtifactKind::TableFragment),
+    ] {
+        match render_document(&doc, &request).unwrap_err() {
+            DocpackError::Render { detail, .. } => {
+                assert!(detail.starts_with("non-finite float NaN at /0/value"));
+            }
+            other => panic!("expected render error, got {other}"),
+        }
+    }
+}
+
+#[test]
+fn non_finite_floats_render_natively_when_requested() {
+    let doc = parse_text_fixture(SourceFormat::Csv, b"metric,value\nratio,NaN\nfloor,-inf\n");
+
+    let mut typst = typst_request(ArtifactKind::DataModule);
+    typst.options.non_finite = NonFinitePolicy::Native;
+    assert_eq!(
+        render_document(&doc, &typst).unwrap().body,
+        "#let data = ((\"metric\": \"ratio\", \"value\": float.nan), (\"metric\": \"floor\", \"value\": -float.inf))\n"
+    );
+    let mut typst = typst_request(ArtifactKind::TableFragment);
+    typst.options.non_finite = NonFinitePolicy::Native;
+    assert_eq!(
+        render_document(&doc, &typst).unwrap().body,
+        "#table(\n  columns: 2,\n  table.header[metric][value],\n  [ratio], [NaN],\n  [floor], [-inf],\n)\n"
+    );
+    let mut latex = latex_request(ArtifactKind::TableFragment);
+    latex.options.non_finite = NonFinitePolicy::Native;
+    assert!(
+        render_document(&doc, &latex)
+            .unwrap()
+            .body
+            .contains("ratio & NaN \\\\\nfloor & -inf \\\\\n")
+    );
+    let mut html = html_request(ArtifactKind::DataModule);
+    html.options.non_finite = NonFinitePolicy::Native;
+    match render_document(&doc, &html).unwrap_err() {
+        DocpackError::Render { detail, .. } => assert_eq!(
+            detail,
+            "JSON has no spelling for non-finite float NaN at /0/value; set non_finite to \"null\""
+        ),
+        other => panic!("expected render error, got {other}"),
+    }
+}
+
+#[test]
+fn non_finite_floats_render_as_null_when_requested() {
+    let doc = parse_text_fixture(SourceFormat::Csv, b"metric,value\nratio,NaN\nceiling,inf\n");
+
+    let mut typst = typst_request(ArtifactKind::DataModule);
+    typst.options.non_finite = NonFinitePolicy::Null;
+    assert_eq!(
+        render_document(&doc, &typst).unwrap().body,
+        "#let data = ((\"metric\": \"ratio\", \"value\": none), (\"metric\": \"ceiling\", \"value\": none))\n"
+    );
+    let mut latex = latex_request(ArtifactKind::DataModule);
+    latex.options.non_finite = NonFinitePolicy::Null;
+    assert!(
+        render_document(&doc, &latex)
+            .unwrap()
+            .body
+            .contains("{1/value} {none}")
+    );
+    let mut latex = latex_request(ArtifactKind::TableFragment);
+    latex.options.non_finite = NonFinitePolicy::Null;
+    assert!(
+        render_document(&doc, &latex)
+            .unwrap()
+            .body
+            .contains("ratio &  \\\\\nceiling &  \\\\\n")
+    );
+}
+
+#[test]
+fn markdown_matches_reference_outputs() {
+    let doc = parse_text_fixture(
+        SourceFormat::Csv,
+        b"name,amount,note\nAlice,12.50,\"a|b\"\nBob,3,\"line\nbreak\"\n",
+    );
+    assert_markdown_data(
+        &doc,
+        "---\ndata:\n  - amount: 12.5\n    name: \"Alice\"\n    note: \"a|b\"\n  - amount: 3\n    name: \"Bob\"\n    note: \"line\\nbreak\"\n---\n",
+    );
+    assert_markdown_table(
+        &doc,
+        "| name | amount | note |\n| --- | ---: | --- |\n| Alice | 12.5 | a\\|b |\n| Bob | 3 | line<br>break |\n",
+    );
+
+    let spec = SourceSpec::from_stdin(
+        "data",
+        SourceFormat::Csv,
+        b"Alice,30\nBob,25\n".to_vec(),
+        true,
+        None,
+    );
+    let matrix = parse_source(&spec).unwrap();
+    assert_markdown_table(
+        &matrix,
+        "|  |  |\n| --- | ---: |\n| Alice | 30 |\n| Bob | 25 |\n",
+    );
+
+    let nested = parse_text_fixture(
+        SourceFormat::Json,
+        br#"{"title":"Report","matrix":[[1,2],[]],"rows":[{"id":1,"tags":["a"]}],"yes":null}"#,
+    );
+    assert_markdown_data(
+        &nested,
+        "---\ndata:\n  matrix:\n    - - 1\n      - 2\n    - []\n  rows:\n    - id: 1\n      tags:\n        - \"a\"\n  title: \"Report\"\n  \"yes\": null\n---\n",
+    );
+
+    let list = parse_text_fixture(SourceFormat::Yaml, b"- alpha\n- beta\n");
+    assert_table_error(&list, BackendKind::Markdown);
+}
+
+#[test]
+fn html_matches_reference_outputs() {
+    let spec = SourceSpec::from_stdin(
+        "data",
+        SourceFormat::Csv,
+        b"name,amount,note\nA&B,12.50,\"</script>\"\nBob,3,\"line\nbreak\"\n".to_vec(),
+        false,
+        None,
+    )
+    .with_options(ParseOptions {
+        exact_decimals: true,
+        ..ParseOptions::default()
+    });
+    let doc = parse_source(&spec).unwrap();
+    assert_html_data(
+        &doc,
+        "<script type=\"application/json\" id=\"data\">\n[\n  {\n    \"amount\": 12.50,\n    \"name\": \"A&B\",\n    \"note\": \"\\u003c/script>\"\n  },\n  {\n    \"amount\": 3,\n    \"name\": \"Bob\",\n    \"note\": \"line\\nbreak\"\n  }\n]\n</script>\n",
+    );
+    assert_html_table(
+        &doc,
+        "<table>\n  <thead>\n    <tr><th>name</th><th>amount</th><th>note</th></tr>\n  </thead>\n  <tbody>\n    <tr><td>A&amp;B</td><td>12.50</td><td>&lt;/script&gt;</td></tr>\n    <tr><td>Bob</td><td>3</td><td>line<br>break</td></tr>\n  </tbody>\n</table>\n",
+    );
+
+    let mut styled = html_request(ArtifactKind::TableFragment);
+    styled.options.css_class = Some("report".to_string());
+    assert!(
+        render_document(&doc, &styled)
+            .unwrap()
+            .body
+            .starts_with("<table class=\"report\">\n")
+    );
+
+    let list = parse_text_fixture(SourceFormat::Yaml, b"- alpha\n- beta\n");
+    assert_table_error(&list, BackendKind::Html);
+}
+
+#[test]
+fn asciidoc_and_rst_match_reference_outputs() {
+    let doc = parse_text_fixture(
+        SourceFormat::Csv,
+        b"name,amount,note\nA. Smith,12.5,\"*bold* a|b\"\n-Bob,3,\"line\nbreak\"\n",
+    );
+    assert_asciidoc_table(
+        &doc,
+        "[%header,cols=\"1,>1,1\"]\n|===\n|name |amount |note\n\n|A. Smith |12.5 |pass:c[*bold* a\\|b]\n|-Bob |3 |line +\nbreak\n|===\n",
+    );
+    assert_rst_table(
+        &doc,
+        ".. list-table::\n   :header-rows: 1\n\n   * - name\n     - amount\n     - note\n   * - \\A. Smith\n     - 12.5\n     - \\*bold\\* a\\|b\n   * - \\-Bob\n     - 3\n     - | line\n       | break\n",
+    );
+
+    let object = parse_text_fixture(
+        SourceFormat::Json,
+        br#"{"total":42,"note":"see {total}","empty":"","tags":["a_b"]}"#,
+    );
+    assert_asciidoc_data(
+        &object,
+        ":data-empty:\n:data-note: see \\{total}\n:data-tags-1: a_b\n:data-tags-__len__: 1\n:data-total: 42\n",
+    );
+    assert_rst_data(
+        &object,
+        ".. |data-empty| replace:: \\ \n.. |data-note| replace:: see {total}\n.. |data-tags-1| replace:: a\\_b\n.. |data-tags-__len__| replace:: 1\n.. |data-total| replace:: 42\n",
+    );
+
+    let clashing = parse_text_fixture(SourceFormat::Json, br#"{"Total":1,"total":2}"#);
+    for request in [
+        asciidoc_request(ArtifactKind::DataModule),
+        rst_request(ArtifactKind::DataModule),
+    ] {
+        match render_document(&clashing, &request).unwrap_err() {
+            DocpackError::Render { detail, .. } => assert_eq!(
+                detail,
+                "'/Total' and '/total' both become the name 'data-total'; rename one of them"
+            ),
+            other => panic!("expected render error, got {other}"),
+        }
+    }
+
+    let list = parse_text_fixture(SourceFormat::Yaml, b"- alpha\n- beta\n");
+    assert_table_error(&list, BackendKind::Asciidoc);
+    assert_table_error(&list, BackendKind::Rst);
+}
+
+#[test]
+fn context_matches_reference_outputs() {
+    let doc = parse_text_fixture(
+        SourceFormat::Csv,
+        b"name,note\nAlice,a|b_c\nBob,\"100% {x}\"\n",
+    );
+    assert_context_data(
+        &doc,
+        "\\setvariables[data][\n  1-name={Alice},\n  1-note={a\\letterbar{}b\\_c},\n  2-name={Bob},\n  2-note={100\\% \\{x\\}},\n  __len__={2},\n]\n",
+    );
+    assert_context_table(
+        &doc,
+        "\\startxtable\n\\startxtablehead\n\\startxrow \\startxcell name \\stopxcell \\startxcell note \\stopxcell \\stopxrow\n\\stopxtablehead\n\\startxtablebody\n\\startxrow \\startxcell Alice \\stopxcell \\startxcell a\\letterbar{}b\\_c \\stopxcell \\stopxrow\n\\startxrow \\startxcell Bob \\stopxcell \\startxcell 100\\% \\{x\\} \\stopxcell \\stopxrow\n\\stopxtablebody\n\\stopxtable\n",
+    );
+
+    let mut natural = context_request(ArtifactKind::TableFragment);
+    natural.style = "context-natural-table".to_string();
+    assert_eq!(
+        render_document(&doc, &natural).unwrap().body,
+        "\\bTABLE\n\\bTABLEhead\n\\bTR \\bTH name \\eTH \\bTH note \\eTH \\eTR\n\\eTABLEhead\n\\bTABLEbody\n\\bTR \\bTD Alice \\eTD \\bTD a\\letterbar{}b\\_c \\eTD \\eTR\n\\bTR \\bTD Bob \\eTD \\bTD 100\\% \\{x\\} \\eTD \\eTR\n\\eTABLEbody\n\\eTABLE\n"
+    );
+
+    let clashing = parse_text_fixture(SourceFormat::Json, br#"{"a b":1,"a-b":2}"#);
+    let mut bad_namespace = context_request(ArtifactKind::DataModule);
+    bad_namespace.root_name = "data][x".to_string();
+    for (doc, request, expected) in [
+        (
+            &clashing,
+            context_request(ArtifactKind::DataModule),
+            "'/a b' and '/a-b' both become the name 'a-b'; rename one of them",
+        ),
+        (
+            &doc,
+            bad_namespace,
+            "root name 'data][x' is not a valid \\setvariables namespace; use ASCII letters, digits, '_', or '-'",
+        ),
+    ] {
+        match render_document(doc, &request).unwrap_err() {
+            DocpackError::Render { detail, .. } => assert_eq!(detail, expected),
+            other => panic!("expected render error, got {other}"),
+        }
+    }
+
+    let list = parse_text_fixture(SourceFormat::Yaml, b"- alpha\n- beta\n");
+    assert_table_error(&list, BackendKind::Context);
+}
+
 fn parse_text_fixture(format: SourceFormat, bytes: &[u8]) -> docpack::Document {
     let spec = SourceSpec::from_stdin("data", format, bytes.to_vec(), false, None);
     parse_source(&spec).unwrap()
@@ -195,18 +616,78 @@ fn assert_latex_table(doc: &docpack::Document, expected: &str) {
     assert_eq!(rendered.body, expected);
 }
 
+fn assert_markdown_data(doc: &docpack::Document, expected: &str) {
+    let rendered = render_document(doc, &markdown_request(ArtifactKind::DataModule)).unwrap();
+    assert_eq!(rendered.body, expected);
+}
+
+fn assert_markdown_table(doc: &docpack::Document, expected: &str) {
+    let rendered = render_document(doc, &markdown_request(ArtifactKind::TableFragment)).unwrap();
+    assert_eq!(rendered.body, expected);
+}
+
+fn assert_html_data(doc: &docpack::Document, expected: &str) {
+    let rendered = render_document(doc, &html_request(ArtifactKind::DataModule)).unwrap();
+    assert_eq!(rendered.body, expected);
+}
+
+fn assert_html_table(doc: &docpack::Document, expected: &str) {
+    let rendered = render_document(doc, &html_request(ArtifactKind::TableFragment)).unwrap();
+    assert_eq!(rendered.body, expected);
+}
+
+fn assert_asciidoc_data(doc: &docpack::Document, expected: &str) {
+    let rendered = render_document(doc, &asciidoc_request(ArtifactKind::DataModule)).unwrap();
+    assert_eq!(rendered.body, expected);
+}
+
+fn assert_asciidoc_table(doc: &docpack::Document, expected: &str) {
+    let rendered = render_document(doc, &asciidoc_request(ArtifactKind::TableFragment)).unwrap();
+    assert_eq!(rendered.body, expected);
+}
+
+fn assert_rst_data(doc: &docpack::Document, expected: &str) {
+    let rendered = render_document(doc, &rst_request(ArtifactKind::DataModule)).unwrap();
+    assert_eq!(rendered.body, expected);
+}
+
+fn assert_rst_table(doc: &docpack::Document, expected: &str) {
+    let rendered = render_document(doc, &rst_request(ArtifactKind::TableFragment)).unwrap();
+    assert_eq!(rendered.body, expected);
+}
+
+fn assert_context_data(doc: &docpack::Document, expected: &str) {
+    let rendered = render_document(doc, &context_request(ArtifactKind::DataModule)).unwrap();
+    assert_eq!(rendered.body, expected);
+}
+
+fn assert_context_table(doc: &docpack::Document, expected: &str) {
+    let rendered = render_document(doc, &context_request(ArtifactKind::TableFragment)).unwrap();
+    assert_eq!(rendered.body, expected);
+}
+
 fn assert_table_error(doc: &docpack::Document, backend: BackendKind) {
+    assert_table_error_detail(
+        doc,
+        backend,
+        "artifact table-fragment requires tabular source metadata or an object",
+    );
+}
+
+fn assert_table_error_detail(doc: &docpack::Document, backend: BackendKind, expected: &str) {
     let request = match backend {
         BackendKind::Typst => typst_request(ArtifactKind::TableFragment),
         BackendKind::Latex => latex_request(ArtifactKind::TableFragment),
+        BackendKind::Markdown => markdown_request(ArtifactKind::TableFragment),
+        BackendKind::Html => html_request(ArtifactKind::TableFragment),
+        BackendKind::Asciidoc => asciidoc_request(ArtifactKind::TableFragment),
+        BackendKind::Rst => rst_request(ArtifactKind::TableFragment),
+        BackendKind::Context => context_request(ArtifactKind::TableFragment),
     };
     let error = render_document(doc, &request).unwrap_err();
     match error {
         DocpackError::Render { detail, .. } => {
-            assert_eq!(
-                detail,
-                "artifact table-fragment requires tabular source metadata"
-            );
+            assert_eq!(detail, expected);
         }
         other => panic!("expected render error, got {other}"),
     }
@@ -221,6 +702,7 @@ fn typst_request(artifact: ArtifactKind) -> RenderRequest {
             ArtifactKind::TableFragment => "typst-table".to_string(),
         },
         root_name: "data".to_string(),
+        options: RenderOptions::default(),
     }
 }
 
@@ -233,6 +715,72 @@ fn latex_request(artifact: ArtifactKind) -> RenderRequest {
             ArtifactKind::TableFragment => "latex-booktabs-longtable".to_string(),
         },
         root_name: "data".to_string(),
+        options: RenderOptions::default(),
+    }
+}
+
+fn markdown_request(artifact: ArtifactKind) -> RenderRequest {
+    RenderRequest {
+        backend: BackendKind::Markdown,
+        artifact,
+        style: match artifact {
+            ArtifactKind::DataModule => "markdown-front-matter".to_string(),
+            ArtifactKind::TableFragment => "markdown-gfm".to_string(),
+        },
+        root_name: "data".to_string(),
+        options: RenderOptions::default(),
+    }
+}
+
+fn html_request(artifact: ArtifactKind) -> RenderRequest {
+    RenderRequest {
+        backend: BackendKind::Html,
+        artifact,
+        style: match artifact {
+            ArtifactKind::DataModule => "html-json-script".to_string(),
+            ArtifactKind::TableFragment => "html-table".to_string(),
+        },
+        root_name: "data".to_string(),
+        options: RenderOptions::default(),
+    }
+}
+
+fn asciidoc_request(artifact: ArtifactKind) -> RenderRequest {
+    RenderRequest {
+        backend: BackendKind::Asciidoc,
+        artifact,
+        style: match artifact {
+            ArtifactKind::DataModule => "asciidoc-attributes".to_string(),
+            ArtifactKind::TableFragment => "asciidoc-table".to_string(),
+        },
+        root_name: "data".to_string(),
+        options: RenderOptions::default(),
+    }
+}
+
+fn rst_request(artifact: ArtifactKind) -> RenderRequest {
+    RenderRequest {
+        backend: BackendKind::Rst,
+        artifact,
+        style: match artifact {
+            ArtifactKind::DataModule => "rst-substitutions".to_string(),
+            ArtifactKind::TableFragment => "rst-list-table".to_string(),
+        },
+        root_name: "data".to_string(),
+        options: RenderOptions::default(),
+    }
+}
+
+fn context_request(artifact: ArtifactKind) -> RenderRequest {
+    RenderRequest {
+        backend: BackendKind::Context,
+        artifact,
+        style: match artifact {
+            ArtifactKind::DataModule => "context-setvariables".to_string(),
+            ArtifactKind::TableFragment => "context-xtable".to_string(),
+        },
+        root_name: "data".to_string(),
+        options: RenderOptions::default(),
     }
 }
 